@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use cleopatra_cairo::cairo_run;
+use cleopatra_cairo::cairo_run::{self, CairoRunConfig};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 const BENCH_NAMES: &'static [&'static str] = &[
@@ -11,22 +11,36 @@ const BENCH_NAMES: &'static [&'static str] = &[
     "linear_search",
 ];
 const BENCH_PATH: &'static str = "cairo_programs/benchmarks/";
+// Hand-assembled versions of the benchmarks above, which don't need cairo-compile
+const MANUALLY_COMPILED_BENCH_NAMES: &[&str] = &["factorial_multirun", "fibonacci_1000_multirun"];
+const MANUALLY_COMPILED_BENCH_PATH: &str = "cairo_programs/benchmarks/manually_compiled/";
 
 pub fn criterion_benchmarks(c: &mut Criterion) {
-    for benchmark_name in build_bench_strings() {
+    let benchmarks = build_bench_strings(BENCH_NAMES, BENCH_PATH)
+        .into_iter()
+        .chain(build_bench_strings(
+            MANUALLY_COMPILED_BENCH_NAMES,
+            MANUALLY_COMPILED_BENCH_PATH,
+        ));
+    for benchmark_name in benchmarks {
         c.bench_function(&benchmark_name.0, |b| {
-            b.iter(|| cairo_run::cairo_run(black_box(Path::new(&benchmark_name.1)), false))
+            b.iter(|| {
+                cairo_run::cairo_run(
+                    black_box(Path::new(&benchmark_name.1)),
+                    &CairoRunConfig::default(),
+                )
+            })
         });
     }
 }
 
-fn build_bench_strings() -> Vec<(String, String)> {
+fn build_bench_strings(names: &[&str], path: &str) -> Vec<(String, String)> {
     let mut full_string = Vec::<(String, String)>::new();
 
-    for filename in BENCH_NAMES {
+    for filename in names {
         let file_no_extension = String::from(*filename);
         let file_extension = String::from(".json");
-        let bench_path = String::from(BENCH_PATH);
+        let bench_path = String::from(path);
         let cairo_call = String::from("cairo_run(");
         let full_file_path = bench_path + &file_no_extension + &file_extension;
         full_string.push((cairo_call + &full_file_path.clone(), full_file_path));
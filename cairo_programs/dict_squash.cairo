@@ -0,0 +1,30 @@
+%builtins range_check
+
+from starkware.cairo.common.dict import dict_read, dict_write, dict_update, dict_squash
+from starkware.cairo.common.default_dict import default_dict_new
+from starkware.cairo.common.dict_access import DictAccess
+
+func main{range_check_ptr}() -> ():
+    alloc_locals
+    #Create default dict
+    let (local my_dict_start : DictAccess*) = default_dict_new(17)
+    let my_dict = my_dict_start
+    #Write and update values
+    dict_write{dict_ptr=my_dict}(key=1, new_value=2)
+    dict_update{dict_ptr=my_dict}(key=1, prev_value=2, new_value=3)
+    dict_write{dict_ptr=my_dict}(key=5, new_value=8)
+    let (local val : felt) = dict_read{dict_ptr=my_dict}(key=10)
+    assert val = 17
+    #Squash the dict
+    let (squashed_dict_start, squashed_dict_end) = dict_squash(my_dict_start, my_dict)
+    #Keys are sorted in the squashed dict
+    assert squashed_dict_end - squashed_dict_start = 3 * DictAccess.SIZE
+    assert squashed_dict_start[0] = DictAccess(key=1, prev_value=17, new_value=3)
+    assert squashed_dict_start[1] = DictAccess(key=5, prev_value=17, new_value=8)
+    assert squashed_dict_start[2] = DictAccess(key=10, prev_value=17, new_value=17)
+    #The squashed dict can be used as a regular dict
+    let my_squashed_dict = squashed_dict_end
+    let (local val2 : felt) = dict_read{dict_ptr=my_squashed_dict}(key=5)
+    assert val2 = 8
+    return ()
+end
@@ -0,0 +1,38 @@
+%builtins range_check
+from starkware.cairo.common.find_element import search_sorted_lower
+from starkware.cairo.common.alloc import alloc
+
+struct MyStruct:
+    member a : felt
+    member b : felt
+end
+
+func main{range_check_ptr}() -> ():
+    # Create an array with MyStruct elements sorted by key: (1,2), (3,4), (5,6).
+    alloc_locals
+    let (local array_ptr : MyStruct*) = alloc()
+    assert array_ptr[0] = MyStruct(a=1, b=2)
+    assert array_ptr[1] = MyStruct(a=3, b=4)
+    assert array_ptr[2] = MyStruct(a=5, b=6)
+
+    # Find the first element with key >= 2.
+    let (element_ptr : MyStruct*) = search_sorted_lower(
+        array_ptr=array_ptr,
+        elm_size=MyStruct.SIZE,
+        n_elms=3,
+        key=2,
+    )
+    assert element_ptr.a = 3
+    assert element_ptr.b = 4
+
+    # No element has key >= 6, so a pointer to the end of the array is returned.
+    let (end_ptr : MyStruct*) = search_sorted_lower(
+        array_ptr=array_ptr,
+        elm_size=MyStruct.SIZE,
+        n_elms=3,
+        key=6,
+    )
+    assert end_ptr = array_ptr + 3 * MyStruct.SIZE
+
+    return ()
+end
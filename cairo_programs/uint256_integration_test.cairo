@@ -0,0 +1,45 @@
+%builtins range_check
+
+from starkware.cairo.common.uint256 import (
+    Uint256,
+    uint256_add,
+    uint256_sub,
+    uint256_sqrt,
+    uint256_signed_nn,
+    uint256_unsigned_div_rem,
+    uint256_mul_div_mod,
+)
+
+func main{range_check_ptr: felt}():
+    let a = Uint256(89, 72)
+    let b = Uint256(3, 7)
+
+    let (sub) = uint256_sub(a, b)
+    assert sub.low = 86
+    assert sub.high = 65
+
+    let (root) = uint256_sqrt(Uint256(17, 7))
+    assert root.low = 48805497317890012913
+    assert root.high = 0
+
+    let (signed_nn) = uint256_signed_nn(a)
+    assert signed_nn = 1
+    let (signed_nn) = uint256_signed_nn(Uint256(1, 2 ** 127))
+    assert signed_nn = 0
+
+    let (quotient, remainder) = uint256_unsigned_div_rem(a, b)
+    assert quotient.low = 10
+    assert quotient.high = 0
+    assert remainder.low = 59
+    assert remainder.high = 2
+
+    let (quotient_low, quotient_high, remainder) = uint256_mul_div_mod(
+        Uint256(89, 72), Uint256(3, 7), Uint256(107, 114))
+    assert quotient_low.low = 143276786071974089879315624181797141668
+    assert quotient_low.high = 4
+    assert quotient_high.low = 0
+    assert quotient_high.high = 0
+    assert remainder.low = 322372768661941702228460154409043568767
+    assert remainder.high = 101
+    return ()
+end
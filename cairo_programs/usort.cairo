@@ -0,0 +1,25 @@
+%builtins range_check
+from starkware.cairo.common.usort import usort
+from starkware.cairo.common.alloc import alloc
+
+func main{range_check_ptr}() -> ():
+    alloc_locals
+    let (input_arr : felt*) = alloc()
+    assert input_arr[0] = 2
+    assert input_arr[1] = 1
+    assert input_arr[2] = 2
+    assert input_arr[3] = 3
+    assert input_arr[4] = 1
+    assert input_arr[5] = 2
+
+    let (output_len, output, multiplicities) = usort(input_len=6, input=input_arr)
+
+    assert output_len = 3
+    assert output[0] = 1
+    assert output[1] = 2
+    assert output[2] = 3
+    assert multiplicities[0] = 2
+    assert multiplicities[1] = 3
+    assert multiplicities[2] = 1
+    return ()
+end
@@ -0,0 +1,119 @@
+#![deny(warnings)]
+use clap::{Parser, Subcommand, ValueHint};
+use cleopatra_cairo::cairo_run;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Compares the trace or memory files of two runs of the same program, usually the output of
+/// the original cairo vm against the one of cleopatra-run.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Compares two binary trace files step by step
+    Trace {
+        #[clap(value_parser, value_hint=ValueHint::FilePath)]
+        expected: PathBuf,
+        #[clap(value_parser, value_hint=ValueHint::FilePath)]
+        actual: PathBuf,
+    },
+    /// Compares two binary memory files by relocated address
+    Memory {
+        #[clap(value_parser, value_hint=ValueHint::FilePath)]
+        expected: PathBuf,
+        #[clap(value_parser, value_hint=ValueHint::FilePath)]
+        actual: PathBuf,
+    },
+}
+
+fn compare_traces(expected: &Path, actual: &Path) -> io::Result<bool> {
+    let expected_trace = cairo_run::read_binary_trace(expected)?;
+    let actual_trace = cairo_run::read_binary_trace(actual)?;
+
+    for (step, (expected_entry, actual_entry)) in
+        expected_trace.iter().zip(actual_trace.iter()).enumerate()
+    {
+        if expected_entry != actual_entry {
+            println!("Traces differ at step {step}:");
+            println!(
+                "  {}: pc={} ap={} fp={}",
+                expected.display(),
+                expected_entry.pc,
+                expected_entry.ap,
+                expected_entry.fp
+            );
+            println!(
+                "  {}: pc={} ap={} fp={}",
+                actual.display(),
+                actual_entry.pc,
+                actual_entry.ap,
+                actual_entry.fp
+            );
+            return Ok(false);
+        }
+    }
+
+    if expected_trace.len() != actual_trace.len() {
+        println!(
+            "Traces differ in length: {} has {} steps, {} has {} steps",
+            expected.display(),
+            expected_trace.len(),
+            actual.display(),
+            actual_trace.len()
+        );
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+fn compare_memories(expected: &Path, actual: &Path) -> io::Result<bool> {
+    let expected_memory = cairo_run::read_binary_memory(expected)?;
+    let actual_memory = cairo_run::read_binary_memory(actual)?;
+
+    if expected_memory == actual_memory {
+        return Ok(true);
+    }
+
+    let mut addresses: Vec<&usize> = expected_memory.keys().chain(actual_memory.keys()).collect();
+    addresses.sort();
+    addresses.dedup();
+
+    println!(
+        "Mismatch between {} and {}",
+        expected.display(),
+        actual.display()
+    );
+    for addr in addresses {
+        match (expected_memory.get(addr), actual_memory.get(addr)) {
+            (Some(value), None) => println!("  {addr}: {value} only in {}", expected.display()),
+            (None, Some(value)) => println!("  {addr}: {value} only in {}", actual.display()),
+            (Some(expected_value), Some(actual_value)) if expected_value != actual_value => {
+                println!("  {addr}: {expected_value} <-> {actual_value}")
+            }
+            _ => (),
+        }
+    }
+
+    Ok(false)
+}
+
+fn main() -> io::Result<()> {
+    let args = Args::parse();
+    let equal = match &args.command {
+        Command::Trace { expected, actual } => compare_traces(expected, actual)?,
+        Command::Memory { expected, actual } => compare_memories(expected, actual)?,
+    };
+
+    if !equal {
+        process::exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+#![deny(warnings)]
+use clap::{Parser, ValueHint};
+use cleopatra_cairo::types::errors::program_errors::ProgramError;
+use cleopatra_cairo::types::program::Program;
+use cleopatra_cairo::vm::decoding::disassembler::disassemble;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+/// Prints the bytecode of a compiled cairo program in Cairo assembly syntax.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(value_parser, value_hint=ValueHint::FilePath)]
+    filename: PathBuf,
+}
+
+fn main() -> Result<(), ProgramError> {
+    let args = Args::parse();
+    let program = Program::new(&args.filename)?;
+
+    let mut buffer = BufWriter::new(io::stdout());
+    disassemble(&program, &mut buffer)?;
+    buffer.flush()?;
+    Ok(())
+}
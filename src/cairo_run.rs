@@ -1,19 +1,85 @@
+use crate::math_utils::as_int;
+use crate::types::errors::program_errors::ProgramError;
+use crate::types::exec_scope::PyValueType;
+use crate::types::instruction::Instruction;
 use crate::types::program::Program;
+use crate::vm::decoding::decoder::decode_instruction;
 use crate::vm::errors::{cairo_run_errors::CairoRunError, runner_errors::RunnerError};
 use crate::vm::runners::cairo_runner::CairoRunner;
+use crate::vm::security::verify_secure_runner;
 use crate::vm::trace::trace_entry::RelocatedTraceEntry;
-use num_bigint::BigInt;
+use num_bigint::{BigInt, Sign};
+use num_traits::ToPrimitive;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, BufWriter, Error, ErrorKind, Write};
+use std::io::{self, BufRead, BufReader, BufWriter, Error, ErrorKind, Read, Write};
 use std::path::Path;
+use std::str::FromStr;
+
+/// Output format of the trace file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TraceFormat {
+    Binary,
+    Json,
+    Csv,
+    Text,
+}
+
+impl FromStr for TraceFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "binary" => Ok(TraceFormat::Binary),
+            "json" => Ok(TraceFormat::Json),
+            "csv" => Ok(TraceFormat::Csv),
+            "text" => Ok(TraceFormat::Text),
+            _ => Err(format!(
+                "Unknown trace format {format}, expected one of: binary, json, csv, text"
+            )),
+        }
+    }
+}
+
+// A trace entry with its step number and the instruction executed at its pc, used by the
+// human-readable trace formats.
+#[derive(Serialize)]
+struct TraceStep {
+    step: usize,
+    pc: usize,
+    ap: usize,
+    fp: usize,
+    instruction: String,
+}
 
-pub fn cairo_run(path: &Path, trace_enabled: bool) -> Result<CairoRunner, CairoRunError> {
+/// Options of a `cairo_run` call. The default runs the program without a trace, secure run
+/// checks or program input.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CairoRunConfig<'a> {
+    pub trace_enabled: bool,
+    pub secure_run: bool,
+    /// Path of a JSON file whose contents are exposed to hints as `program_input`.
+    pub program_input: Option<&'a Path>,
+}
+
+pub fn cairo_run(path: &Path, config: &CairoRunConfig) -> Result<CairoRunner, CairoRunError> {
     let program = match Program::new(path) {
         Ok(program) => program,
         Err(error) => return Err(CairoRunError::Program(error)),
     };
 
-    let mut cairo_runner = CairoRunner::new(&program, trace_enabled);
+    let mut cairo_runner =
+        CairoRunner::new(&program, config.trace_enabled).map_err(CairoRunError::Runner)?;
+    if let Some(program_input_path) = config.program_input {
+        let program_input =
+            read_program_input(program_input_path).map_err(CairoRunError::ProgramInput)?;
+        //Hints access the program input through the main scope
+        cairo_runner
+            .vm
+            .exec_scopes
+            .assign_or_update_variable("program_input", PyValueType::new_any(program_input));
+    }
     cairo_runner.initialize_segments(None);
 
     let end = match cairo_runner.initialize_main_entrypoint() {
@@ -29,10 +95,17 @@ pub fn cairo_run(path: &Path, trace_enabled: bool) -> Result<CairoRunner, CairoR
         return Err(CairoRunError::VirtualMachine(error));
     }
 
-    if let Err(error) = cairo_runner.vm.verify_auto_deductions() {
+    if let Err(error) = cairo_runner.end_run() {
         return Err(CairoRunError::VirtualMachine(error));
     }
 
+    if config.secure_run {
+        cairo_runner
+            .read_return_values()
+            .map_err(CairoRunError::Runner)?;
+        verify_secure_runner(&mut cairo_runner).map_err(CairoRunError::Runner)?;
+    }
+
     if let Err(error) = cairo_runner.relocate() {
         return Err(CairoRunError::Trace(error));
     }
@@ -40,6 +113,11 @@ pub fn cairo_run(path: &Path, trace_enabled: bool) -> Result<CairoRunner, CairoR
     Ok(cairo_runner)
 }
 
+pub fn read_program_input(path: &Path) -> Result<serde_json::Value, ProgramError> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(reader)?)
+}
+
 pub fn write_output(cairo_runner: &mut CairoRunner) -> Result<(), CairoRunError> {
     let mut buffer = BufWriter::new(io::stdout());
     writeln!(&mut buffer, "Program Output: ")
@@ -52,6 +130,29 @@ pub fn write_output(cairo_runner: &mut CairoRunner) -> Result<(), CairoRunError>
         .map_err(|_| CairoRunError::Runner(RunnerError::WriteFail))
 }
 
+pub fn write_memory(cairo_runner: &CairoRunner) -> Result<(), CairoRunError> {
+    let mut buffer = BufWriter::new(io::stdout());
+    writeln!(&mut buffer, "Memory: ").map_err(|_| CairoRunError::Runner(RunnerError::WriteFail))?;
+    cairo_runner
+        .write_memory(&mut buffer)
+        .map_err(CairoRunError::Runner)?;
+    buffer
+        .flush()
+        .map_err(|_| CairoRunError::Runner(RunnerError::WriteFail))
+}
+
+pub fn write_segments(cairo_runner: &mut CairoRunner) -> Result<(), CairoRunError> {
+    let mut buffer = BufWriter::new(io::stdout());
+    writeln!(&mut buffer, "Segments: ")
+        .map_err(|_| CairoRunError::Runner(RunnerError::WriteFail))?;
+    cairo_runner
+        .write_segments(&mut buffer)
+        .map_err(CairoRunError::Runner)?;
+    buffer
+        .flush()
+        .map_err(|_| CairoRunError::Runner(RunnerError::WriteFail))
+}
+
 /// Writes a trace as a binary file. Bincode encodes to little endian by default and each trace
 /// entry is composed of 3 usize values that are padded to always reach 64 bit size.
 pub fn write_binary_trace(
@@ -72,6 +173,104 @@ pub fn write_binary_trace(
     buffer.flush()
 }
 
+/// Writes a trace in the given format. Every format but `Binary` also includes the step number
+/// and the instruction at each pc, decoded from the relocated memory. Immediates are stored as
+/// field elements, so `prime` is used to show negative immediates as such.
+pub fn write_trace(
+    relocated_trace: &[RelocatedTraceEntry],
+    relocated_memory: &[Option<BigInt>],
+    prime: &BigInt,
+    trace_file: &Path,
+    format: TraceFormat,
+) -> io::Result<()> {
+    if format == TraceFormat::Binary {
+        return write_binary_trace(relocated_trace, trace_file);
+    }
+
+    let file = File::create(trace_file)?;
+    let mut buffer = BufWriter::new(file);
+    let steps = relocated_trace
+        .iter()
+        .enumerate()
+        .map(|(step, entry)| TraceStep {
+            step,
+            pc: entry.pc,
+            ap: entry.ap,
+            fp: entry.fp,
+            instruction: decode_relocated_instruction(relocated_memory, entry.pc, prime)
+                .map_or_else(
+                    || String::from("<unknown>"),
+                    |instruction| instruction.to_string(),
+                ),
+        });
+
+    match format {
+        TraceFormat::Json => {
+            serde_json::to_writer_pretty(&mut buffer, &steps.collect::<Vec<TraceStep>>())?
+        }
+        TraceFormat::Csv => {
+            writeln!(buffer, "step,pc,ap,fp,instruction")?;
+            for step in steps {
+                writeln!(
+                    buffer,
+                    "{},{},{},{},\"{}\"",
+                    step.step, step.pc, step.ap, step.fp, step.instruction
+                )?;
+            }
+        }
+        TraceFormat::Text => {
+            for step in steps {
+                writeln!(
+                    buffer,
+                    "{}: pc={} ap={} fp={}  {}",
+                    step.step, step.pc, step.ap, step.fp, step.instruction
+                )?;
+            }
+        }
+        TraceFormat::Binary => unreachable!(),
+    }
+
+    buffer.flush()
+}
+
+// decodes the instruction stored at the relocated address `pc`, along with its immediate,
+// which is converted from a field element into a signed integer.
+fn decode_relocated_instruction(
+    relocated_memory: &[Option<BigInt>],
+    pc: usize,
+    prime: &BigInt,
+) -> Option<Instruction> {
+    let encoded_instruction = relocated_memory.get(pc)?.as_ref()?.to_i64()?;
+    let imm = relocated_memory
+        .get(pc + 1)
+        .and_then(|imm| imm.as_ref())
+        .map(|imm| as_int(imm, prime));
+    decode_instruction(encoded_instruction, imm).ok()
+}
+
+/// Reads a binary trace file written by `write_binary_trace` (or by the original cairo vm),
+/// returning its entries in execution order.
+pub fn read_binary_trace(trace_file: &Path) -> io::Result<Vec<RelocatedTraceEntry>> {
+    let file = File::open(trace_file)?;
+    let mut buffer = BufReader::new(file);
+    let mut relocated_trace = Vec::new();
+
+    while !buffer.fill_buf()?.is_empty() {
+        match bincode::deserialize_from(&mut buffer) {
+            Ok(entry) => relocated_trace.push(entry),
+            Err(e) => {
+                let error_string = format!(
+                    "Failed to read trace at position {}, deserialize error: {e}",
+                    relocated_trace.len()
+                );
+                return Err(Error::new(ErrorKind::InvalidData, error_string));
+            }
+        }
+    }
+
+    Ok(relocated_trace)
+}
+
 /*
    Writes a binary memory file with the relocated memory as input.
    The memory pairs (address, value) are encoded and concatenated in the file
@@ -103,6 +302,37 @@ pub fn write_binary_memory(
     buffer.flush()
 }
 
+/*
+   Reads a binary memory file with the format written by `write_binary_memory`.
+   The pairs are returned keyed by their relocated address, so the order in which
+   they were written doesn't matter.
+*/
+pub fn read_binary_memory(memory_file: &Path) -> io::Result<HashMap<usize, BigInt>> {
+    let mut file = File::open(memory_file)?;
+    let mut memory_bytes: Vec<u8> = Vec::new();
+    file.read_to_end(&mut memory_bytes)?;
+
+    let memory_pairs = memory_bytes.chunks_exact(40);
+    if !memory_pairs.remainder().is_empty() {
+        let error_string = format!(
+            "Malformed memory file: size {} is not a multiple of 40 bytes",
+            memory_bytes.len()
+        );
+        return Err(Error::new(ErrorKind::InvalidData, error_string));
+    }
+
+    let mut relocated_memory = HashMap::new();
+    for memory_pair in memory_pairs {
+        let (addr, value) = decode_relocated_memory(memory_pair);
+        if relocated_memory.insert(addr, value).is_some() {
+            let error_string = format!("Malformed memory file: address {addr} has two values");
+            return Err(Error::new(ErrorKind::InvalidData, error_string));
+        }
+    }
+
+    Ok(relocated_memory)
+}
+
 // encodes a given memory cell.
 fn encode_relocated_memory(memory_bytes: &mut Vec<u8>, addr: usize, memory_cell: &BigInt) {
     // append memory address to bytes vector using a 8 bytes representation
@@ -115,10 +345,26 @@ fn encode_relocated_memory(memory_bytes: &mut Vec<u8>, addr: usize, memory_cell:
     memory_bytes.append(&mut value_bytes);
 }
 
+// decodes a 40 byte memory pair into its address and value.
+fn decode_relocated_memory(memory_pair: &[u8]) -> (usize, BigInt) {
+    let (addr_bytes, value_bytes) = memory_pair.split_at(8);
+    let mut addr = [0; 8];
+    addr.copy_from_slice(addr_bytes);
+    (
+        u64::from_le_bytes(addr) as usize,
+        BigInt::from_bytes_le(Sign::Plus, value_bytes),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Read;
+    use crate::{bigint, bigint64, bigint_str};
+    use num_traits::FromPrimitive;
+
+    fn test_prime() -> BigInt {
+        bigint_str!(b"3618502788666131213697322783095070105623107215331596699973092056135872020481")
+    }
 
     fn run_test_program(program_path: &Path) -> Result<CairoRunner, CairoRunError> {
         let program = match Program::new(program_path) {
@@ -126,7 +372,7 @@ mod tests {
             Err(e) => return Err(CairoRunError::Program(e)),
         };
 
-        let mut cairo_runner = CairoRunner::new(&program, true);
+        let mut cairo_runner = CairoRunner::new(&program, true).unwrap();
 
         cairo_runner.initialize_segments(None);
 
@@ -166,7 +412,7 @@ mod tests {
         // it should fail when the program is loaded.
         let no_data_program_path = Path::new("cairo_programs/no_data_program.json");
 
-        assert!(cairo_run(no_data_program_path, false).is_err());
+        assert!(cairo_run(no_data_program_path, &CairoRunConfig::default()).is_err());
     }
 
     #[test]
@@ -175,7 +421,7 @@ mod tests {
         // it should fail when trying to run initialize_main_entrypoint.
         let no_main_program_path = Path::new("cairo_programs/no_main_program.json");
 
-        assert!(cairo_run(no_main_program_path, false).is_err());
+        assert!(cairo_run(no_main_program_path, &CairoRunConfig::default()).is_err());
     }
 
     #[test]
@@ -184,7 +430,44 @@ mod tests {
         // decode the instruction.
         let invalid_memory = Path::new("cairo_programs/invalid_memory.json");
 
-        assert!(cairo_run(invalid_memory, false).is_err());
+        assert!(cairo_run(invalid_memory, &CairoRunConfig::default()).is_err());
+    }
+
+    #[test]
+    fn cairo_run_with_program_input() {
+        let cairo_runner = cairo_run(
+            Path::new("cairo_programs/manually_compiled/valid_program_a.json"),
+            &CairoRunConfig {
+                program_input: Some(Path::new(
+                    "cairo_programs/manually_compiled/program_input.json",
+                )),
+                ..CairoRunConfig::default()
+            },
+        )
+        .unwrap();
+
+        let program_input = cairo_runner
+            .vm
+            .exec_scopes
+            .get_from_main_scope::<serde_json::Value>("program_input")
+            .unwrap();
+        assert_eq!(program_input["nested"]["id"], "12345678901234567890");
+    }
+
+    #[test]
+    fn cairo_run_with_missing_program_input() {
+        assert!(matches!(
+            cairo_run(
+                Path::new("cairo_programs/manually_compiled/valid_program_a.json"),
+                &CairoRunConfig {
+                    program_input: Some(Path::new(
+                        "cairo_programs/manually_compiled/missing_input.json"
+                    )),
+                    ..CairoRunConfig::default()
+                }
+            ),
+            Err(CairoRunError::ProgramInput(ProgramError::IO(_)))
+        ));
     }
 
     #[test]
@@ -236,7 +519,7 @@ mod tests {
     fn run_with_no_trace() {
         let program_path = Path::new("cairo_programs/struct.json");
         let program = Program::new(program_path).unwrap();
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
 
         cairo_runner.initialize_segments(None);
 
@@ -248,4 +531,189 @@ mod tests {
 
         assert!(cairo_runner.vm.trace.is_none());
     }
+
+    #[test]
+    fn trace_format_from_str() {
+        assert_eq!(TraceFormat::from_str("binary"), Ok(TraceFormat::Binary));
+        assert_eq!(TraceFormat::from_str("json"), Ok(TraceFormat::Json));
+        assert_eq!(TraceFormat::from_str("csv"), Ok(TraceFormat::Csv));
+        assert_eq!(TraceFormat::from_str("text"), Ok(TraceFormat::Text));
+        assert!(TraceFormat::from_str("xml").is_err());
+    }
+
+    fn test_trace_and_memory() -> (Vec<RelocatedTraceEntry>, Vec<Option<BigInt>>) {
+        // [ap + 0] = [fp + -3] + 5; ap++
+        // ret
+        let relocated_memory = vec![
+            None,
+            Some(bigint64!(0x482680017ffd8000)),
+            Some(bigint!(5)),
+            Some(bigint64!(0x208b7fff7fff7ffe)),
+        ];
+        let relocated_trace = vec![
+            RelocatedTraceEntry {
+                ap: 8,
+                fp: 8,
+                pc: 1,
+            },
+            RelocatedTraceEntry {
+                ap: 9,
+                fp: 8,
+                pc: 3,
+            },
+        ];
+        (relocated_trace, relocated_memory)
+    }
+
+    #[test]
+    fn write_text_trace() {
+        let trace_path = std::env::temp_dir().join("cleopatra_write_text_trace.txt");
+        let (relocated_trace, relocated_memory) = test_trace_and_memory();
+
+        assert!(write_trace(
+            &relocated_trace,
+            &relocated_memory,
+            &test_prime(),
+            &trace_path,
+            TraceFormat::Text
+        )
+        .is_ok());
+        assert_eq!(
+            std::fs::read_to_string(&trace_path).unwrap(),
+            "0: pc=1 ap=8 fp=8  [ap + 0] = [fp + -3] + 5; ap++\n1: pc=3 ap=9 fp=8  ret\n"
+        );
+    }
+
+    #[test]
+    fn write_text_trace_negative_immediate() {
+        let trace_path = std::env::temp_dir().join("cleopatra_write_text_trace_negative_imm.txt");
+        let (relocated_trace, mut relocated_memory) = test_trace_and_memory();
+        // [ap + 0] = [fp + -3] + -5; ap++
+        relocated_memory[2] = Some(test_prime() - 5);
+
+        assert!(write_trace(
+            &relocated_trace,
+            &relocated_memory,
+            &test_prime(),
+            &trace_path,
+            TraceFormat::Text
+        )
+        .is_ok());
+        assert_eq!(
+            std::fs::read_to_string(&trace_path).unwrap(),
+            "0: pc=1 ap=8 fp=8  [ap + 0] = [fp + -3] + -5; ap++\n1: pc=3 ap=9 fp=8  ret\n"
+        );
+    }
+
+    #[test]
+    fn write_csv_trace() {
+        let trace_path = std::env::temp_dir().join("cleopatra_write_csv_trace.csv");
+        let (relocated_trace, relocated_memory) = test_trace_and_memory();
+
+        assert!(write_trace(
+            &relocated_trace,
+            &relocated_memory,
+            &test_prime(),
+            &trace_path,
+            TraceFormat::Csv
+        )
+        .is_ok());
+        assert_eq!(
+            std::fs::read_to_string(&trace_path).unwrap(),
+            "step,pc,ap,fp,instruction\n0,1,8,8,\"[ap + 0] = [fp + -3] + 5; ap++\"\n1,3,9,8,\"ret\"\n"
+        );
+    }
+
+    #[test]
+    fn write_json_trace() {
+        let trace_path = std::env::temp_dir().join("cleopatra_write_json_trace.json");
+        let (relocated_trace, relocated_memory) = test_trace_and_memory();
+
+        assert!(write_trace(
+            &relocated_trace,
+            &relocated_memory,
+            &test_prime(),
+            &trace_path,
+            TraceFormat::Json
+        )
+        .is_ok());
+        let trace: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&trace_path).unwrap()).unwrap();
+        assert_eq!(
+            trace,
+            serde_json::json!([
+                {"step": 0, "pc": 1, "ap": 8, "fp": 8, "instruction": "[ap + 0] = [fp + -3] + 5; ap++"},
+                {"step": 1, "pc": 3, "ap": 9, "fp": 8, "instruction": "ret"}
+            ])
+        );
+    }
+
+    #[test]
+    fn read_binary_trace_round_trip() {
+        let trace_path = std::env::temp_dir().join("cleopatra_read_binary_trace.trace");
+        let relocated_trace = vec![
+            RelocatedTraceEntry {
+                ap: 24,
+                fp: 24,
+                pc: 1,
+            },
+            RelocatedTraceEntry {
+                ap: 25,
+                fp: 24,
+                pc: 3,
+            },
+        ];
+
+        assert!(write_binary_trace(&relocated_trace, &trace_path).is_ok());
+        assert_eq!(read_binary_trace(&trace_path).unwrap(), relocated_trace);
+    }
+
+    #[test]
+    fn read_binary_trace_truncated_entry() {
+        let trace_path = std::env::temp_dir().join("cleopatra_read_binary_trace_truncated.trace");
+        std::fs::write(&trace_path, [0; 30]).unwrap();
+
+        let error = read_binary_trace(&trace_path).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn read_binary_memory_round_trip() {
+        let memory_path = std::env::temp_dir().join("cleopatra_read_binary_memory.memory");
+        let relocated_memory = vec![None, Some(bigint!(5)), None, Some(bigint!(1) << 251_u32)];
+
+        assert!(write_binary_memory(&relocated_memory, &memory_path).is_ok());
+        assert_eq!(
+            read_binary_memory(&memory_path).unwrap(),
+            HashMap::from([(1, bigint!(5)), (3, bigint!(1) << 251_u32)])
+        );
+    }
+
+    #[test]
+    fn read_binary_memory_malformed_size() {
+        let memory_path = std::env::temp_dir().join("cleopatra_read_binary_memory_size.memory");
+        std::fs::write(&memory_path, [0; 41]).unwrap();
+
+        let error = read_binary_memory(&memory_path).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "Malformed memory file: size 41 is not a multiple of 40 bytes"
+        );
+    }
+
+    #[test]
+    fn read_binary_memory_repeated_address() {
+        let memory_path = std::env::temp_dir().join("cleopatra_read_binary_memory_repeated.memory");
+        let mut memory_bytes = Vec::new();
+        encode_relocated_memory(&mut memory_bytes, 2, &bigint!(3));
+        encode_relocated_memory(&mut memory_bytes, 2, &bigint!(4));
+        std::fs::write(&memory_path, memory_bytes).unwrap();
+
+        let error = read_binary_memory(&memory_path).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Malformed memory file: address 2 has two values"
+        );
+    }
 }
@@ -1,9 +1,13 @@
 #![deny(warnings)]
 use clap::{Parser, ValueHint};
-use cleopatra_cairo::cairo_run;
+use cleopatra_cairo::cairo_run::{self, CairoRunConfig, TraceFormat};
+use cleopatra_cairo::vm::coverage::Coverage;
 use cleopatra_cairo::vm::errors::cairo_run_errors::CairoRunError;
 use cleopatra_cairo::vm::errors::runner_errors::RunnerError;
 use cleopatra_cairo::vm::errors::trace_errors::TraceError;
+use cleopatra_cairo::vm::profiler::{Profile, ProfileFormat};
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 
 #[cfg(feature = "with_mimalloc")]
@@ -20,17 +24,42 @@ struct Args {
     filename: PathBuf,
     #[clap(long = "--trace_file", value_parser)]
     trace_file: Option<PathBuf>,
+    #[clap(long = "--trace_format", value_parser, default_value = "binary")]
+    trace_format: TraceFormat,
     #[structopt(long = "--print_output")]
     print_output: bool,
     trace: Option<PathBuf>,
     #[structopt(long = "--memory_file")]
     memory_file: Option<PathBuf>,
+    #[structopt(long = "--print_memory")]
+    print_memory: bool,
+    #[structopt(long = "--print_segments")]
+    print_segments: bool,
+    #[structopt(long = "--secure_run")]
+    secure_run: bool,
+    #[clap(long = "--profile_output", value_parser)]
+    profile_output: Option<PathBuf>,
+    #[clap(long = "--profile_format", value_parser, default_value = "folded")]
+    profile_format: ProfileFormat,
+    #[clap(long = "--coverage_output", value_parser)]
+    coverage_output: Option<PathBuf>,
+    #[clap(long = "--program_input", value_parser, value_hint=ValueHint::FilePath)]
+    program_input: Option<PathBuf>,
 }
 
 fn main() -> Result<(), CairoRunError> {
     let args = Args::parse();
-    let trace_enabled = args.trace_file.is_some();
-    let mut cairo_runner = match cairo_run::cairo_run(&args.filename, trace_enabled) {
+    let trace_enabled = args.trace_file.is_some()
+        || args.profile_output.is_some()
+        || args.coverage_output.is_some();
+    let mut cairo_runner = match cairo_run::cairo_run(
+        &args.filename,
+        &CairoRunConfig {
+            trace_enabled,
+            secure_run: args.secure_run,
+            program_input: args.program_input.as_deref(),
+        },
+    ) {
         Ok(runner) => runner,
         Err(error) => return Err(error),
     };
@@ -40,16 +69,57 @@ fn main() -> Result<(), CairoRunError> {
             .relocated_trace
             .as_ref()
             .ok_or(CairoRunError::Trace(TraceError::TraceNotEnabled))?;
-        match cairo_run::write_binary_trace(relocated_trace, &trace_path) {
+        match cairo_run::write_trace(
+            relocated_trace,
+            &cairo_runner.relocated_memory,
+            &cairo_runner.vm.prime,
+            &trace_path,
+            args.trace_format,
+        ) {
             Ok(()) => (),
             Err(_e) => return Err(CairoRunError::Runner(RunnerError::WriteFail)),
         }
     }
 
+    if let Some(profile_path) = args.profile_output {
+        let profile = Profile::new(&cairo_runner).map_err(CairoRunError::Runner)?;
+        let write_profile = || -> std::io::Result<()> {
+            let mut buffer = BufWriter::new(File::create(profile_path)?);
+            profile.write(&mut buffer, args.profile_format)?;
+            buffer.flush()
+        };
+        if write_profile().is_err() {
+            return Err(CairoRunError::Runner(RunnerError::WriteFail));
+        }
+    }
+
+    if let Some(coverage_path) = args.coverage_output {
+        let mut coverage = Coverage::new();
+        coverage
+            .add_run(&cairo_runner)
+            .map_err(CairoRunError::Runner)?;
+        let write_coverage = || -> std::io::Result<()> {
+            let mut buffer = BufWriter::new(File::create(coverage_path)?);
+            coverage.write_lcov(&mut buffer)?;
+            buffer.flush()
+        };
+        if write_coverage().is_err() {
+            return Err(CairoRunError::Runner(RunnerError::WriteFail));
+        }
+    }
+
     if args.print_output {
         cairo_run::write_output(&mut cairo_runner)?;
     }
 
+    if args.print_segments {
+        cairo_run::write_segments(&mut cairo_runner)?;
+    }
+
+    if args.print_memory {
+        cairo_run::write_memory(&cairo_runner)?;
+    }
+
     if let Some(memory_path) = args.memory_file {
         match cairo_run::write_binary_memory(&cairo_runner.relocated_memory, &memory_path) {
             Ok(()) => (),
@@ -3,7 +3,7 @@ use std::ops::Shr;
 use crate::{bigint, vm::errors::vm_errors::VirtualMachineError};
 use num_bigint::BigInt;
 use num_integer::Integer;
-use num_traits::{abs, FromPrimitive, Signed};
+use num_traits::{abs, FromPrimitive, One, Signed, Zero};
 
 ///Returns the integer square root of the nonnegative integer n.
 ///This is the floor of the exact square root of n.
@@ -69,7 +69,7 @@ fn igcdex(num_a: BigInt, num_b: BigInt) -> (BigInt, BigInt, BigInt) {
     }
 }
 ///Finds a nonnegative integer x < p such that (m * x) % p == n.
-fn div_mod(n: BigInt, m: BigInt, p: BigInt) -> BigInt {
+pub fn div_mod(n: BigInt, m: BigInt, p: BigInt) -> BigInt {
     let (a, _, c) = igcdex(m, p.clone());
     assert_eq!(c, bigint!(1));
     (n * a).mod_floor(&p)
@@ -116,6 +116,55 @@ pub fn ec_double_slope(point: (BigInt, BigInt), alpha: &BigInt, prime: &BigInt)
     )
 }
 
+///Returns true if n is a quadratic residue modulo the prime p (zero included).
+pub fn is_quad_residue(n: &BigInt, p: &BigInt) -> bool {
+    let n = n.mod_floor(p);
+    n.is_zero() || n.modpow(&((p - 1_i32).shr(1_i32)), p).is_one()
+}
+
+///Finds the minimum non-negative integer m such that (m * m) % p == n, using Tonelli-Shanks.
+///Returns None if n is not a quadratic residue modulo the prime p.
+pub fn sqrt_prime(n: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let n = n.mod_floor(p);
+    if n.is_zero() {
+        return Some(n);
+    }
+    if !is_quad_residue(&n, p) {
+        return None;
+    }
+    //Write p - 1 as q * 2^s with q odd
+    let mut q = p - 1_i32;
+    let mut s = 0_usize;
+    while q.is_even() {
+        q = q.shr(1_i32);
+        s += 1;
+    }
+    //Find a quadratic non-residue z
+    let mut z = bigint!(2);
+    while is_quad_residue(&z, p) {
+        z += 1_i32;
+    }
+    let mut m = s;
+    let mut c = z.modpow(&q, p);
+    let mut t = n.modpow(&q, p);
+    let mut r = n.modpow(&((&q + 1_i32).shr(1_i32)), p);
+    while !t.is_one() {
+        //Find the least i such that t^(2^i) = 1
+        let mut i = 0_usize;
+        let mut t_pow = t.clone();
+        while !t_pow.is_one() {
+            t_pow = (&t_pow * &t_pow).mod_floor(p);
+            i += 1;
+        }
+        let b = c.modpow(&(bigint!(1) << (m - i - 1)), p);
+        m = i;
+        c = (&b * &b).mod_floor(p);
+        t = (t * &c).mod_floor(p);
+        r = (r * b).mod_floor(p);
+    }
+    Some(std::cmp::min(r.clone(), p - r))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -476,4 +525,34 @@ mod tests {
         let n = bigint!(-1);
         assert_eq!(isqrt(&n), Err(VirtualMachineError::SqrtNegative(n)));
     }
+
+    #[test]
+    fn calculate_is_quad_residue() {
+        let prime = bigint_str!(
+            b"3618502788666131213697322783095070105623107215331596699973092056135872020481"
+        );
+        assert!(is_quad_residue(&bigint!(0), &prime));
+        assert!(is_quad_residue(&bigint!(4), &prime));
+        assert!(!is_quad_residue(&bigint!(3), &prime));
+    }
+
+    #[test]
+    fn calculate_sqrt_prime_small() {
+        assert_eq!(sqrt_prime(&bigint!(4), &bigint!(7)), Some(bigint!(2)));
+        assert_eq!(sqrt_prime(&bigint!(2), &bigint!(7)), Some(bigint!(3)));
+        assert_eq!(sqrt_prime(&bigint!(3), &bigint!(7)), None);
+        assert_eq!(sqrt_prime(&bigint!(0), &bigint!(7)), Some(bigint!(0)));
+    }
+
+    #[test]
+    fn calculate_sqrt_prime_stark_field() {
+        let prime = bigint_str!(
+            b"3618502788666131213697322783095070105623107215331596699973092056135872020481"
+        );
+        let n = bigint_str!(b"1606938044258990275541962092341162602522202993782792835301376");
+        let root = sqrt_prime(&n, &prime).unwrap();
+        assert_eq!((&root * &root).mod_floor(&prime), n);
+        assert!(root <= &prime - &root);
+        assert_eq!(sqrt_prime(&bigint!(3), &prime), None);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::serde::deserialize_utils;
+use crate::serde::deserialize_utils::{self, ReferenceExpr};
 use crate::types::instruction::Register;
 use crate::types::{
     errors::program_errors::ProgramError, program::Program, relocatable::MaybeRelocatable,
@@ -6,6 +6,7 @@ use crate::types::{
 use num_bigint::{BigInt, Sign};
 use num_traits::abs;
 use serde::{de, de::MapAccess, de::SeqAccess, Deserialize, Deserializer};
+use serde_json::value::RawValue;
 use std::{collections::HashMap, fmt, fs::File, io::BufReader, path::Path};
 
 #[derive(Deserialize, Debug)]
@@ -18,6 +19,7 @@ pub struct ProgramJson {
     pub identifiers: HashMap<String, Identifier>,
     pub hints: HashMap<usize, Vec<HintParams>>,
     pub reference_manager: ReferenceManager,
+    pub debug_info: Option<DebugInfo>,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -56,12 +58,50 @@ impl Default for ApTracking {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq, Clone)]
 pub struct Identifier {
     pub pc: Option<usize>,
+    #[serde(rename(deserialize = "type"))]
+    pub type_: Option<String>,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_option_bigint_number")]
+    pub value: Option<BigInt>,
+    pub full_name: Option<String>,
+    pub members: Option<HashMap<String, Member>>,
+    pub cairo_type: Option<String>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct Member {
+    pub cairo_type: String,
+    pub offset: usize,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct DebugInfo {
+    pub instruction_locations: HashMap<usize, InstructionLocation>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct InstructionLocation {
+    pub inst: Location,
 }
 
 #[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct Location {
+    pub end_line: usize,
+    pub end_col: usize,
+    pub input_file: InputFile,
+    pub start_line: usize,
+    pub start_col: usize,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct InputFile {
+    pub filename: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone, Default)]
 pub struct ReferenceManager {
     pub references: Vec<Reference>,
 }
@@ -70,9 +110,10 @@ pub struct ReferenceManager {
 pub struct Reference {
     pub ap_tracking_data: ApTracking,
     pub pc: Option<usize>,
-    #[serde(deserialize_with = "deserialize_value_address")]
-    #[serde(rename(deserialize = "value"))]
-    pub value_address: ValueAddress,
+    //References that can't be reduced to a register and offsets are still loaded, hints fail
+    //when they resolve them
+    #[serde(deserialize_with = "deserialize_reference_expr")]
+    pub value: ReferenceExpr,
 }
 
 #[derive(Deserialize, Debug, PartialEq, Clone)]
@@ -83,6 +124,8 @@ pub struct ValueAddress {
     pub immediate: Option<BigInt>,
     pub dereference: bool,
     pub inner_dereference: bool,
+    //Cairo type of the referenced value, if known
+    pub value_type: Option<String>,
 }
 
 struct BigIntVisitor;
@@ -181,10 +224,10 @@ impl<'de> de::Visitor<'de> for ReferenceIdsVisitor {
     }
 }
 
-struct ValueAddressVisitor;
+struct ReferenceExprVisitor;
 
-impl<'de> de::Visitor<'de> for ValueAddressVisitor {
-    type Value = ValueAddress;
+impl<'de> de::Visitor<'de> for ReferenceExprVisitor {
+    type Value = ReferenceExpr;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("a string representing the address in memory of a variable")
@@ -194,13 +237,7 @@ impl<'de> de::Visitor<'de> for ValueAddressVisitor {
     where
         E: de::Error,
     {
-        let res = match value.chars().next() {
-            Some('[') => deserialize_utils::parse_dereference(value).map_err(de::Error::custom)?,
-            Some('c') => deserialize_utils::parse_reference(value).map_err(de::Error::custom)?,
-            _c => return Err("Expected '[' or 'c' as first char").map_err(de::Error::custom),
-        };
-
-        Ok(res)
+        deserialize_utils::parse_reference_expr(value).map_err(de::Error::custom)
     }
 }
 
@@ -220,10 +257,22 @@ pub fn deserialize_map_to_string_and_bigint_hashmap<'de, D: Deserializer<'de>>(
     d.deserialize_map(ReferenceIdsVisitor)
 }
 
-pub fn deserialize_value_address<'de, D: Deserializer<'de>>(
+pub fn deserialize_option_bigint_number<'de, D: Deserializer<'de>>(
+    d: D,
+) -> Result<Option<BigInt>, D::Error> {
+    // Constant values can exceed 64 bits, so they are parsed from the raw JSON number
+    match Option::<Box<RawValue>>::deserialize(d)? {
+        Some(raw_value) => BigInt::parse_bytes(raw_value.get().as_bytes(), 10)
+            .map(Some)
+            .ok_or_else(|| de::Error::custom(format!("Invalid integer value: {}", raw_value))),
+        None => Ok(None),
+    }
+}
+
+pub fn deserialize_reference_expr<'de, D: Deserializer<'de>>(
     d: D,
-) -> Result<ValueAddress, D::Error> {
-    d.deserialize_str(ValueAddressVisitor)
+) -> Result<ReferenceExpr, D::Error> {
+    d.deserialize_str(ReferenceExprVisitor)
 }
 
 pub fn deserialize_program_json(path: &Path) -> Result<ProgramJson, ProgramError> {
@@ -237,20 +286,51 @@ pub fn deserialize_program_json(path: &Path) -> Result<ProgramJson, ProgramError
 
 pub fn deserialize_program(path: &Path) -> Result<Program, ProgramError> {
     let program_json: ProgramJson = deserialize_program_json(path)?;
+    let constants = program_json
+        .identifiers
+        .iter()
+        .filter(|(_, identifier)| identifier.type_.as_deref() == Some("const"))
+        .map(|(name, identifier)| {
+            identifier
+                .value
+                .clone()
+                .map(|value| (name.clone(), value))
+                .ok_or_else(|| ProgramError::ConstWithoutValue(name.clone()))
+        })
+        .collect::<Result<HashMap<String, BigInt>, ProgramError>>()?;
+    let struct_types = program_json
+        .identifiers
+        .iter()
+        .filter(|(_, identifier)| identifier.type_.as_deref() == Some("struct"))
+        .filter_map(|(name, identifier)| {
+            identifier
+                .members
+                .clone()
+                .map(|members| (name.clone(), members))
+        })
+        .collect();
     Ok(Program {
         builtins: program_json.builtins,
         prime: program_json.prime,
         data: program_json.data,
-        main: program_json.identifiers["__main__.main"].pc,
+        main: program_json
+            .identifiers
+            .get("__main__.main")
+            .and_then(|main| main.pc),
         hints: program_json.hints,
         reference_manager: program_json.reference_manager,
+        constants,
+        struct_types,
+        identifiers: program_json.identifiers,
+        debug_info: program_json.debug_info,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bigint;
+    use crate::serde::deserialize_utils::parse_reference_expr;
+    use crate::{bigint, bigint_str};
     use num_traits::FromPrimitive;
 
     #[test]
@@ -432,14 +512,7 @@ mod tests {
                         offset: 0,
                     },
                     pc: Some(0),
-                    value_address: ValueAddress {
-                        register: Some(Register::FP),
-                        offset1: -4,
-                        offset2: 0,
-                        immediate: None,
-                        dereference: true,
-                        inner_dereference: false,
-                    },
+                    value: parse_reference_expr("[cast(fp + (-4), felt*)]").unwrap(),
                 },
                 Reference {
                     ap_tracking_data: ApTracking {
@@ -447,14 +520,7 @@ mod tests {
                         offset: 0,
                     },
                     pc: Some(0),
-                    value_address: ValueAddress {
-                        register: Some(Register::FP),
-                        offset1: -3,
-                        offset2: 0,
-                        immediate: None,
-                        dereference: true,
-                        inner_dereference: false,
-                    },
+                    value: parse_reference_expr("[cast(fp + (-3), felt*)]").unwrap(),
                 },
                 Reference {
                     ap_tracking_data: ApTracking {
@@ -462,14 +528,7 @@ mod tests {
                         offset: 0,
                     },
                     pc: Some(0),
-                    value_address: ValueAddress {
-                        register: Some(Register::FP),
-                        offset1: -3,
-                        offset2: 0,
-                        immediate: Some(bigint!(2)),
-                        dereference: false,
-                        inner_dereference: false,
-                    },
+                    value: parse_reference_expr("cast([fp + (-3)] + 2, felt)").unwrap(),
                 },
                 Reference {
                     ap_tracking_data: ApTracking {
@@ -477,14 +536,7 @@ mod tests {
                         offset: 0,
                     },
                     pc: Some(0),
-                    value_address: ValueAddress {
-                        register: Some(Register::FP),
-                        offset1: 0,
-                        offset2: 0,
-                        immediate: None,
-                        dereference: true,
-                        inner_dereference: false,
-                    },
+                    value: parse_reference_expr("[cast(fp, felt*)]").unwrap(),
                 },
             ],
         };
@@ -628,4 +680,220 @@ mod tests {
         assert_eq!(program.main, Some(0));
         assert_eq!(program.hints, hints);
     }
+
+    #[test]
+    fn deserialize_identifiers_with_big_const_values_and_members() {
+        let valid_json = r#"
+            {
+                "__main__.main": {
+                    "decorators": [],
+                    "pc": 0,
+                    "type": "function"
+                },
+                "__main__.SHIFT": {
+                    "type": "const",
+                    "value": 340282366920938463463374607431768211456
+                },
+                "__main__.NEGATIVE": {
+                    "type": "const",
+                    "value": -1
+                },
+                "__main__.MyStruct": {
+                    "full_name": "__main__.MyStruct",
+                    "members": {
+                        "a": {
+                            "cairo_type": "felt",
+                            "offset": 0
+                        },
+                        "b": {
+                            "cairo_type": "felt*",
+                            "offset": 1
+                        }
+                    },
+                    "size": 2,
+                    "type": "struct"
+                },
+                "__main__.Alias": {
+                    "cairo_type": "felt",
+                    "type": "type_definition"
+                }
+            }"#;
+
+        let identifiers: HashMap<String, Identifier> = serde_json::from_str(valid_json).unwrap();
+
+        assert_eq!(identifiers["__main__.main"].pc, Some(0));
+        assert_eq!(
+            identifiers["__main__.main"].type_,
+            Some(String::from("function"))
+        );
+        assert_eq!(
+            identifiers["__main__.SHIFT"].value,
+            Some(bigint_str!(b"340282366920938463463374607431768211456"))
+        );
+        assert_eq!(identifiers["__main__.NEGATIVE"].value, Some(bigint!(-1)));
+        assert_eq!(
+            identifiers["__main__.MyStruct"].full_name,
+            Some(String::from("__main__.MyStruct"))
+        );
+        assert_eq!(
+            identifiers["__main__.MyStruct"].members,
+            Some(HashMap::from([
+                (
+                    String::from("a"),
+                    Member {
+                        cairo_type: String::from("felt"),
+                        offset: 0
+                    }
+                ),
+                (
+                    String::from("b"),
+                    Member {
+                        cairo_type: String::from("felt*"),
+                        offset: 1
+                    }
+                )
+            ]))
+        );
+        assert_eq!(
+            identifiers["__main__.Alias"].cairo_type,
+            Some(String::from("felt"))
+        );
+        assert_eq!(identifiers["__main__.Alias"].value, None);
+    }
+
+    #[test]
+    fn deserialize_program_constants() {
+        let program: Program = deserialize_program(Path::new(
+            "cairo_programs/manually_compiled/valid_program_b.json",
+        ))
+        .expect("Failed to deserialize program");
+
+        assert_eq!(
+            program.constants.get("__main__.main.SIZEOF_LOCALS"),
+            Some(&bigint!(0))
+        );
+        assert_eq!(
+            program
+                .constants
+                .get("starkware.cairo.common.serialize.serialize_word.SIZEOF_LOCALS"),
+            Some(&bigint!(0))
+        );
+        //Only const identifiers are included
+        assert_eq!(program.constants.get("__main__.main"), None);
+    }
+
+    #[test]
+    fn deserialize_program_struct_types() {
+        let program: Program = deserialize_program(Path::new(
+            "cairo_programs/manually_compiled/valid_program_b.json",
+        ))
+        .expect("Failed to deserialize program");
+
+        assert_eq!(
+            program
+                .struct_types
+                .get("__main__.check_range.ImplicitArgs"),
+            Some(&HashMap::from([(
+                String::from("range_check_ptr"),
+                Member {
+                    cairo_type: String::from("felt"),
+                    offset: 0
+                }
+            )]))
+        );
+        //Only struct identifiers are included
+        assert_eq!(program.struct_types.get("__main__.main"), None);
+    }
+
+    #[test]
+    fn deserialize_program_identifiers() {
+        let program: Program = deserialize_program(Path::new(
+            "cairo_programs/manually_compiled/valid_program_b.json",
+        ))
+        .expect("Failed to deserialize program");
+
+        let check_range = &program.identifiers["__main__.check_range"];
+        assert_eq!(check_range.type_.as_deref(), Some("function"));
+        assert_eq!(check_range.pc, Some(4));
+        assert_eq!(program.identifiers["__main__.main"].pc, Some(13));
+    }
+
+    #[test]
+    fn deserialize_program_debug_info() {
+        let program: Program = deserialize_program(Path::new(
+            "cairo_programs/manually_compiled/valid_program_b.json",
+        ))
+        .expect("Failed to deserialize program");
+
+        let debug_info = program.debug_info.expect("Missing debug_info");
+        let location = &debug_info.instruction_locations[&0].inst;
+        assert_eq!((location.start_line, location.start_col), (3, 5));
+        assert_eq!((location.end_line, location.end_col), (3, 31));
+        assert!(location
+            .input_file
+            .filename
+            .ends_with("starkware/cairo/common/serialize.cairo"));
+    }
+
+    #[test]
+    fn deserialize_program_json_without_debug_info() {
+        // Programs compiled with --no_debug_info have a null debug_info
+        let valid_json = r#"
+            {
+                "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+                "builtins": [],
+                "data": [],
+                "identifiers": {},
+                "hints": {},
+                "reference_manager": {
+                    "references": []
+                },
+                "debug_info": null
+            }"#;
+
+        let program_json: ProgramJson = serde_json::from_str(valid_json).unwrap();
+        assert_eq!(program_json.debug_info, None);
+    }
+
+    #[test]
+    fn deserialize_program_json_with_unsupported_references() {
+        // References that can't be reduced to a register and offsets only fail when a hint uses them
+        let valid_json = r#"
+            {
+                "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+                "builtins": [],
+                "data": [],
+                "identifiers": {},
+                "hints": {},
+                "reference_manager": {
+                    "references": [
+                        {
+                            "ap_tracking_data": {
+                                "group": 0,
+                                "offset": 0
+                            },
+                            "pc": 0,
+                            "value": "cast([fp + (-4)] + [fp + (-3)] + 2, felt)"
+                        },
+                        {
+                            "ap_tracking_data": {
+                                "group": 0,
+                                "offset": 0
+                            },
+                            "pc": 0,
+                            "value": "[fp] * 2"
+                        }
+                    ]
+                },
+                "debug_info": null
+            }"#;
+
+        let program_json: ProgramJson = serde_json::from_str(valid_json).unwrap();
+        let references = &program_json.reference_manager.references;
+        assert_eq!(
+            references[0].value.to_string(),
+            "cast([fp + (-4)] + [fp + (-3)] + 2, felt)"
+        );
+        assert_eq!(references[1].value.to_string(), "[fp] * 2");
+    }
 }
@@ -2,14 +2,19 @@ use crate::serde::deserialize_program::ValueAddress;
 use crate::types::instruction::Register;
 use num_bigint::{BigInt, ParseBigIntError};
 use num_integer::Integer;
+use num_traits::{Signed, ToPrimitive, Zero};
 use std::fmt;
-use std::num::{IntErrorKind, ParseIntError};
+use std::iter::Peekable;
+use std::num::ParseIntError;
+use std::str::Chars;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ReferenceParseError {
     IntError(ParseIntError),
     BigIntError(ParseBigIntError),
     InvalidStringError(String),
+    UnsupportedExpression(String),
+    OffsetOutOfRange(BigInt),
 }
 
 impl fmt::Display for ReferenceParseError {
@@ -27,10 +32,20 @@ impl fmt::Display for ReferenceParseError {
                 write!(f, "Invalid reference string error: ")?;
                 error.fmt(f)
             }
+            ReferenceParseError::UnsupportedExpression(value) => write!(
+                f,
+                "Reference {} can't be expressed in terms of a register and offsets",
+                value
+            ),
+            ReferenceParseError::OffsetOutOfRange(offset) => {
+                write!(f, "Reference offset {} doesn't fit in an i32", offset)
+            }
         }
     }
 }
 
+impl std::error::Error for ReferenceParseError {}
+
 // Checks if the hex string has an odd length.
 // If that is the case, prepends '0' to it.
 pub fn maybe_add_padding(mut hex: String) -> String {
@@ -41,199 +56,328 @@ pub fn maybe_add_padding(mut hex: String) -> String {
     hex
 }
 
-fn parse_register(splitted_value_str: &[&str]) -> Option<Register> {
-    let str_tmp: Vec<&str> = splitted_value_str[0].split(',').collect();
-
-    let mut raw_reg_str = str_tmp[0].to_string();
-
-    raw_reg_str.retain(|c| !r#"["#.contains(c));
-
-    match raw_reg_str.split('(').collect::<Vec<_>>()[1] {
-        "ap" => Some(Register::AP),
-        "fp" => Some(Register::FP),
-        _ => None,
-    }
+///Expression of the Cairo reference language, as found in the `value` of a compiled reference.
+///e.g. `[cast([fp + (-4)] + 1, felt*)]`
+#[derive(Debug, PartialEq, Clone)]
+pub enum ReferenceExpr {
+    Register(Register),
+    Immediate(BigInt),
+    //[expr]
+    Deref(Box<ReferenceExpr>),
+    //cast(expr, type)
+    Cast(Box<ReferenceExpr>, String),
+    Add(Box<ReferenceExpr>, Box<ReferenceExpr>),
+    Sub(Box<ReferenceExpr>, Box<ReferenceExpr>),
+    Mul(Box<ReferenceExpr>, Box<ReferenceExpr>),
+    Neg(Box<ReferenceExpr>),
 }
 
-pub fn parse_dereference(value: &str) -> Result<ValueAddress, ReferenceParseError> {
-    let splitted: Vec<&str> = value.split(" + ").collect();
+impl ReferenceExpr {
+    //Returns the expression inside any number of casts
+    fn strip_casts(&self) -> &ReferenceExpr {
+        match self {
+            ReferenceExpr::Cast(inner, _) => inner.strip_casts(),
+            expr => expr,
+        }
+    }
 
-    match splitted.len() {
-        1 => parse_dereference_no_offsets(&splitted),
-        2 => parse_dereference_with_one_offset(&splitted),
-        3 => parse_dereference_with_two_offsets(&splitted),
+    //Splits an expression of the form `base + offset` into (base, offset)
+    fn split_offset(&self) -> (&ReferenceExpr, BigInt) {
+        match self.strip_casts() {
+            ReferenceExpr::Add(base, offset) => match offset.strip_casts() {
+                ReferenceExpr::Immediate(offset) => (base.strip_casts(), offset.clone()),
+                _ => (self.strip_casts(), BigInt::zero()),
+            },
+            ReferenceExpr::Sub(base, offset) => match offset.strip_casts() {
+                ReferenceExpr::Immediate(offset) => (base.strip_casts(), -offset),
+                _ => (self.strip_casts(), BigInt::zero()),
+            },
+            expr => (expr, BigInt::zero()),
+        }
+    }
 
-        // FIXME this match arm is handled like this just to avoid unnecesary deserialization errors.
-        // For the moment, the ValueAddress structs returned in ths arm are not used in hints, so they are not important.
-        // issue: https://github.com/lambdaclass/cleopatra_cairo/issues/280
+    //Returns the Cairo type of the expression's value, if it can be inferred
+    fn value_type(&self) -> Option<String> {
+        match self {
+            ReferenceExpr::Cast(_, cairo_type) => Some(cairo_type.clone()),
+            // Dereferencing a T* yields a T, memory cells are felts unless casted
+            ReferenceExpr::Deref(inner) => match inner.value_type() {
+                Some(cairo_type) => cairo_type.strip_suffix('*').map(String::from),
+                None => Some(String::from("felt")),
+            },
+            ReferenceExpr::Immediate(_) => Some(String::from("felt")),
+            _ => None,
+        }
+    }
 
-        // _ => Err(ReferenceParseError::InvalidStringError(String::from(value))),
-        _ => Ok(ValueAddress {
-            register: Some(Register::FP),
+    ///Converts the expression into a ValueAddress.
+    ///Only immediates and references of the form `reg + offset1`, `[reg + offset1] + offset2` (or
+    ///their dereferences) are supported. Any other expression (e.g. `cast([fp] * 2, felt)`) returns an
+    ///UnsupportedExpression error, and offsets that don't fit in an i32 return an OffsetOutOfRange error.
+    pub fn value_address(&self) -> Result<ValueAddress, ReferenceParseError> {
+        let (dereference, inner) = match self.strip_casts() {
+            ReferenceExpr::Deref(inner) => (true, inner.as_ref()),
+            expr => (false, expr),
+        };
+        let mut value_address = ValueAddress {
+            register: None,
             offset1: 0,
             offset2: 0,
             immediate: None,
-            dereference: true,
+            dereference,
             inner_dereference: false,
-        }),
+            value_type: self.value_type(),
+        };
+        let (base, offset) = inner.split_offset();
+        match base {
+            // reg + offset1
+            ReferenceExpr::Register(register) => {
+                value_address.register = Some(register.clone());
+                value_address.offset1 = offset_to_i32(&offset)?;
+            }
+            // [reg + offset1] + offset2
+            ReferenceExpr::Deref(pointer) => match pointer.split_offset() {
+                (ReferenceExpr::Register(register), offset1) => {
+                    value_address.register = Some(register.clone());
+                    value_address.offset1 = offset_to_i32(&offset1)?;
+                    if dereference {
+                        value_address.offset2 = offset_to_i32(&offset)?;
+                        value_address.inner_dereference = true;
+                    } else if !offset.is_zero() {
+                        value_address.immediate = Some(offset);
+                    }
+                }
+                _ => return Err(ReferenceParseError::UnsupportedExpression(self.to_string())),
+            },
+            ReferenceExpr::Immediate(immediate) => {
+                value_address.immediate = Some(immediate + offset)
+            }
+            _ => return Err(ReferenceParseError::UnsupportedExpression(self.to_string())),
+        }
+        Ok(value_address)
     }
 }
-// parse string values of format `[cast(reg, *felt)]`
-fn parse_dereference_no_offsets(
-    splitted_value_str: &[&str],
-) -> Result<ValueAddress, ReferenceParseError> {
-    let register = parse_register(splitted_value_str);
-
-    Ok(ValueAddress {
-        register,
-        offset1: 0,
-        offset2: 0,
-        immediate: None,
-        dereference: true,
-        inner_dereference: false,
-    })
-}
 
-// parse string values of format `[cast(reg + offset1, *felt)]`
-fn parse_dereference_with_one_offset(
-    splitted_value_str: &[&str],
-) -> Result<ValueAddress, ReferenceParseError> {
-    let mut deref = parse_dereference_no_offsets(splitted_value_str)?;
+//Writes the expression in the syntax of the reference language, e.g. `[cast(fp + (-3), felt*)]`
+impl fmt::Display for ReferenceExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReferenceExpr::Register(Register::AP) => write!(f, "ap"),
+            ReferenceExpr::Register(Register::FP) => write!(f, "fp"),
+            ReferenceExpr::Immediate(value) if value.is_negative() => write!(f, "({})", value),
+            ReferenceExpr::Immediate(value) => write!(f, "{}", value),
+            ReferenceExpr::Deref(inner) => write!(f, "[{}]", inner),
+            ReferenceExpr::Cast(inner, cairo_type) => write!(f, "cast({}, {})", inner, cairo_type),
+            ReferenceExpr::Add(lhs, rhs) => write!(f, "{} + {}", lhs, rhs),
+            ReferenceExpr::Sub(lhs, rhs) => {
+                write!(f, "{} - ", lhs)?;
+                fmt_operand(rhs, f)
+            }
+            ReferenceExpr::Mul(lhs, rhs) => {
+                fmt_operand(lhs, f)?;
+                write!(f, " * ")?;
+                fmt_operand(rhs, f)
+            }
+            ReferenceExpr::Neg(inner) => {
+                write!(f, "-")?;
+                fmt_operand(inner, f)
+            }
+        }
+    }
+}
 
-    let mut offset1_str = splitted_value_str[1].split(',').collect::<Vec<_>>()[0].to_string();
-    offset1_str.retain(|c| !r#"()]"#.contains(c));
+//Writes an operand of a product, a negation or the right side of a subtraction, wrapping sums and
+//differences in parentheses
+fn fmt_operand(expr: &ReferenceExpr, f: &mut fmt::Formatter) -> fmt::Result {
+    match expr {
+        ReferenceExpr::Add(_, _) | ReferenceExpr::Sub(_, _) => write!(f, "({})", expr),
+        _ => write!(f, "{}", expr),
+    }
+}
 
-    let offset1: i32 = match offset1_str.parse() {
-        Ok(offset1) => offset1,
-        // for the moment, references with values that overflow i32 are not important, they are just dummy references.
-        Err(e) => match e.kind() {
-            IntErrorKind::PosOverflow => 0,
-            _ => return Err(ReferenceParseError::IntError(e)),
-        },
-    };
-    deref.offset1 = offset1;
+struct ReferenceParser<'a> {
+    value: &'a str,
+    chars: Peekable<Chars<'a>>,
+}
 
-    if splitted_value_str[0].contains(&"([") {
-        deref.inner_dereference = true;
-        return Ok(deref);
+impl<'a> ReferenceParser<'a> {
+    fn new(value: &'a str) -> Self {
+        ReferenceParser {
+            value,
+            chars: value.chars().peekable(),
+        }
     }
 
-    Ok(deref)
-}
+    fn error(&self) -> ReferenceParseError {
+        ReferenceParseError::InvalidStringError(String::from(self.value))
+    }
 
-// parse string values of format `[cast([reg + offset1] + offset2, *felt)]`
-fn parse_dereference_with_two_offsets(
-    splitted_value_str: &[&str],
-) -> Result<ValueAddress, ReferenceParseError> {
-    let mut deref = parse_dereference_with_one_offset(splitted_value_str)?;
-
-    let mut offset2_str = splitted_value_str[2].split(',').collect::<Vec<_>>()[0].to_string();
-    offset2_str.retain(|c| !r#"()"#.contains(c));
-
-    let offset2: i32 = match offset2_str.parse() {
-        Ok(offset2) => offset2,
-        // for the moment, references with values that overflow i32 are not important, they are just dummy references.
-        Err(e) => match e.kind() {
-            IntErrorKind::PosOverflow => 0,
-            _ => return Err(ReferenceParseError::IntError(e)),
-        },
-    };
-    deref.offset2 = offset2;
-    deref.inner_dereference = true;
-
-    Ok(deref)
-}
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
 
-pub fn parse_reference(value: &str) -> Result<ValueAddress, ReferenceParseError> {
-    let splitted: Vec<_> = value.split(" + ").collect();
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.peek().copied()
+    }
 
-    match splitted.len() {
-        1 => parse_reference_no_offsets(&splitted),
-        2 => parse_reference_with_one_offset(&splitted),
-        3 => parse_reference_with_two_offsets(&splitted),
+    fn expect(&mut self, expected: char) -> Result<(), ReferenceParseError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.chars.next();
+                Ok(())
+            }
+            _ => Err(self.error()),
+        }
+    }
 
-        // FIXME this match arm is handled like this just to avoid unnecesary deserialization errors.
-        // For the moment, the ValueAddress structs returned in ths arm are not used in hints, so they are not important.
-        // issue: https://github.com/lambdaclass/cleopatra_cairo/issues/280
+    fn parse_word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+            word.push(c);
+        }
+        word
+    }
 
-        // _ => Err(ReferenceParseError::InvalidStringError(String::from(value))),
-        _ => Ok(ValueAddress {
-            register: Some(Register::FP),
-            offset1: 0,
-            offset2: 0,
-            immediate: None,
-            dereference: false,
-            inner_dereference: false,
-        }),
+    // expr := term (('+' | '-') term)*
+    fn parse_expr(&mut self) -> Result<ReferenceExpr, ReferenceParseError> {
+        let mut expr = self.parse_term()?;
+        loop {
+            match self.peek() {
+                Some('+') => {
+                    self.chars.next();
+                    expr = ReferenceExpr::Add(Box::new(expr), Box::new(self.parse_term()?));
+                }
+                Some('-') => {
+                    self.chars.next();
+                    expr = ReferenceExpr::Sub(Box::new(expr), Box::new(self.parse_term()?));
+                }
+                _ => return Ok(expr),
+            }
+        }
     }
-}
 
-fn parse_reference_no_offsets(
-    splitted_value_str: &[&str],
-) -> Result<ValueAddress, ReferenceParseError> {
-    let register = parse_register(splitted_value_str);
-
-    Ok(ValueAddress {
-        register,
-        offset1: 0,
-        offset2: 0,
-        immediate: None,
-        dereference: false,
-        inner_dereference: false,
-    })
-}
+    // term := unary ('*' unary)*
+    fn parse_term(&mut self) -> Result<ReferenceExpr, ReferenceParseError> {
+        let mut expr = self.parse_unary()?;
+        while self.peek() == Some('*') {
+            self.chars.next();
+            expr = ReferenceExpr::Mul(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
 
-fn parse_reference_with_one_offset(
-    splitted_value_str: &[&str],
-) -> Result<ValueAddress, ReferenceParseError> {
-    let mut refe = parse_reference_no_offsets(splitted_value_str)?;
-
-    let mut offset1_str = splitted_value_str[1].split(',').collect::<Vec<_>>()[0].to_string();
-    offset1_str.retain(|c| !r#"()]"#.contains(c));
-
-    let offset1: i32 = match offset1_str.parse() {
-        Ok(offset1) => offset1,
-        // for the moment, references with values that overflow i32 are not important, they are just dummy references.
-        Err(e) => match e.kind() {
-            IntErrorKind::PosOverflow => 0,
-            _ => return Err(ReferenceParseError::IntError(e)),
-        },
-    };
-    refe.offset1 = offset1;
-
-    Ok(refe)
-}
+    // unary := '-' unary | atom
+    fn parse_unary(&mut self) -> Result<ReferenceExpr, ReferenceParseError> {
+        if self.peek() == Some('-') {
+            self.chars.next();
+            return Ok(match self.parse_unary()? {
+                ReferenceExpr::Immediate(value) => ReferenceExpr::Immediate(-value),
+                expr => ReferenceExpr::Neg(Box::new(expr)),
+            });
+        }
+        self.parse_atom()
+    }
 
-fn parse_reference_with_two_offsets(
-    splitted_value_str: &[&str],
-) -> Result<ValueAddress, ReferenceParseError> {
-    let mut refe = parse_reference_with_one_offset(splitted_value_str)?;
+    // atom := INT | 'ap' | 'fp' | '(' expr ')' | '[' expr ']' | 'cast' '(' expr ',' type ')'
+    fn parse_atom(&mut self) -> Result<ReferenceExpr, ReferenceParseError> {
+        match self.peek() {
+            Some('(') => {
+                self.chars.next();
+                let expr = self.parse_expr()?;
+                self.expect(')')?;
+                Ok(expr)
+            }
+            Some('[') => {
+                self.chars.next();
+                let expr = self.parse_expr()?;
+                self.expect(']')?;
+                Ok(ReferenceExpr::Deref(Box::new(expr)))
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let word = self.parse_word();
+                let value = match word.strip_prefix("0x") {
+                    Some(hex) => {
+                        BigInt::parse_bytes(hex.as_bytes(), 16).ok_or_else(|| self.error())?
+                    }
+                    None => word.parse().map_err(ReferenceParseError::BigIntError)?,
+                };
+                Ok(ReferenceExpr::Immediate(value))
+            }
+            Some(c) if c.is_alphabetic() => match self.parse_word().as_str() {
+                "ap" => Ok(ReferenceExpr::Register(Register::AP)),
+                "fp" => Ok(ReferenceExpr::Register(Register::FP)),
+                "cast" => {
+                    self.expect('(')?;
+                    let expr = self.parse_expr()?;
+                    self.expect(',')?;
+                    let cairo_type = self.parse_type()?;
+                    self.expect(')')?;
+                    Ok(ReferenceExpr::Cast(Box::new(expr), cairo_type))
+                }
+                _ => Err(self.error()),
+            },
+            _ => Err(self.error()),
+        }
+    }
 
-    let mut immediate_str = splitted_value_str[2].split(',').collect::<Vec<_>>()[0].to_string();
-    immediate_str.retain(|c| !r#"()"#.contains(c));
+    // Reads a type (e.g. `felt*`, `__main__.MyStruct*`, `(felt, felt)`) up to the closing
+    // parenthesis of the cast
+    fn parse_type(&mut self) -> Result<String, ReferenceParseError> {
+        self.skip_whitespace();
+        let mut cairo_type = String::new();
+        let mut depth = 0_usize;
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth == 0 => break,
+                ')' => depth -= 1,
+                _ => (),
+            }
+            cairo_type.push(c);
+            self.chars.next();
+        }
+        let cairo_type = cairo_type.trim_end();
+        if cairo_type.is_empty() {
+            return Err(self.error());
+        }
+        Ok(cairo_type.to_string())
+    }
+}
 
-    let immediate: BigInt = match immediate_str.parse() {
-        Ok(immediate) => immediate,
-        Err(e) => return Err(ReferenceParseError::BigIntError(e)),
-    };
+///Parses a reference expression into its syntax tree
+pub fn parse_reference_expr(value: &str) -> Result<ReferenceExpr, ReferenceParseError> {
+    let mut parser = ReferenceParser::new(value);
+    let expr = parser.parse_expr()?;
+    //The whole string must be consumed
+    if parser.peek().is_some() {
+        return Err(parser.error());
+    }
+    Ok(expr)
+}
 
-    refe.immediate = Some(immediate);
+///Parses a reference expression and converts it into a ValueAddress
+pub fn parse_value_address(value: &str) -> Result<ValueAddress, ReferenceParseError> {
+    parse_reference_expr(value)?.value_address()
+}
 
-    Ok(refe)
+fn offset_to_i32(offset: &BigInt) -> Result<i32, ReferenceParseError> {
+    offset
+        .to_i32()
+        .ok_or_else(|| ReferenceParseError::OffsetOutOfRange(offset.clone()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bigint;
+    use crate::{bigint, bigint64};
     use num_traits::FromPrimitive;
 
     #[test]
     fn parse_dereference_with_one_offset_test() {
         let value_string: &str = "[cast(fp + (-3), felt*)]";
-        let splitted_value: Vec<&str> = value_string.split(" + ").collect();
 
-        let parsed_value = parse_dereference_with_one_offset(&splitted_value).unwrap();
+        let parsed_value = parse_value_address(value_string).unwrap();
 
         let value_address = ValueAddress {
             register: Some(Register::FP),
@@ -242,6 +386,7 @@ mod tests {
             immediate: None,
             dereference: true,
             inner_dereference: false,
+            value_type: Some(String::from("felt")),
         };
 
         assert_eq!(value_address, parsed_value);
@@ -250,9 +395,8 @@ mod tests {
     #[test]
     fn parse_dereference_with_one_offset_and_inner_dereference_test() {
         let value_string: &str = "[cast([fp + (-3)], felt*)]";
-        let splitted_value: Vec<&str> = value_string.split(" + ").collect();
 
-        let parsed_value = parse_dereference_with_one_offset(&splitted_value).unwrap();
+        let parsed_value = parse_value_address(value_string).unwrap();
 
         let value_address = ValueAddress {
             register: Some(Register::FP),
@@ -261,6 +405,7 @@ mod tests {
             immediate: None,
             dereference: true,
             inner_dereference: true,
+            value_type: Some(String::from("felt")),
         };
 
         assert_eq!(value_address, parsed_value);
@@ -269,9 +414,8 @@ mod tests {
     #[test]
     fn parse_dereference_with_two_offsets_test() {
         let value_string: &str = "[cast([fp + (-4)] + 1, felt*)]";
-        let splitted_value: Vec<&str> = value_string.split(" + ").collect();
 
-        let parsed_value = parse_dereference_with_two_offsets(&splitted_value).unwrap();
+        let parsed_value = parse_value_address(value_string).unwrap();
 
         let value_address = ValueAddress {
             register: Some(Register::FP),
@@ -280,6 +424,7 @@ mod tests {
             immediate: None,
             dereference: true,
             inner_dereference: true,
+            value_type: Some(String::from("felt")),
         };
 
         assert_eq!(value_address, parsed_value);
@@ -288,9 +433,8 @@ mod tests {
     #[test]
     fn parse_dereference_no_offsets_test() {
         let value_string: &str = "[cast(fp, felt*)]";
-        let splitted_value: Vec<&str> = value_string.split(" + ").collect();
 
-        let parsed_value = parse_dereference_no_offsets(&splitted_value).unwrap();
+        let parsed_value = parse_value_address(value_string).unwrap();
 
         let value_address = ValueAddress {
             register: Some(Register::FP),
@@ -299,6 +443,7 @@ mod tests {
             immediate: None,
             dereference: true,
             inner_dereference: false,
+            value_type: Some(String::from("felt")),
         };
 
         assert_eq!(value_address, parsed_value);
@@ -307,9 +452,8 @@ mod tests {
     #[test]
     fn parse_reference_with_one_offset_test() {
         let value_string: &str = "cast(fp + (-3), felt*)";
-        let splitted_value: Vec<&str> = value_string.split(" + ").collect();
 
-        let parsed_value = parse_reference_with_one_offset(&splitted_value).unwrap();
+        let parsed_value = parse_value_address(value_string).unwrap();
 
         let value_address = ValueAddress {
             register: Some(Register::FP),
@@ -318,6 +462,7 @@ mod tests {
             immediate: None,
             dereference: false,
             inner_dereference: false,
+            value_type: Some(String::from("felt*")),
         };
 
         assert_eq!(value_address, parsed_value);
@@ -326,9 +471,8 @@ mod tests {
     #[test]
     fn parse_reference_with_two_offsets_test() {
         let value_string: &str = "cast([fp + (-4)] + 1, felt*)";
-        let splitted_value: Vec<&str> = value_string.split(" + ").collect();
 
-        let parsed_value = parse_reference_with_two_offsets(&splitted_value).unwrap();
+        let parsed_value = parse_value_address(value_string).unwrap();
 
         let value_address = ValueAddress {
             register: Some(Register::FP),
@@ -337,6 +481,7 @@ mod tests {
             immediate: Some(bigint!(1)),
             dereference: false,
             inner_dereference: false,
+            value_type: Some(String::from("felt*")),
         };
 
         assert_eq!(value_address, parsed_value);
@@ -345,9 +490,8 @@ mod tests {
     #[test]
     fn parse_reference_no_offsets_test() {
         let value_string: &str = "cast(fp, felt*)";
-        let splitted_value: Vec<&str> = value_string.split(" + ").collect();
 
-        let parsed_value = parse_reference_no_offsets(&splitted_value).unwrap();
+        let parsed_value = parse_value_address(value_string).unwrap();
 
         let value_address = ValueAddress {
             register: Some(Register::FP),
@@ -356,8 +500,169 @@ mod tests {
             immediate: None,
             dereference: false,
             inner_dereference: false,
+            value_type: Some(String::from("felt*")),
+        };
+
+        assert_eq!(value_address, parsed_value);
+    }
+
+    #[test]
+    fn parse_dereference_ap_with_struct_type_test() {
+        let value_string: &str = "[cast(ap + (-2), starkware.cairo.common.uint256.Uint256*)]";
+
+        let parsed_value = parse_value_address(value_string).unwrap();
+
+        let value_address = ValueAddress {
+            register: Some(Register::AP),
+            offset1: -2,
+            offset2: 0,
+            immediate: None,
+            dereference: true,
+            inner_dereference: false,
+            value_type: Some(String::from("starkware.cairo.common.uint256.Uint256")),
+        };
+
+        assert_eq!(value_address, parsed_value);
+    }
+
+    #[test]
+    fn parse_reference_expr_syntax_tree_test() {
+        let value_string: &str = "[cast([fp + (-4)] + 1, __main__.MyStruct**)]";
+
+        assert_eq!(
+            parse_reference_expr(value_string),
+            Ok(ReferenceExpr::Deref(Box::new(ReferenceExpr::Cast(
+                Box::new(ReferenceExpr::Add(
+                    Box::new(ReferenceExpr::Deref(Box::new(ReferenceExpr::Add(
+                        Box::new(ReferenceExpr::Register(Register::FP)),
+                        Box::new(ReferenceExpr::Immediate(bigint!(-4)))
+                    )))),
+                    Box::new(ReferenceExpr::Immediate(bigint!(1)))
+                )),
+                String::from("__main__.MyStruct**")
+            ))))
+        );
+    }
+
+    #[test]
+    fn parse_reference_expr_arithmetic_precedence_test() {
+        let value_string: &str = "cast([ap + (-1)] * 2 + [fp + 3] - 1, felt)";
+
+        assert_eq!(
+            parse_reference_expr(value_string),
+            Ok(ReferenceExpr::Cast(
+                Box::new(ReferenceExpr::Sub(
+                    Box::new(ReferenceExpr::Add(
+                        Box::new(ReferenceExpr::Mul(
+                            Box::new(ReferenceExpr::Deref(Box::new(ReferenceExpr::Add(
+                                Box::new(ReferenceExpr::Register(Register::AP)),
+                                Box::new(ReferenceExpr::Immediate(bigint!(-1)))
+                            )))),
+                            Box::new(ReferenceExpr::Immediate(bigint!(2)))
+                        )),
+                        Box::new(ReferenceExpr::Deref(Box::new(ReferenceExpr::Add(
+                            Box::new(ReferenceExpr::Register(Register::FP)),
+                            Box::new(ReferenceExpr::Immediate(bigint!(3)))
+                        ))))
+                    )),
+                    Box::new(ReferenceExpr::Immediate(bigint!(1)))
+                )),
+                String::from("felt")
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_reference_with_more_than_three_terms_test() {
+        //These references used to be replaced by a dummy fp + 0 reference
+        let value_string: &str = "cast([fp + (-4)] + [fp + (-3)] + 2, felt)";
+
+        assert_eq!(
+            parse_value_address(value_string),
+            Err(ReferenceParseError::UnsupportedExpression(String::from(
+                value_string
+            )))
+        );
+    }
+
+    #[test]
+    fn display_reference_expr_test() {
+        for value_string in [
+            "[cast(fp + (-3), felt*)]",
+            "cast([fp + (-4)] + [fp + (-3)] + 2, felt)",
+            "[ap] * 2",
+            "-(ap + 1) * [fp - (ap + 2)]",
+            "cast(ap, (felt, __main__.MyStruct*)*)",
+        ] {
+            assert_eq!(
+                parse_reference_expr(value_string).unwrap().to_string(),
+                value_string
+            );
+        }
+    }
+
+    #[test]
+    fn parse_reference_offset_out_of_range_test() {
+        let value_string: &str = "[cast(fp + 4294967296, felt*)]";
+
+        assert_eq!(
+            parse_value_address(value_string),
+            Err(ReferenceParseError::OffsetOutOfRange(bigint64!(4294967296)))
+        );
+    }
+
+    #[test]
+    fn parse_reference_immediate_test() {
+        let value_string: &str = "cast(340282366920938463463374607431768211456, felt)";
+
+        let parsed_value = parse_value_address(value_string).unwrap();
+
+        let value_address = ValueAddress {
+            register: None,
+            offset1: 0,
+            offset2: 0,
+            immediate: Some(bigint!(1) << 128_usize),
+            dereference: false,
+            inner_dereference: false,
+            value_type: Some(String::from("felt")),
         };
 
         assert_eq!(value_address, parsed_value);
     }
+
+    #[test]
+    fn parse_reference_unbalanced_brackets_test() {
+        let value_string: &str = "[cast(fp + (-3), felt*)";
+
+        assert_eq!(
+            parse_value_address(value_string),
+            Err(ReferenceParseError::InvalidStringError(String::from(
+                value_string
+            )))
+        );
+    }
+
+    #[test]
+    fn parse_reference_unknown_register_test() {
+        let value_string: &str = "[cast(pc + 1, felt*)]";
+
+        assert_eq!(
+            parse_value_address(value_string),
+            Err(ReferenceParseError::InvalidStringError(String::from(
+                value_string
+            )))
+        );
+    }
+
+    #[test]
+    fn parse_reference_trailing_characters_test() {
+        let value_string: &str = "[cast(fp, felt*)] ]";
+
+        assert_eq!(
+            parse_value_address(value_string),
+            Err(ReferenceParseError::InvalidStringError(String::from(
+                value_string
+            )))
+        );
+    }
 }
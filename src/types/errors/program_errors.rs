@@ -5,6 +5,7 @@ use std::io;
 pub enum ProgramError {
     IO(io::Error),
     Parse(serde_json::Error),
+    ConstWithoutValue(String),
 }
 
 impl From<serde_json::Error> for ProgramError {
@@ -30,6 +31,19 @@ impl fmt::Display for ProgramError {
                 write!(f, "Parsing error: ")?;
                 error.fmt(f)
             }
+            ProgramError::ConstWithoutValue(name) => {
+                write!(f, "Constant {} has no value", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProgramError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProgramError::IO(error) => Some(error),
+            ProgramError::Parse(error) => Some(error),
+            ProgramError::ConstWithoutValue(_) => None,
         }
     }
 }
@@ -1,18 +1,95 @@
-use crate::vm::errors::exec_scope_errors::ExecScopeError;
+use crate::vm::errors::{exec_scope_errors::ExecScopeError, vm_errors::VirtualMachineError};
 use num_bigint::BigInt;
+use std::any::Any;
 use std::collections::HashMap;
+use std::fmt;
 
 #[derive(Eq, PartialEq, Debug)]
 pub struct ExecutionScopes {
     pub data: Vec<HashMap<String, PyValueType>>,
 }
 
-#[derive(Eq, PartialEq, Debug)]
 pub enum PyValueType {
     BigInt(BigInt),
     List(Vec<BigInt>),
     Dictionary(HashMap<BigInt, BigInt>),
     KeyToListMap(HashMap<BigInt, Vec<BigInt>>),
+    ListU64(Vec<u64>),
+    KeyToListU64Map(HashMap<BigInt, Vec<u64>>),
+    //Any other value (tuples, relocatables, sets, hint specific structs...)
+    Any(Box<dyn Any>),
+}
+
+impl PyValueType {
+    pub fn new_any<T: Any>(value: T) -> PyValueType {
+        PyValueType::Any(Box::new(value))
+    }
+
+    //Returns the wrapped value, so it can be downcasted to its concrete type
+    fn as_any(&self) -> &dyn Any {
+        match self {
+            PyValueType::BigInt(value) => value,
+            PyValueType::List(value) => value,
+            PyValueType::Dictionary(value) => value,
+            PyValueType::KeyToListMap(value) => value,
+            PyValueType::ListU64(value) => value,
+            PyValueType::KeyToListU64Map(value) => value,
+            PyValueType::Any(value) => value.as_ref(),
+        }
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        match self {
+            PyValueType::BigInt(value) => value,
+            PyValueType::List(value) => value,
+            PyValueType::Dictionary(value) => value,
+            PyValueType::KeyToListMap(value) => value,
+            PyValueType::ListU64(value) => value,
+            PyValueType::KeyToListU64Map(value) => value,
+            PyValueType::Any(value) => value.as_mut(),
+        }
+    }
+}
+
+//Values of arbitrary types can't be compared by value, so two Any values are equal only if they
+//are the same value: they have the same type and live at the same address
+impl PartialEq for PyValueType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (PyValueType::BigInt(a), PyValueType::BigInt(b)) => a == b,
+            (PyValueType::List(a), PyValueType::List(b)) => a == b,
+            (PyValueType::Dictionary(a), PyValueType::Dictionary(b)) => a == b,
+            (PyValueType::KeyToListMap(a), PyValueType::KeyToListMap(b)) => a == b,
+            (PyValueType::ListU64(a), PyValueType::ListU64(b)) => a == b,
+            (PyValueType::KeyToListU64Map(a), PyValueType::KeyToListU64Map(b)) => a == b,
+            (PyValueType::Any(a), PyValueType::Any(b)) => {
+                a.as_ref().type_id() == b.as_ref().type_id()
+                    && std::ptr::eq(
+                        a.as_ref() as *const dyn Any as *const u8,
+                        b.as_ref() as *const dyn Any as *const u8,
+                    )
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for PyValueType {}
+
+impl fmt::Debug for PyValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PyValueType::BigInt(value) => f.debug_tuple("BigInt").field(value).finish(),
+            PyValueType::List(value) => f.debug_tuple("List").field(value).finish(),
+            PyValueType::Dictionary(value) => f.debug_tuple("Dictionary").field(value).finish(),
+            PyValueType::KeyToListMap(value) => f.debug_tuple("KeyToListMap").field(value).finish(),
+            PyValueType::ListU64(value) => f.debug_tuple("ListU64").field(value).finish(),
+            PyValueType::KeyToListU64Map(value) => {
+                f.debug_tuple("KeyToListU64Map").field(value).finish()
+            }
+            PyValueType::Any(_) => f.write_str("Any(..)"),
+        }
+    }
 }
 
 impl ExecutionScopes {
@@ -50,6 +127,40 @@ impl ExecutionScopes {
             local_variables.remove(&var_name.to_string());
         }
     }
+
+    ///Returns a reference to the value of a variable in the current scope, if it holds a T
+    pub fn get<T: Any>(&self, var_name: &str) -> Result<&T, VirtualMachineError> {
+        get_from_scope(self.data.last(), var_name)
+    }
+
+    ///Returns a reference to the value of a variable in the main scope, if it holds a T.
+    ///Variables of the main scope, such as program_input, are visible from every scope
+    pub fn get_from_main_scope<T: Any>(&self, var_name: &str) -> Result<&T, VirtualMachineError> {
+        get_from_scope(self.data.first(), var_name)
+    }
+
+    ///Returns a mutable reference to the value of a variable in the current scope, if it holds a T
+    pub fn get_mut<T: Any>(&mut self, var_name: &str) -> Result<&mut T, VirtualMachineError> {
+        self.data
+            .last_mut()
+            .and_then(|local_variables| local_variables.get_mut(var_name))
+            .ok_or_else(|| VirtualMachineError::VariableNotInScopeError(var_name.to_string()))?
+            .as_any_mut()
+            .downcast_mut::<T>()
+            .ok_or_else(|| VirtualMachineError::ScopeVariableTypeError(var_name.to_string()))
+    }
+}
+
+fn get_from_scope<'a, T: Any>(
+    scope: Option<&'a HashMap<String, PyValueType>>,
+    var_name: &str,
+) -> Result<&'a T, VirtualMachineError> {
+    scope
+        .and_then(|local_variables| local_variables.get(var_name))
+        .ok_or_else(|| VirtualMachineError::VariableNotInScopeError(var_name.to_string()))?
+        .as_any()
+        .downcast_ref::<T>()
+        .ok_or_else(|| VirtualMachineError::ScopeVariableTypeError(var_name.to_string()))
 }
 
 impl Default for ExecutionScopes {
@@ -62,7 +173,9 @@ impl Default for ExecutionScopes {
 mod tests {
     use super::*;
     use crate::bigint;
+    use crate::types::relocatable::Relocatable;
     use num_traits::FromPrimitive;
+    use std::collections::HashSet;
 
     #[test]
     fn initialize_execution_scopes() {
@@ -210,4 +323,138 @@ mod tests {
 
         assert!(scopes.exit_scope().is_err());
     }
+
+    #[test]
+    fn get_existing_variant_as_type() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.assign_or_update_variable("n", PyValueType::BigInt(bigint!(5)));
+        scopes.assign_or_update_variable("l", PyValueType::List(vec![bigint!(1)]));
+
+        assert_eq!(scopes.get::<BigInt>("n"), Ok(&bigint!(5)));
+        assert_eq!(scopes.get::<Vec<BigInt>>("l"), Ok(&vec![bigint!(1)]));
+    }
+
+    #[test]
+    fn get_any_values() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.assign_or_update_variable(
+            "pair",
+            PyValueType::new_any((Relocatable::from((1, 2)), bigint!(3))),
+        );
+        scopes.assign_or_update_variable(
+            "nested",
+            PyValueType::new_any(vec![vec![1_u64, 2], vec![3]]),
+        );
+        scopes.assign_or_update_variable("set", PyValueType::new_any(HashSet::from([bigint!(4)])));
+
+        assert_eq!(
+            scopes.get::<(Relocatable, BigInt)>("pair"),
+            Ok(&(Relocatable::from((1, 2)), bigint!(3)))
+        );
+        assert_eq!(
+            scopes.get::<Vec<Vec<u64>>>("nested"),
+            Ok(&vec![vec![1_u64, 2], vec![3]])
+        );
+        assert_eq!(
+            scopes.get::<HashSet<BigInt>>("set"),
+            Ok(&HashSet::from([bigint!(4)]))
+        );
+    }
+
+    #[test]
+    fn get_mut_updates_value() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.assign_or_update_variable("set", PyValueType::new_any(HashSet::<u64>::new()));
+        scopes.assign_or_update_variable("n", PyValueType::BigInt(bigint!(1)));
+
+        scopes.get_mut::<HashSet<u64>>("set").unwrap().insert(7);
+        *scopes.get_mut::<BigInt>("n").unwrap() += 1;
+
+        assert_eq!(
+            scopes.get::<HashSet<u64>>("set"),
+            Ok(&HashSet::from([7_u64]))
+        );
+        assert_eq!(
+            scopes.get_local_variables().unwrap().get("n"),
+            Some(&PyValueType::BigInt(bigint!(2)))
+        );
+    }
+
+    #[test]
+    fn get_variable_with_wrong_type() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.assign_or_update_variable("n", PyValueType::BigInt(bigint!(1)));
+
+        assert_eq!(
+            scopes.get::<u64>("n"),
+            Err(VirtualMachineError::ScopeVariableTypeError(String::from(
+                "n"
+            )))
+        );
+    }
+
+    #[test]
+    fn get_from_main_scope_in_nested_scope() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.assign_or_update_variable("n", PyValueType::BigInt(bigint!(1)));
+        scopes.enter_scope(HashMap::from([(
+            String::from("n"),
+            PyValueType::BigInt(bigint!(2)),
+        )]));
+
+        assert_eq!(scopes.get::<BigInt>("n"), Ok(&bigint!(2)));
+        assert_eq!(scopes.get_from_main_scope::<BigInt>("n"), Ok(&bigint!(1)));
+        assert_eq!(
+            scopes.get_from_main_scope::<BigInt>("m"),
+            Err(VirtualMachineError::VariableNotInScopeError(String::from(
+                "m"
+            )))
+        );
+    }
+
+    #[test]
+    fn get_variable_not_in_scope() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.assign_or_update_variable("n", PyValueType::BigInt(bigint!(1)));
+        scopes.enter_scope(HashMap::new());
+
+        assert_eq!(
+            scopes.get::<BigInt>("n"),
+            Err(VirtualMachineError::VariableNotInScopeError(String::from(
+                "n"
+            )))
+        );
+        assert_eq!(
+            scopes.get_mut::<BigInt>("n"),
+            Err(VirtualMachineError::VariableNotInScopeError(String::from(
+                "n"
+            )))
+        );
+    }
+
+    #[test]
+    fn enter_scope_with_any_locals() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.enter_scope(HashMap::from([
+            (String::from("n"), PyValueType::BigInt(bigint!(2))),
+            (
+                String::from("ptr"),
+                PyValueType::new_any(Relocatable::from((2, 0))),
+            ),
+        ]));
+
+        assert_eq!(scopes.get::<BigInt>("n"), Ok(&bigint!(2)));
+        assert_eq!(
+            scopes.get::<Relocatable>("ptr"),
+            Ok(&Relocatable::from((2, 0)))
+        );
+    }
+
+    #[test]
+    fn any_values_are_only_equal_to_themselves() {
+        let value = PyValueType::new_any(Relocatable::from((2, 0)));
+        assert_eq!(value, value);
+        assert_ne!(value, PyValueType::new_any(Relocatable::from((2, 0))));
+        assert_ne!(value, PyValueType::new_any(bigint!(2)));
+    }
 }
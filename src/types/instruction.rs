@@ -1,5 +1,6 @@
 use num_bigint::BigInt;
 use serde::Deserialize;
+use std::fmt;
 
 #[derive(Deserialize, Debug, PartialEq, Clone)]
 pub enum Register {
@@ -77,4 +78,113 @@ impl Instruction {
             None => 1,
         }
     }
+
+    fn dst_to_string(&self) -> String {
+        format!("[{} + {}]", self.dst_register, self.off0)
+    }
+
+    fn op0_to_string(&self) -> String {
+        format!("[{} + {}]", self.op0_register, self.off1)
+    }
+
+    fn op1_to_string(&self) -> String {
+        match (&self.op1_addr, &self.imm) {
+            (Op1Addr::Imm, Some(imm)) => imm.to_string(),
+            (Op1Addr::Imm, None) => format!("[pc + {}]", self.off2),
+            (Op1Addr::AP, _) => format!("[ap + {}]", self.off2),
+            (Op1Addr::FP, _) => format!("[fp + {}]", self.off2),
+            (Op1Addr::Op0, _) => format!("[{} + {}]", self.op0_to_string(), self.off2),
+        }
+    }
+
+    fn res_to_string(&self) -> String {
+        match self.res {
+            Res::Op1 | Res::Unconstrained => self.op1_to_string(),
+            Res::Add => format!("{} + {}", self.op0_to_string(), self.op1_to_string()),
+            Res::Mul => format!("{} * {}", self.op0_to_string(), self.op1_to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Register::AP => write!(f, "ap"),
+            Register::FP => write!(f, "fp"),
+        }
+    }
+}
+
+///Renders the instruction in Cairo assembly syntax, e.g. `[ap + 0] = [fp + -3] + 5; ap++`
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.opcode, &self.pc_update) {
+            (Opcode::AssertEq, _) => {
+                write!(f, "{} = {}", self.dst_to_string(), self.res_to_string())?
+            }
+            (Opcode::Call, PcUpdate::Jump) => write!(f, "call abs {}", self.res_to_string())?,
+            (Opcode::Call, _) => write!(f, "call rel {}", self.res_to_string())?,
+            (Opcode::Ret, _) => write!(f, "ret")?,
+            (Opcode::NOp, PcUpdate::Jump) => write!(f, "jmp abs {}", self.res_to_string())?,
+            (Opcode::NOp, PcUpdate::JumpRel) => write!(f, "jmp rel {}", self.res_to_string())?,
+            (Opcode::NOp, PcUpdate::Jnz) => write!(
+                f,
+                "jmp rel {} if {} != 0",
+                self.op1_to_string(),
+                self.dst_to_string()
+            )?,
+            (Opcode::NOp, PcUpdate::Regular) => {
+                if self.ap_update == ApUpdate::Add {
+                    return write!(f, "ap += {}", self.res_to_string());
+                }
+                write!(f, "nop")?
+            }
+        }
+        match self.ap_update {
+            ApUpdate::Add1 => write!(f, "; ap++"),
+            ApUpdate::Add => write!(f, "; ap += {}", self.res_to_string()),
+            ApUpdate::Regular | ApUpdate::Add2 => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bigint;
+    use crate::vm::decoding::decoder::decode_instruction;
+    use num_bigint::BigInt;
+    use num_traits::FromPrimitive;
+
+    #[test]
+    fn display_assert_eq_add_imm() {
+        let instruction = decode_instruction(0x482680017ffd8000, Some(bigint!(5))).unwrap();
+        assert_eq!(instruction.to_string(), "[ap + 0] = [fp + -3] + 5; ap++");
+    }
+
+    #[test]
+    fn display_assert_eq_add_registers() {
+        let instruction = decode_instruction(0x48307ffe7fff8000, None).unwrap();
+        assert_eq!(
+            instruction.to_string(),
+            "[ap + 0] = [ap + -1] + [ap + -2]; ap++"
+        );
+    }
+
+    #[test]
+    fn display_call_rel() {
+        let instruction = decode_instruction(0x1104800180018000, Some(bigint!(7))).unwrap();
+        assert_eq!(instruction.to_string(), "call rel 7");
+    }
+
+    #[test]
+    fn display_jnz() {
+        let instruction = decode_instruction(0x0206800180007fff, Some(bigint!(3))).unwrap();
+        assert_eq!(instruction.to_string(), "jmp rel 3 if [ap + -1] != 0");
+    }
+
+    #[test]
+    fn display_ret() {
+        let instruction = decode_instruction(0x208b7fff7fff7ffe, None).unwrap();
+        assert_eq!(instruction.to_string(), "ret");
+    }
 }
@@ -1,10 +1,12 @@
-use crate::serde::deserialize_program::{deserialize_program, HintParams, ReferenceManager};
+use crate::serde::deserialize_program::{
+    deserialize_program, DebugInfo, HintParams, Identifier, Member, ReferenceManager,
+};
 use crate::types::errors::program_errors::ProgramError;
 use crate::types::relocatable::MaybeRelocatable;
 use num_bigint::BigInt;
 use std::{collections::HashMap, path::Path};
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct Program {
     pub builtins: Vec<String>,
     pub prime: BigInt,
@@ -12,6 +14,10 @@ pub struct Program {
     pub main: Option<usize>,
     pub hints: HashMap<usize, Vec<HintParams>>,
     pub reference_manager: ReferenceManager,
+    pub constants: HashMap<String, BigInt>,
+    pub struct_types: HashMap<String, HashMap<String, Member>>,
+    pub identifiers: HashMap<String, Identifier>,
+    pub debug_info: Option<DebugInfo>,
 }
 
 impl Program {
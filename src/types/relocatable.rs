@@ -52,10 +52,9 @@ impl MaybeRelocatable {
             }
             MaybeRelocatable::RelocatableValue(ref rel) => {
                 let mut big_offset = rel.offset + other;
-                assert!(
-                    !big_offset.is_negative(),
-                    "Address offsets cant be negative"
-                );
+                if big_offset.is_negative() {
+                    return Err(VirtualMachineError::NegativeOffset(big_offset));
+                }
                 big_offset = big_offset.mod_floor(prime);
                 let new_offset = match big_offset.to_usize() {
                     Some(usize) => usize,
@@ -242,6 +241,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn add_int_mod_negative_offset() {
+        let addr = MaybeRelocatable::from((0, 1));
+        let error = addr.add_int_mod(&bigint!(-2), &bigint!(17));
+        assert_eq!(error, Err(VirtualMachineError::NegativeOffset(bigint!(-1))));
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "Address offsets cant be negative, got -1"
+        );
+    }
+
     #[test]
     fn add_usize_to_relocatable() {
         let addr = MaybeRelocatable::RelocatableValue(relocatable!(7, 65));
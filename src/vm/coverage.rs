@@ -0,0 +1,208 @@
+use crate::vm::errors::runner_errors::RunnerError;
+use crate::vm::errors::trace_errors::TraceError;
+use crate::vm::runners::cairo_runner::CairoRunner;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+
+/// Execution counts of the lines and functions of a single source file.
+#[derive(Debug, Default, PartialEq)]
+pub struct FileCoverage {
+    /// Number of times each line was executed, by line number. A line counts once per
+    /// execution, even if it is compiled to several instructions.
+    pub lines: BTreeMap<usize, usize>,
+    /// Start line and number of entries of each function, by name.
+    pub functions: BTreeMap<String, (usize, usize)>,
+}
+
+/// Source coverage of one or more runs, by source file name.
+#[derive(Debug, Default, PartialEq)]
+pub struct Coverage {
+    pub files: BTreeMap<String, FileCoverage>,
+}
+
+impl Coverage {
+    pub fn new() -> Coverage {
+        Coverage::default()
+    }
+
+    ///Adds the executions of a finished run to the coverage. The executed pcs are mapped to source
+    /// lines through the instruction locations of the program's debug_info, so runs of different
+    /// programs sharing source files are aggregated by file and line.
+    pub fn add_run(&mut self, runner: &CairoRunner) -> Result<(), RunnerError> {
+        let trace = runner
+            .vm
+            .trace
+            .as_ref()
+            .ok_or(RunnerError::Trace(TraceError::TraceNotEnabled))?;
+        let program = runner.get_program();
+        let instruction_locations = &program
+            .debug_info
+            .as_ref()
+            .ok_or(RunnerError::MissingDebugInfo)?
+            .instruction_locations;
+        let program_base = runner.get_program_base().ok_or(RunnerError::NoProgBase)?;
+
+        let mut executed_pcs: HashMap<usize, usize> = HashMap::new();
+        for entry in trace.iter() {
+            if entry.pc.segment_index == program_base.segment_index {
+                *executed_pcs
+                    .entry(entry.pc.offset - program_base.offset)
+                    .or_default() += 1;
+            }
+        }
+        let executions = |pc: &usize| executed_pcs.get(pc).copied().unwrap_or_default();
+
+        //A line runs as many times as the most executed of its instructions
+        let mut line_executions: HashMap<(&str, usize), usize> = HashMap::new();
+        for (pc, location) in instruction_locations.iter() {
+            let line = line_executions
+                .entry((&location.inst.input_file.filename, location.inst.start_line))
+                .or_default();
+            *line = (*line).max(executions(pc));
+        }
+        for ((filename, line), executions) in line_executions {
+            *self.file(filename).lines.entry(line).or_default() += executions;
+        }
+        for (name, identifier) in program.identifiers.iter() {
+            let pc = match (identifier.type_.as_deref(), identifier.pc) {
+                (Some("function"), Some(pc)) => pc,
+                _ => continue,
+            };
+            if let Some(location) = instruction_locations.get(&pc) {
+                let file = self.file(&location.inst.input_file.filename);
+                let function = file
+                    .functions
+                    .entry(name.clone())
+                    .or_insert((location.inst.start_line, 0));
+                function.1 += executions(&pc);
+            }
+        }
+        Ok(())
+    }
+
+    fn file(&mut self, filename: &str) -> &mut FileCoverage {
+        self.files.entry(filename.to_string()).or_default()
+    }
+
+    ///Writes the coverage as an LCOV tracefile, with one record per source file.
+    pub fn write_lcov(&self, out: &mut dyn io::Write) -> io::Result<()> {
+        for (filename, file) in self.files.iter() {
+            writeln!(out, "SF:{}", filename)?;
+            for (name, (line, _)) in file.functions.iter() {
+                writeln!(out, "FN:{},{}", line, name)?;
+            }
+            for (name, (_, entries)) in file.functions.iter() {
+                writeln!(out, "FNDA:{},{}", entries, name)?;
+            }
+            writeln!(out, "FNF:{}", file.functions.len())?;
+            writeln!(
+                out,
+                "FNH:{}",
+                file.functions
+                    .values()
+                    .filter(|(_, entries)| *entries > 0)
+                    .count()
+            )?;
+            for (line, executions) in file.lines.iter() {
+                writeln!(out, "DA:{},{}", line, executions)?;
+            }
+            writeln!(out, "LF:{}", file.lines.len())?;
+            writeln!(
+                out,
+                "LH:{}",
+                file.lines
+                    .values()
+                    .filter(|executions| **executions > 0)
+                    .count()
+            )?;
+            writeln!(out, "end_of_record")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cairo_run::{cairo_run, CairoRunConfig};
+    use crate::types::program::Program;
+    use std::path::Path;
+
+    #[test]
+    fn coverage_of_a_run() {
+        let mut program = Program::new(Path::new(
+            "cairo_programs/manually_compiled/valid_program_a.json",
+        ))
+        .unwrap();
+        //Move the instruction at pc 2 to line 2, which then has two instructions
+        let instruction_locations = &mut program.debug_info.as_mut().unwrap().instruction_locations;
+        let first_location = instruction_locations[&0].clone();
+        instruction_locations.insert(2, first_location);
+        let mut runner = CairoRunner::new(&program, true).unwrap();
+        runner.initialize_segments(None);
+        let end = runner.initialize_main_entrypoint().unwrap();
+        runner.initialize_vm().unwrap();
+        runner.run_until_pc(end).unwrap();
+        let mut coverage = Coverage::new();
+        coverage.add_run(&runner).unwrap();
+
+        let mut out = Vec::new();
+        coverage.write_lcov(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "SF:test.cairo\nFN:2,__main__.main\nFNDA:1,__main__.main\nFNF:1\nFNH:1\nDA:2,1\nDA:4,1\nDA:5,1\nLF:3\nLH:3\nend_of_record\n"
+        );
+    }
+
+    #[test]
+    fn coverage_is_aggregated_across_runs() {
+        let path = Path::new("cairo_programs/manually_compiled/valid_program_b.json");
+        let mut coverage = Coverage::new();
+        coverage
+            .add_run(
+                &cairo_run(
+                    path,
+                    &CairoRunConfig {
+                        trace_enabled: true,
+                        ..CairoRunConfig::default()
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let single_run_lines = coverage.files["main1.cairo"].lines.clone();
+        coverage
+            .add_run(
+                &cairo_run(
+                    path,
+                    &CairoRunConfig {
+                        trace_enabled: true,
+                        ..CairoRunConfig::default()
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let file = &coverage.files["main1.cairo"];
+        assert_eq!(file.functions["__main__.check_range"].1, 2);
+        for (line, steps) in file.lines.iter() {
+            assert_eq!(*steps, single_run_lines[line] * 2);
+        }
+        assert_eq!(coverage.files.len(), 2);
+    }
+
+    #[test]
+    fn coverage_without_debug_info() {
+        let mut program = Program::new(Path::new(
+            "cairo_programs/manually_compiled/valid_program_a.json",
+        ))
+        .unwrap();
+        program.debug_info = None;
+        let runner = CairoRunner::new(&program, true).unwrap();
+        assert_eq!(
+            Coverage::new().add_run(&runner),
+            Err(RunnerError::MissingDebugInfo)
+        );
+    }
+}
@@ -0,0 +1,402 @@
+use crate::bigint;
+use crate::types::instruction::{
+    ApUpdate, FpUpdate, Instruction, Op1Addr, Opcode, PcUpdate, Register, Res,
+};
+use crate::types::relocatable::MaybeRelocatable;
+use crate::vm::decoding::encoder::encode_instruction;
+use crate::vm::errors::assembler_errors::AssemblerError;
+use crate::vm::errors::vm_errors::VirtualMachineError;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::FromPrimitive;
+
+/// Assembles code written in the Cairo assembly syntax used by the `Instruction` Display
+/// (one instruction per line) into program data.
+/// The output of the disassembler is also accepted: `pc:` prefixes, function labels, hints
+/// and `//` comments are skipped. Jump and call targets must be given as numbers.
+pub fn assemble(code: &str, prime: &BigInt) -> Result<Vec<MaybeRelocatable>, AssemblerError> {
+    let mut data = Vec::new();
+    let mut in_hint = false;
+    for (i, line) in code.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_hint {
+            in_hint = !line.ends_with("%}");
+            continue;
+        }
+        if line.starts_with("%{") {
+            in_hint = !line.ends_with("%}");
+            continue;
+        }
+        if line.is_empty() || (line.ends_with(':') && !line.contains(' ')) {
+            continue;
+        }
+
+        let instruction = parse_instruction(strip_pc(line))
+            .ok_or_else(|| AssemblerError::InvalidInstruction(line_number, line.to_string()))?;
+        let (encoded_instr, imm) =
+            encode_instruction(&instruction).map_err(|error| match error {
+                VirtualMachineError::InstructionOffsetOutOfRange(offset) => {
+                    AssemblerError::OffsetOutOfRange(line_number, offset)
+                }
+                _ => AssemblerError::InvalidInstruction(line_number, line.to_string()),
+            })?;
+        data.push(MaybeRelocatable::Int(encoded_instr));
+        if let Some(imm) = imm {
+            data.push(MaybeRelocatable::Int(imm.mod_floor(prime)));
+        }
+    }
+    Ok(data)
+}
+
+// Removes the `pc:` prefix written by the disassembler.
+fn strip_pc(line: &str) -> &str {
+    match line.split_once(':') {
+        Some((pc, instruction)) if pc.chars().all(|c| c.is_ascii_digit()) => instruction.trim(),
+        _ => line,
+    }
+}
+
+enum Operand {
+    Imm(BigInt),
+    Mem(Register, BigInt),
+    // [[register + off1] + off2]
+    DoubleDeref(Register, BigInt, BigInt),
+}
+
+// The right hand side of an assert_eq, jump or call: `op1`, `op0 + op1` or `op0 * op1`.
+struct ResExpression {
+    res: Res,
+    op0: Option<(Register, BigInt)>,
+    op1: Operand,
+}
+
+fn parse_instruction(text: &str) -> Option<Instruction> {
+    let (body, ap_update, ap_res) = match text.split_once(';') {
+        Some((body, suffix)) if suffix.trim() == "ap++" => {
+            (body.trim(), Some(ApUpdate::Add1), None)
+        }
+        Some((body, suffix)) => {
+            let ap_res = suffix.trim().strip_prefix("ap += ")?;
+            (body.trim(), Some(ApUpdate::Add), Some(ap_res))
+        }
+        None => (text, None, None),
+    };
+
+    // ret, nop and jnz don't write their res, but it can still be given by `; ap += res`
+    let implicit_res = |default_res: Res| match ap_res {
+        Some(ap_res) => parse_res(ap_res),
+        None => Some(ResExpression {
+            res: default_res,
+            op0: None,
+            op1: Operand::Mem(Register::FP, bigint!(-1)),
+        }),
+    };
+
+    let words: Vec<&str> = body.splitn(3, ' ').collect();
+    match words[..] {
+        ["ret"] => build_instruction(
+            Opcode::Ret,
+            Some((Register::FP, bigint!(-2))),
+            implicit_res(Res::Op1)?,
+            PcUpdate::Jump,
+            ap_update.unwrap_or(ApUpdate::Regular),
+        ),
+        ["nop"] => build_instruction(
+            Opcode::NOp,
+            None,
+            implicit_res(Res::Op1)?,
+            PcUpdate::Regular,
+            ap_update.unwrap_or(ApUpdate::Regular),
+        ),
+        ["call", kind, res] => build_instruction(
+            Opcode::Call,
+            None,
+            parse_res(res)?,
+            parse_jump_kind(kind)?,
+            ap_update.unwrap_or(ApUpdate::Add2),
+        ),
+        ["jmp", "rel", condition] if condition.ends_with(" != 0") => {
+            let (op1, dst) = condition.trim_end_matches(" != 0").split_once(" if ")?;
+            let res = implicit_res(Res::Unconstrained)?;
+            build_instruction(
+                Opcode::NOp,
+                Some(parse_memory(dst)?),
+                ResExpression {
+                    op1: parse_operand(op1)?,
+                    ..res
+                },
+                PcUpdate::Jnz,
+                ap_update.unwrap_or(ApUpdate::Regular),
+            )
+        }
+        ["jmp", kind, res] => build_instruction(
+            Opcode::NOp,
+            None,
+            parse_res(res)?,
+            parse_jump_kind(kind)?,
+            ap_update.unwrap_or(ApUpdate::Regular),
+        ),
+        ["ap", "+=", res] if ap_update.is_none() => build_instruction(
+            Opcode::NOp,
+            None,
+            parse_res(res)?,
+            PcUpdate::Regular,
+            ApUpdate::Add,
+        ),
+        _ => {
+            let (dst, res) = body.split_once(" = ")?;
+            build_instruction(
+                Opcode::AssertEq,
+                Some(parse_memory(dst)?),
+                parse_res(res)?,
+                PcUpdate::Regular,
+                ap_update.unwrap_or(ApUpdate::Regular),
+            )
+        }
+    }
+}
+
+fn parse_jump_kind(kind: &str) -> Option<PcUpdate> {
+    match kind {
+        "abs" => Some(PcUpdate::Jump),
+        "rel" => Some(PcUpdate::JumpRel),
+        _ => None,
+    }
+}
+
+// Unused operands are set to the same defaults the cairo compiler uses.
+fn build_instruction(
+    opcode: Opcode,
+    dst: Option<(Register, BigInt)>,
+    res: ResExpression,
+    pc_update: PcUpdate,
+    ap_update: ApUpdate,
+) -> Option<Instruction> {
+    let (default_dst, default_op0) = match opcode {
+        Opcode::Call => ((Register::AP, bigint!(0)), (Register::AP, bigint!(1))),
+        _ => ((Register::FP, bigint!(-1)), (Register::FP, bigint!(-1))),
+    };
+    let (dst_register, off0) = dst.unwrap_or(default_dst);
+
+    let (op1_addr, off2, imm, op0) = match res.op1 {
+        Operand::Imm(imm) => (Op1Addr::Imm, bigint!(1), Some(imm), res.op0),
+        Operand::Mem(Register::AP, off2) => (Op1Addr::AP, off2, None, res.op0),
+        Operand::Mem(Register::FP, off2) => (Op1Addr::FP, off2, None, res.op0),
+        Operand::DoubleDeref(register, off1, off2) => {
+            if matches!(&res.op0, Some(op0) if op0 != &(register.clone(), off1.clone())) {
+                return None;
+            }
+            (Op1Addr::Op0, off2, None, Some((register, off1)))
+        }
+    };
+    let (op0_register, off1) = op0.unwrap_or(default_op0);
+
+    let fp_update = match opcode {
+        Opcode::Call => FpUpdate::APPlus2,
+        Opcode::Ret => FpUpdate::Dst,
+        _ => FpUpdate::Regular,
+    };
+
+    Some(Instruction {
+        off0,
+        off1,
+        off2,
+        imm,
+        dst_register,
+        op0_register,
+        op1_addr,
+        res: res.res,
+        pc_update,
+        ap_update,
+        fp_update,
+        opcode,
+    })
+}
+
+fn parse_res(text: &str) -> Option<ResExpression> {
+    // Look for the operator outside of brackets, as offsets are written as `[ap + 1]`
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            '+' | '*' if depth == 0 => {
+                let res = if c == '+' { Res::Add } else { Res::Mul };
+                return Some(ResExpression {
+                    res,
+                    op0: Some(parse_memory(&text[..i])?),
+                    op1: parse_operand(&text[i + 1..])?,
+                });
+            }
+            _ => (),
+        }
+    }
+    Some(ResExpression {
+        res: Res::Op1,
+        op0: None,
+        op1: parse_operand(text)?,
+    })
+}
+
+fn parse_operand(text: &str) -> Option<Operand> {
+    let text = text.trim();
+    let inner = match text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        Some(inner) => inner.trim(),
+        None => return text.parse::<BigInt>().ok().map(Operand::Imm),
+    };
+    match inner.strip_prefix('[') {
+        Some(double_deref) => {
+            let (op0, off2) = double_deref.split_once(']')?;
+            let (register, off1) = parse_register_offset(op0)?;
+            let off2 = off2
+                .trim()
+                .strip_prefix('+')?
+                .trim()
+                .parse::<BigInt>()
+                .ok()?;
+            Some(Operand::DoubleDeref(register, off1, off2))
+        }
+        None => {
+            let (register, offset) = parse_register_offset(inner)?;
+            Some(Operand::Mem(register, offset))
+        }
+    }
+}
+
+fn parse_memory(text: &str) -> Option<(Register, BigInt)> {
+    match parse_operand(text)? {
+        Operand::Mem(register, offset) => Some((register, offset)),
+        _ => None,
+    }
+}
+
+// Parses `ap + 1`, `fp + -3` or a bare register.
+fn parse_register_offset(text: &str) -> Option<(Register, BigInt)> {
+    let (register, offset) = match text.split_once('+') {
+        Some((register, offset)) => (register.trim(), offset.trim().parse::<BigInt>().ok()?),
+        None => (text.trim(), bigint!(0)),
+    };
+    match register {
+        "ap" => Some((Register::AP, offset)),
+        "fp" => Some((Register::FP, offset)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint64;
+    use crate::types::program::Program;
+    use crate::vm::decoding::decoder::decode_instruction;
+    use crate::vm::decoding::disassembler::disassemble;
+    use num_traits::ToPrimitive;
+    use std::path::Path;
+
+    fn assemble_line(line: &str) -> Vec<MaybeRelocatable> {
+        assemble(line, &bigint!(17)).unwrap()
+    }
+
+    #[test]
+    fn assemble_assert_eq_add_imm() {
+        assert_eq!(
+            assemble_line("[ap + 0] = [fp + -3] + 5; ap++"),
+            vec![
+                MaybeRelocatable::Int(bigint64!(0x482680017ffd8000)),
+                MaybeRelocatable::Int(bigint!(5))
+            ]
+        );
+    }
+
+    #[test]
+    fn assemble_negative_imm() {
+        assert_eq!(
+            assemble_line("[ap + 0] = -1; ap++"),
+            vec![
+                MaybeRelocatable::Int(bigint64!(0x480680017fff8000)),
+                MaybeRelocatable::Int(bigint!(16))
+            ]
+        );
+    }
+
+    #[test]
+    fn assemble_ret() {
+        assert_eq!(
+            assemble_line("ret"),
+            vec![MaybeRelocatable::Int(bigint64!(0x208b7fff7fff7ffe))]
+        );
+    }
+
+    #[test]
+    fn assemble_jnz() {
+        assert_eq!(
+            assemble_line("jmp rel 3 if [ap + -1] != 0"),
+            vec![
+                MaybeRelocatable::Int(bigint64!(0x020680017fff7fff)),
+                MaybeRelocatable::Int(bigint!(3))
+            ]
+        );
+    }
+
+    #[test]
+    fn assemble_invalid_instruction() {
+        assert_eq!(
+            assemble("ret\n[ap + 0] = [bp + 1]", &bigint!(17)),
+            Err(AssemblerError::InvalidInstruction(
+                2,
+                String::from("[ap + 0] = [bp + 1]")
+            ))
+        );
+    }
+
+    #[test]
+    fn assemble_offset_out_of_range() {
+        assert_eq!(
+            assemble("[ap + 32768] = 1", &bigint!(17)),
+            Err(AssemblerError::OffsetOutOfRange(1, bigint!(32768)))
+        );
+    }
+
+    #[test]
+    fn assemble_disassembled_programs() {
+        for path in [
+            "cairo_programs/manually_compiled/valid_program_a.json",
+            "cairo_programs/manually_compiled/valid_program_b.json",
+        ] {
+            let program = Program::new(Path::new(path)).unwrap();
+            let mut code = Vec::new();
+            disassemble(&program, &mut code).unwrap();
+            let code = String::from_utf8(code).unwrap();
+            assert_eq!(assemble(&code, &program.prime).unwrap(), program.data);
+        }
+    }
+
+    #[test]
+    fn assemble_display_round_trip() {
+        // Every valid flag combination, with offsets at both ends of their range
+        let offsets: [i64; 4] = [0x0000, 0x7fff, 0x8000, 0xffff];
+        for flags in 0..0x8000_i64 {
+            for offset in offsets {
+                let encoded_instr = flags << 48 | offset << 32 | (offset ^ 0xffff) << 16 | offset;
+                let instruction = match decode_instruction(encoded_instr, Some(bigint!(7))) {
+                    Ok(instruction) => instruction,
+                    Err(_) => continue,
+                };
+                let text = instruction.to_string();
+                let data = assemble(&text, &bigint!(17)).unwrap();
+                let imm = match data.get(1) {
+                    Some(MaybeRelocatable::Int(imm)) => Some(imm.clone()),
+                    _ => None,
+                };
+                let assembled = match &data[0] {
+                    MaybeRelocatable::Int(word) => {
+                        decode_instruction(word.to_i64().unwrap(), imm).unwrap()
+                    }
+                    _ => unreachable!(),
+                };
+                assert_eq!(assembled.to_string(), text);
+            }
+        }
+    }
+}
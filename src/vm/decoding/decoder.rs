@@ -72,10 +72,9 @@ pub fn decode_instruction(
     };
 
     if op1_addr == instruction::Op1Addr::Imm {
-        assert!(
-            imm.is_some(),
-            "op1_addr is Op1Addr.IMM, but no immediate given"
-        )
+        if imm.is_none() {
+            return Err(VirtualMachineError::NoImm);
+        }
     } else {
         imm = None
     }
@@ -186,6 +185,16 @@ mod decoder_test {
         assert_eq!(error.unwrap_err().to_string(), "Invalid ap_update value: 3")
     }
 
+    #[test]
+    fn imm_op1_without_immediate() {
+        let error = decode_instruction(0x14A7800080008000, None);
+        assert_eq!(error, Err(VirtualMachineError::NoImm));
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "op1_addr is Op1Addr.IMM, but no immediate given"
+        )
+    }
+
     #[test]
     fn decode_flags_call_add_jmp_add_imm_fp_fp() {
         //  0|  opcode|ap_update|pc_update|res_logic|op1_src|op0_reg|dst_reg
@@ -0,0 +1,136 @@
+use crate::math_utils::as_int;
+use crate::types::instruction::Instruction;
+use crate::types::program::Program;
+use crate::types::relocatable::MaybeRelocatable;
+use crate::vm::decoding::decoder::decode_instruction;
+use num_traits::ToPrimitive;
+use std::collections::HashMap;
+use std::io;
+
+/// Writes the program's bytecode in Cairo assembly syntax, one instruction per line prefixed by
+/// its pc. Functions and labels are written before the instruction they point to, along with
+/// the hints that run at that pc. Words that can't be decoded are written as `dw <value>`.
+pub fn disassemble(program: &Program, out: &mut dyn io::Write) -> io::Result<()> {
+    let labels = get_labels(program);
+    let mut pc = 0;
+    while pc < program.data.len() {
+        for label in labels.get(&pc).into_iter().flatten() {
+            writeln!(out, "{}:", label)?;
+        }
+        for hint in program.hints.get(&pc).into_iter().flatten() {
+            let code = String::from_utf8_lossy(&hint.code);
+            if code.contains('\n') {
+                writeln!(out, "       %{{")?;
+                for line in code.lines() {
+                    writeln!(out, "           {}", line)?;
+                }
+                writeln!(out, "       %}}")?;
+            } else {
+                writeln!(out, "       %{{ {} %}}", code)?;
+            }
+        }
+        match decode_program_instruction(program, pc) {
+            Some(instruction) => {
+                writeln!(out, "{:>5}: {}", pc, instruction)?;
+                pc += instruction.size();
+            }
+            None => {
+                match &program.data[pc] {
+                    MaybeRelocatable::Int(value) => writeln!(out, "{:>5}: dw {}", pc, value)?,
+                    MaybeRelocatable::RelocatableValue(value) => writeln!(
+                        out,
+                        "{:>5}: dw ({}, {})",
+                        pc, value.segment_index, value.offset
+                    )?,
+                }
+                pc += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Function and label names of the program, keyed by pc.
+fn get_labels(program: &Program) -> HashMap<usize, Vec<&String>> {
+    let mut labels: HashMap<usize, Vec<&String>> = HashMap::new();
+    for (name, identifier) in program.identifiers.iter() {
+        if let (Some(pc), Some("function" | "label")) = (identifier.pc, identifier.type_.as_deref())
+        {
+            labels.entry(pc).or_default().push(name);
+        }
+    }
+    for names in labels.values_mut() {
+        names.sort();
+    }
+    labels
+}
+
+// Decodes the instruction at `pc`, writing its immediate (if any) as a signed field element.
+fn decode_program_instruction(program: &Program, pc: usize) -> Option<Instruction> {
+    let encoded_instruction = match &program.data[pc] {
+        MaybeRelocatable::Int(value) => value.to_i64()?,
+        MaybeRelocatable::RelocatableValue(_) => return None,
+    };
+    let imm = match program.data.get(pc + 1) {
+        Some(MaybeRelocatable::Int(imm)) => Some(as_int(imm, &program.prime)),
+        _ => None,
+    };
+    decode_instruction(encoded_instruction, imm).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bigint, bigint64};
+    use num_bigint::BigInt;
+    use num_traits::FromPrimitive;
+    use std::path::Path;
+
+    #[test]
+    fn disassemble_program_with_hints() {
+        let program = Program::new(Path::new(
+            "cairo_programs/manually_compiled/valid_program_a.json",
+        ))
+        .unwrap();
+        let mut out = Vec::new();
+        disassemble(&program, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "__main__.main:\n       %{ memory[ap] = segments.add() %}\n    0: [ap + 0] = 1000; ap++\n    2: [ap + 0] = 2000; ap++\n    4: [ap + 0] = [ap + -2] + [ap + -1]; ap++\n    5: ret\n"
+        );
+    }
+
+    #[test]
+    fn disassemble_program_with_functions() {
+        let program = Program::new(Path::new(
+            "cairo_programs/manually_compiled/valid_program_b.json",
+        ))
+        .unwrap();
+        let mut out = Vec::new();
+        disassemble(&program, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "starkware.cairo.common.serialize.serialize_word:");
+        assert_eq!(lines[4], "__main__.check_range:");
+        // 0xffffffffffffffff is written as is, since it's below prime / 2
+        assert_eq!(lines[6], "    5: [ap + 0] = 18446744073709551615; ap++");
+        assert!(lines.contains(&"__main__.main:"));
+    }
+
+    #[test]
+    fn decode_negative_immediate() {
+        let mut program = Program::new(Path::new(
+            "cairo_programs/manually_compiled/valid_program_a.json",
+        ))
+        .unwrap();
+        //[ap + 0] = -5; ap++
+        program.data = vec![
+            MaybeRelocatable::from(bigint64!(0x480680017fff8000)),
+            MaybeRelocatable::from(&program.prime - 5),
+        ];
+        assert_eq!(
+            decode_program_instruction(&program, 0).and_then(|instruction| instruction.imm),
+            Some(bigint!(-5))
+        );
+    }
+}
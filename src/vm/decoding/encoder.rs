@@ -0,0 +1,144 @@
+use crate::bigint64;
+use crate::types::instruction::{ApUpdate, Instruction, Op1Addr, Opcode, PcUpdate, Register, Res};
+use crate::vm::errors::vm_errors::VirtualMachineError;
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive};
+
+//  0|  opcode|ap_update|pc_update|res_logic|op1_src|op0_reg|dst_reg
+// 15|14 13 12|    11 10|  9  8  7|     6  5|4  3  2|      1|      0
+
+/// Encodes an instruction, returning the encoded instruction and its immediate, if any.
+/// This is the inverse of `decode_instruction`. Offsets must fit in the 16 bit biased
+/// representation used by the encoding, i.e. be in the range [-2^15, 2^15).
+pub fn encode_instruction(
+    instruction: &Instruction,
+) -> Result<(BigInt, Option<BigInt>), VirtualMachineError> {
+    let dst_reg_num = match instruction.dst_register {
+        Register::AP => 0,
+        Register::FP => 1,
+    };
+    let op0_reg_num = match instruction.op0_register {
+        Register::AP => 0,
+        Register::FP => 1,
+    };
+    let op1_src_num = match instruction.op1_addr {
+        Op1Addr::Op0 => 0,
+        Op1Addr::Imm => 1,
+        Op1Addr::FP => 2,
+        Op1Addr::AP => 4,
+    };
+    let res_logic_num = match instruction.res {
+        Res::Op1 | Res::Unconstrained => 0,
+        Res::Add => 1,
+        Res::Mul => 2,
+    };
+    let pc_update_num = match instruction.pc_update {
+        PcUpdate::Regular => 0,
+        PcUpdate::Jump => 1,
+        PcUpdate::JumpRel => 2,
+        PcUpdate::Jnz => 4,
+    };
+    // Add2 is implied by the call opcode
+    let ap_update_num = match instruction.ap_update {
+        ApUpdate::Regular | ApUpdate::Add2 => 0,
+        ApUpdate::Add => 1,
+        ApUpdate::Add1 => 2,
+    };
+    let opcode_num = match instruction.opcode {
+        Opcode::NOp => 0,
+        Opcode::Call => 1,
+        Opcode::Ret => 2,
+        Opcode::AssertEq => 4,
+    };
+
+    let flags: i64 = dst_reg_num
+        | op0_reg_num << 1
+        | op1_src_num << 2
+        | res_logic_num << 5
+        | pc_update_num << 7
+        | ap_update_num << 10
+        | opcode_num << 12;
+
+    let encoded_instr = encode_offset(&instruction.off0)?
+        | encode_offset(&instruction.off1)? << 16
+        | encode_offset(&instruction.off2)? << 32
+        | flags << 48;
+
+    Ok((bigint64!(encoded_instr), instruction.imm.clone()))
+}
+
+// Offsets are stored as 16 bit values biased by 2^15.
+fn encode_offset(offset: &BigInt) -> Result<i64, VirtualMachineError> {
+    offset
+        .to_i64()
+        .map(|offset| offset + (1 << 15))
+        .filter(|biased_offset| (0..1 << 16).contains(biased_offset))
+        .ok_or_else(|| VirtualMachineError::InstructionOffsetOutOfRange(offset.clone()))
+}
+
+#[cfg(test)]
+mod encoder_test {
+    use super::*;
+    use crate::bigint;
+    use crate::vm::decoding::decoder::decode_instruction;
+
+    #[test]
+    fn encode_assert_eq_add_imm() {
+        let instruction = decode_instruction(0x482680017ffd8000, Some(bigint!(5))).unwrap();
+        assert_eq!(
+            encode_instruction(&instruction),
+            Ok((bigint64!(0x482680017ffd8000), Some(bigint!(5))))
+        );
+    }
+
+    #[test]
+    fn encode_ret() {
+        let instruction = decode_instruction(0x208b7fff7fff7ffe, None).unwrap();
+        assert_eq!(
+            encode_instruction(&instruction),
+            Ok((bigint64!(0x208b7fff7fff7ffe), None))
+        );
+    }
+
+    #[test]
+    fn encode_offset_out_of_range() {
+        let mut instruction = decode_instruction(0x208b7fff7fff7ffe, None).unwrap();
+        instruction.off1 = bigint!(1 << 15);
+        assert_eq!(
+            encode_instruction(&instruction),
+            Err(VirtualMachineError::InstructionOffsetOutOfRange(bigint!(
+                1 << 15
+            )))
+        );
+        instruction.off1 = bigint!(-(1 << 15) - 1);
+        assert_eq!(
+            encode_instruction(&instruction),
+            Err(VirtualMachineError::InstructionOffsetOutOfRange(bigint!(
+                -(1 << 15) - 1
+            )))
+        );
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        // Every valid flag combination, with offsets at both ends of their range
+        let offsets: [i64; 4] = [0x0000, 0x7fff, 0x8000, 0xffff];
+        for flags in 0..0x8000_i64 {
+            for offset in offsets {
+                let encoded_instr = flags << 48 | offset << 32 | (offset ^ 0xffff) << 16 | offset;
+                let imm = Some(bigint!(17));
+                let instruction = match decode_instruction(encoded_instr, imm) {
+                    Ok(instruction) => instruction,
+                    Err(_) => continue,
+                };
+                let (encoded, encoded_imm) = encode_instruction(&instruction).unwrap();
+                // Every field is recovered by the decoder, so the encoding must be identical
+                assert_eq!(encoded, bigint64!(encoded_instr), "flags: {:#x}", flags);
+                assert_eq!(
+                    decode_instruction(encoded.to_i64().unwrap(), encoded_imm),
+                    Ok(instruction)
+                );
+            }
+        }
+    }
+}
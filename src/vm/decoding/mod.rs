@@ -1 +1,4 @@
+pub mod assembler;
 pub mod decoder;
+pub mod disassembler;
+pub mod encoder;
@@ -0,0 +1,25 @@
+use num_bigint::BigInt;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum AssemblerError {
+    InvalidInstruction(usize, String),
+    OffsetOutOfRange(usize, BigInt),
+}
+
+impl fmt::Display for AssemblerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssemblerError::InvalidInstruction(line, instruction) => {
+                write!(f, "Invalid instruction at line {}: {}", line, instruction)
+            }
+            AssemblerError::OffsetOutOfRange(line, offset) => write!(
+                f,
+                "Offset {} at line {} is out of the [-2^15, 2^15) range",
+                offset, line
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AssemblerError {}
@@ -10,6 +10,7 @@ pub enum CairoRunError {
     VirtualMachine(VirtualMachineError),
     Trace(TraceError),
     Runner(RunnerError),
+    ProgramInput(ProgramError),
 }
 
 impl fmt::Display for CairoRunError {
@@ -31,6 +32,22 @@ impl fmt::Display for CairoRunError {
                 write!(f, "Runner failure: ")?;
                 error.fmt(f)
             }
+            CairoRunError::ProgramInput(error) => {
+                write!(f, "Program input failure: ")?;
+                error.fmt(f)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CairoRunError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CairoRunError::Program(error) => Some(error),
+            CairoRunError::VirtualMachine(error) => Some(error),
+            CairoRunError::Trace(error) => Some(error),
+            CairoRunError::Runner(error) => Some(error),
+            CairoRunError::ProgramInput(error) => Some(error),
         }
     }
 }
@@ -14,3 +14,5 @@ impl fmt::Display for ExecScopeError {
         }
     }
 }
+
+impl std::error::Error for ExecScopeError {}
@@ -11,6 +11,7 @@ pub enum MemoryError {
     InconsistentMemory(MaybeRelocatable, MaybeRelocatable, MaybeRelocatable),
     EffectiveSizesNotCalled,
     Relocation,
+    MalformedPublicMemory,
 }
 
 impl fmt::Display for MemoryError {
@@ -41,6 +42,11 @@ impl fmt::Display for MemoryError {
                 "compute_effective_sizes should be called before relocate_segments"
             ),
             MemoryError::Relocation => write!(f, "Inconsistent Relocation"),
+            MemoryError::MalformedPublicMemory => {
+                write!(f, "Missing segment offsets for the public memory")
+            }
         }
     }
 }
+
+impl std::error::Error for MemoryError {}
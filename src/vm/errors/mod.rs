@@ -1,3 +1,4 @@
+pub mod assembler_errors;
 pub mod cairo_run_errors;
 pub mod exec_scope_errors;
 pub mod memory_errors;
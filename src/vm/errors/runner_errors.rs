@@ -1,8 +1,10 @@
-use crate::types::relocatable::MaybeRelocatable;
+use crate::types::relocatable::{MaybeRelocatable, Relocatable};
+use crate::vm::errors::vm_errors::VirtualMachineError;
 use num_bigint::BigInt;
 use std::fmt;
 
 use super::memory_errors::MemoryError;
+use super::trace_errors::TraceError;
 
 #[derive(Debug, PartialEq)]
 pub enum RunnerError {
@@ -24,6 +26,17 @@ pub enum RunnerError {
     FailedMemoryGet(MemoryError),
     EcOpBuiltinScalarLimit(BigInt),
     FailedToParseIdsNameFromPath(String),
+    NoStopPointer(String),
+    InvalidStopPointer(Relocatable, Relocatable),
+    OutOfBoundsProgramSegmentAccess(usize, usize),
+    OutOfBoundsBuiltinSegmentAccess(String, usize, usize),
+    FailedAutoDeductions(Box<VirtualMachineError>),
+    DisorderedBuiltins,
+    IntegerBiggerThanPowerOfTwo(MaybeRelocatable, u32, BigInt),
+    EcOpSameXCoordinate(String),
+    PointNotOnCurve((usize, usize)),
+    Trace(TraceError),
+    MissingDebugInfo,
 }
 
 impl fmt::Display for RunnerError {
@@ -78,6 +91,65 @@ impl fmt::Display for RunnerError {
                     path
                 )
             }
+            RunnerError::NoStopPointer(name) => write!(
+                f,
+                "Missing stop pointer for builtin {}, read_return_values should be called first",
+                name
+            ),
+            RunnerError::InvalidStopPointer(expected, found) => write!(
+                f,
+                "Invalid stop pointer. Expected: {:?}, found: {:?}",
+                expected, found
+            ),
+            RunnerError::OutOfBoundsProgramSegmentAccess(size, program_length) => write!(
+                f,
+                "Program segment was written beyond the program data: segment size {} > program length {}",
+                size, program_length
+            ),
+            RunnerError::OutOfBoundsBuiltinSegmentAccess(name, size, stop_offset) => write!(
+                f,
+                "Out of bounds access to builtin segment {}: segment size {} > stop pointer offset {}",
+                name, size, stop_offset
+            ),
+            RunnerError::FailedAutoDeductions(error) => {
+                write!(f, "Builtin cells failed auto deduction verification. ")?;
+                error.fmt(f)
+            }
+            RunnerError::DisorderedBuiltins => {
+                write!(f, "Given builtins are not in appropiate order")
+            }
+            RunnerError::IntegerBiggerThanPowerOfTwo(addr, bits, value) => write!(
+                f,
+                "Expected integer at address {:?} to be smaller than 2^{}, Got {}",
+                addr, bits, value
+            ),
+            RunnerError::EcOpSameXCoordinate(points) => write!(
+                f,
+                "EcOpBuiltin: points have the same x coordinate ({})",
+                points
+            ),
+            RunnerError::PointNotOnCurve(pair) => write!(
+                f,
+                "EcOpBuiltin: point {:?} is not on the curve",
+                pair
+            ),
+            RunnerError::Trace(error) => error.fmt(f),
+            RunnerError::MissingDebugInfo => {
+                write!(f, "The program was compiled without debug info")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RunnerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RunnerError::MemoryValidationError(error)
+            | RunnerError::MemoryInitializationError(error)
+            | RunnerError::FailedMemoryGet(error) => Some(error),
+            RunnerError::FailedAutoDeductions(error) => Some(error.as_ref()),
+            RunnerError::Trace(error) => Some(error),
+            _ => None,
         }
     }
 }
@@ -21,3 +21,12 @@ impl fmt::Display for TraceError {
         }
     }
 }
+
+impl std::error::Error for TraceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TraceError::MemoryError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
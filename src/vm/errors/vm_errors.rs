@@ -1,3 +1,4 @@
+use crate::serde::deserialize_utils::ReferenceParseError;
 use crate::vm::errors::memory_errors::MemoryError;
 use num_bigint::BigInt;
 use std::fmt;
@@ -26,6 +27,7 @@ pub enum VirtualMachineError {
     PureValue,
     InvalidRes(i64),
     InvalidOpcode(i64),
+    InstructionOffsetOutOfRange(BigInt),
     RelocatableAdd,
     OffsetExceeded(BigInt),
     NotImplemented,
@@ -59,6 +61,7 @@ pub enum VirtualMachineError {
     MainScopeError(ExecScopeError),
     ScopeError,
     VariableNotInScopeError(String),
+    ScopeVariableTypeError(String),
     CantCreateDictionaryOnTakenSegment(usize),
     NoDictTracker(usize),
     NoValueForKey(BigInt),
@@ -69,6 +72,9 @@ pub enum VirtualMachineError {
     NoneApTrackingData,
     InvalidTrackingGroup(usize, usize),
     InvalidApValue(MaybeRelocatable),
+    InvalidFpValue(MaybeRelocatable),
+    InvalidReferenceAddress(Relocatable, i64),
+    ReferenceParseError(ReferenceParseError),
     NoInitialDict,
     NoLocalVariable(String),
     NoKeyInAccessIndices(BigInt),
@@ -84,6 +90,22 @@ pub enum VirtualMachineError {
     NAccessesTooBig(BigInt),
     BigintToUsizeFail,
     InvalidSetRange(MaybeRelocatable, MaybeRelocatable),
+    AssertionFailed(String),
+    DividedByZero,
+    UsortOutOfRange(BigInt, BigInt),
+    EmptyPositions,
+    PositionsNotEmpty,
+    MismatchedDictPtr(Relocatable, Relocatable),
+    MissingConstant(String),
+    AmbiguousConstant(String),
+    UnexpectedConstantValue(String, BigInt, BigInt),
+    MissingReferenceType(String),
+    UnknownStruct(String),
+    UnknownStructMember(String, String),
+    NoImm,
+    NegativeOffset(BigInt),
+    ProgramInputNotFound(String),
+    InvalidProgramInput(String),
 }
 
 impl fmt::Display for VirtualMachineError {
@@ -119,6 +141,7 @@ impl fmt::Display for VirtualMachineError {
             VirtualMachineError::NoDst => write!(f,  "Couldn't get or load dst"),
             VirtualMachineError::InvalidRes(n) => write!(f, "Invalid res value: {}", n),
             VirtualMachineError::InvalidOpcode(n) => write!(f, "Invalid opcode value: {}", n),
+            VirtualMachineError::InstructionOffsetOutOfRange(offset) => write!(f, "Instruction offset {} is out of the [-2^15, 2^15) range", offset),
             VirtualMachineError::RelocatableAdd => {
                 write!(f, "Cannot add two relocatable values")
             }
@@ -191,6 +214,9 @@ impl fmt::Display for VirtualMachineError {
             VirtualMachineError::VariableNotInScopeError(var_name) => {
                 write!(f, "Variable {} not in local scope", var_name)
             },
+            VirtualMachineError::ScopeVariableTypeError(var_name) => {
+                write!(f, "Variable {} in local scope has an unexpected type", var_name)
+            },
             VirtualMachineError::ScopeError => write!(f, "Failed to get scope variables"),
             VirtualMachineError::CantCreateDictionaryOnTakenSegment(index) => {
                 write!(f, "DictManagerError: Tried to create tracker for a dictionary on segment: {:?} when there is already a tracker for a dictionary on this segment", index)
@@ -233,6 +259,13 @@ impl fmt::Display for VirtualMachineError {
             VirtualMachineError::InvalidApValue(addr) => {
                 write!(f, "Expected relocatable for ap, got {:?}", addr)
             },
+            VirtualMachineError::InvalidFpValue(addr) => {
+                write!(f, "Expected relocatable for fp, got {:?}", addr)
+            },
+            VirtualMachineError::InvalidReferenceAddress(base, offset) => {
+                write!(f, "Reference resolves to an address outside its segment: {:?} + {}", base, offset)
+            },
+            VirtualMachineError::ReferenceParseError(error) => write!(f, "Invalid reference: {}", error),
             VirtualMachineError::NumUsedAccessesAssertFail(used, len, key) => {
                 write!(f, "squash_dict_inner fail: Number of used accesses:{:?} doesnt match the lengh: {:?} of the access_indices at key: {:?}", used, len, key)
             },
@@ -256,6 +289,34 @@ impl fmt::Display for VirtualMachineError {
             VirtualMachineError::FindElemMaxSize(find_elem_max_size, n_elms) => write!(f, "find_elem() can only be used with n_elms <= {:?}.\nGot: n_elms = {:?}", find_elem_max_size, n_elms),
             VirtualMachineError::InvalidIndex(find_element_index, key, found_key) => write!(f, "Invalid index found in find_element_index. Index: {:?}.\nExpected key: {:?}, found_key {:?}", find_element_index, key, found_key),
             VirtualMachineError::KeyNotFound => write!(f, "Found Key is None"),
+            VirtualMachineError::AssertionFailed(assertion) => write!(f, "Assertion failed: {}", assertion),
+            VirtualMachineError::DividedByZero => write!(f, "Attempted to divide by zero"),
+            VirtualMachineError::UsortOutOfRange(usort_max_size, input_len) => write!(f, "usort() can only be used with input_len<={}. Got: input_len={}.", usort_max_size, input_len),
+            VirtualMachineError::EmptyPositions => write!(f, "verify_multiplicity fail: local positions is empty"),
+            VirtualMachineError::PositionsNotEmpty => write!(f, "verify_multiplicity fail: local positions is not empty, unaccounted positions remain"),
+            VirtualMachineError::MissingConstant(name) => write!(f, "Constant {} not found in the program", name),
+            VirtualMachineError::AmbiguousConstant(name) => write!(f, "Constant {} is defined in more than one scope of the program", name),
+            VirtualMachineError::UnexpectedConstantValue(name, expected, found) => write!(f, "Constant {} should be {}, found {}", name, expected, found),
+            VirtualMachineError::MismatchedDictPtr(current_ptr, dict_ptr) => write!(f, "Dict Error: Wrong dict pointer supplied. Got {:?}, expected {:?}.", dict_ptr, current_ptr),
+            VirtualMachineError::MissingReferenceType(name) => write!(f, "The Cairo type of ids.{} is unknown", name),
+            VirtualMachineError::UnknownStruct(name) => write!(f, "Struct {} is not defined in the program", name),
+            VirtualMachineError::UnknownStructMember(struct_name, member) => write!(f, "Struct {} has no member {}", struct_name, member),
+            VirtualMachineError::NoImm => write!(f, "op1_addr is Op1Addr.IMM, but no immediate given"),
+            VirtualMachineError::NegativeOffset(offset) => write!(f, "Address offsets cant be negative, got {}", offset),
+            VirtualMachineError::ProgramInputNotFound(path) => write!(f, "Program input {} not found", path),
+            VirtualMachineError::InvalidProgramInput(path) => write!(f, "Program input {} is not an integer or a list of integers", path),
+        }
+    }
+}
+
+impl std::error::Error for VirtualMachineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VirtualMachineError::RunnerError(error) => Some(error),
+            VirtualMachineError::MemoryError(error) => Some(error),
+            VirtualMachineError::MainScopeError(error) => Some(error),
+            VirtualMachineError::ReferenceParseError(error) => Some(error),
+            _ => None,
         }
     }
 }
@@ -8,7 +8,8 @@ use crate::{
     vm::{errors::vm_errors::VirtualMachineError, vm_core::VirtualMachine},
 };
 
-use super::hint_utils::get_address_from_reference;
+use super::hint_utils::{get_address_from_reference, get_relocatable_from_var_name};
+use super::ids_manager::IdsManager;
 //DictAccess struct has three memebers, so the size of DictAccess* is 3
 pub const DICT_ACCESS_SIZE: usize = 3;
 
@@ -29,9 +30,6 @@ fn get_initial_dict(vm: &mut VirtualMachine) -> Option<HashMap<BigInt, BigInt>>
 
        memory[ap] = __dict_manager.new_dict(segments, initial_dict)
        del initial_dict
-
-For now, the functionality to create a dictionary from a previously defined initial_dict (using a hint)
-is not available
 */
 pub fn dict_new(vm: &mut VirtualMachine) -> Result<(), VirtualMachineError> {
     //Get initial dictionary from scope (defined by an earlier hint)
@@ -39,6 +37,7 @@ pub fn dict_new(vm: &mut VirtualMachine) -> Result<(), VirtualMachineError> {
     let base = vm
         .dict_manager
         .new_dict(&mut vm.segments, &mut vm.memory, initial_dict)?;
+    vm.exec_scopes.delete_variable("initial_dict");
     vm.memory
         .insert(&vm.run_context.ap, &base)
         .map_err(VirtualMachineError::MemoryError)
@@ -70,19 +69,13 @@ pub fn default_dict_new(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let default_value_addr = if let Ok(Some(default_value_addr)) = get_address_from_reference(
+    let default_value_addr = get_address_from_reference(
         default_value_ref,
         &vm.references,
         &vm.run_context,
         vm,
         hint_ap_tracking,
-    ) {
-        default_value_addr
-    } else {
-        return Err(VirtualMachineError::FailedToGetReference(
-            default_value_ref.clone(),
-        ));
-    };
+    )?;
     //Check that ids.default_value is an Int value
     let default_value = if let Ok(Some(&MaybeRelocatable::Int(ref default_value))) =
         vm.memory.get(&default_value_addr)
@@ -135,34 +128,27 @@ pub fn dict_read(
             ));
         };
     //Check that each reference id corresponds to a value in the reference manager
-    let (key_addr, value_addr, dict_ptr_addr) =
-        if let (Ok(Some(key_addr)), Ok(Some(value_addr)), Ok(Some(dict_ptr_addr))) = (
-            get_address_from_reference(
-                key_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-            get_address_from_reference(
-                value_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-            get_address_from_reference(
-                dict_ptr_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-        ) {
-            (key_addr, value_addr, dict_ptr_addr)
-        } else {
-            return Err(VirtualMachineError::FailedToGetIds);
-        };
+    let key_addr = get_address_from_reference(
+        key_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let value_addr = get_address_from_reference(
+        value_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let dict_ptr_addr = get_address_from_reference(
+        dict_ptr_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
     //Check that these addresses point to the data types needed
     match (
         vm.memory.get(&key_addr),
@@ -205,70 +191,11 @@ pub fn dict_write(
     ids: HashMap<String, BigInt>,
     hint_ap_tracking: Option<&ApTracking>,
 ) -> Result<(), VirtualMachineError> {
-    //Check that ids contains the reference id for each variable used by the hint
-    let (key_ref, value_ref, dict_ptr_ref) =
-        if let (Some(key_ref), Some(value_ref), Some(dict_ptr_ref)) = (
-            ids.get(&String::from("key")),
-            ids.get(&String::from("new_value")),
-            ids.get(&String::from("dict_ptr")),
-        ) {
-            (key_ref, value_ref, dict_ptr_ref)
-        } else {
-            return Err(VirtualMachineError::IncorrectIds(
-                vec![
-                    String::from("key"),
-                    String::from("new_value"),
-                    String::from("dict_ptr"),
-                ],
-                ids.into_keys().collect(),
-            ));
-        };
-    //Check that each reference id corresponds to a value in the reference manager
-    let (key_addr, value_addr, dict_ptr_addr) =
-        if let (Ok(Some(key_addr)), Ok(Some(value_addr)), Ok(Some(dict_ptr_addr))) = (
-            get_address_from_reference(
-                key_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-            get_address_from_reference(
-                value_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-            get_address_from_reference(
-                dict_ptr_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-        ) {
-            (key_addr, value_addr, dict_ptr_addr)
-        } else {
-            return Err(VirtualMachineError::FailedToGetIds);
-        };
-    //Check that these addresses point to the data types needed
-    let (key, new_value, dict_ptr) = if let (
-        Ok(Some(MaybeRelocatable::Int(ref key))),
-        Ok(Some(MaybeRelocatable::Int(new_value))),
-        Ok(Some(MaybeRelocatable::RelocatableValue(dict_ptr))),
-    ) = (
-        vm.memory.get(&key_addr),
-        vm.memory.get(&value_addr),
-        vm.memory.get(&dict_ptr_addr),
-    ) {
-        (key, new_value, dict_ptr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
-    //Auxiliary copy values
-    let key_copy = key.clone();
-    let value_copy = new_value.clone();
+    let ids_manager = IdsManager::new(&ids, hint_ap_tracking);
+    let key = ids_manager.get_integer("key", vm)?.clone();
+    let new_value = ids_manager.get_integer("new_value", vm)?.clone();
+    let dict_ptr = ids_manager.get_relocatable("dict_ptr", vm)?.clone();
+    let dict_ptr_prev_value = ids_manager.get_address("dict_ptr.prev_value", vm)?;
 
     //Get tracker for dictionary
     let tracker = if let Some(tracker) = vm.dict_manager.trackers.get_mut(&dict_ptr.segment_index) {
@@ -276,30 +203,23 @@ pub fn dict_write(
     } else {
         return Err(VirtualMachineError::NoDictTracker(dict_ptr.segment_index));
     };
-
-    //dict_ptr is a pointer to a struct, with the ordered fields (key, prev_value, new_value),
-    //dict_ptr.prev_value will be equal to dict_ptr + 1
-    let dict_ptr_prev_value =
-        MaybeRelocatable::RelocatableValue(dict_ptr.clone()).add_usize_mod(1, None);
     //Tracker set to track next dictionary entry
     tracker.current_ptr.offset += DICT_ACCESS_SIZE;
     //Get previous value
-    let prev_value = if let Some(value) = tracker.data.get(key) {
-        value
+    let prev_value = if let Some(value) = tracker.data.get(&key) {
+        value.clone()
     } else {
-        return Err(VirtualMachineError::NoValueForKey(key.clone()));
+        return Err(VirtualMachineError::NoValueForKey(key));
     };
+    //Insert new value into tracker
+    tracker.data.insert(&key, &new_value);
     //Insert previous value into dict_ptr.prev_value
-    //Addres for dict_ptr.prev_value should be dict_ptr* + 1 (defined above)
     vm.memory
         .insert(
-            &dict_ptr_prev_value,
-            &MaybeRelocatable::from(prev_value.clone()),
+            &MaybeRelocatable::RelocatableValue(dict_ptr_prev_value),
+            &MaybeRelocatable::from(prev_value),
         )
-        .map_err(VirtualMachineError::MemoryError)?;
-    //Insert new value into tracker
-    tracker.data.insert(&key_copy, &value_copy);
-    Ok(())
+        .map_err(VirtualMachineError::MemoryError)
 }
 
 /* Implements hint:
@@ -318,82 +238,11 @@ pub fn dict_update(
     ids: HashMap<String, BigInt>,
     hint_ap_tracking: Option<&ApTracking>,
 ) -> Result<(), VirtualMachineError> {
-    //Check that ids contains the reference id for each variable used by the hint
-    let (key_ref, prev_value_ref, new_value_ref, dict_ptr_ref) =
-        if let (Some(key_ref), Some(prev_value_ref), Some(new_value_ref), Some(dict_ptr_ref)) = (
-            ids.get(&String::from("key")),
-            ids.get(&String::from("prev_value")),
-            ids.get(&String::from("new_value")),
-            ids.get(&String::from("dict_ptr")),
-        ) {
-            (key_ref, prev_value_ref, new_value_ref, dict_ptr_ref)
-        } else {
-            return Err(VirtualMachineError::IncorrectIds(
-                vec![
-                    String::from("key"),
-                    String::from("prev_value"),
-                    String::from("new_value"),
-                    String::from("dict_ptr"),
-                ],
-                ids.into_keys().collect(),
-            ));
-        };
-    //Check that each reference id corresponds to a value in the reference manager
-    let (key_addr, prev_value_addr, new_value_addr, dict_ptr_addr) = if let (
-        Ok(Some(key_addr)),
-        Ok(Some(prev_value_addr)),
-        Ok(Some(new_value_addr)),
-        Ok(Some(dict_ptr_addr)),
-    ) = (
-        get_address_from_reference(
-            key_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            prev_value_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            new_value_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            dict_ptr_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-    ) {
-        (key_addr, prev_value_addr, new_value_addr, dict_ptr_addr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
-    //Check that these addresses point to the data types needed
-    let (key, prev_value, new_value, dict_ptr) = if let (
-        Ok(Some(MaybeRelocatable::Int(ref key))),
-        Ok(Some(MaybeRelocatable::Int(prev_value))),
-        Ok(Some(MaybeRelocatable::Int(new_value))),
-        Ok(Some(MaybeRelocatable::RelocatableValue(dict_ptr))),
-    ) = (
-        vm.memory.get(&key_addr),
-        vm.memory.get(&prev_value_addr),
-        vm.memory.get(&new_value_addr),
-        vm.memory.get(&dict_ptr_addr),
-    ) {
-        (key, prev_value, new_value, dict_ptr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
+    let ids_manager = IdsManager::new(&ids, hint_ap_tracking);
+    let key = ids_manager.get_integer("key", vm)?.clone();
+    let prev_value = ids_manager.get_integer("prev_value", vm)?.clone();
+    let new_value = ids_manager.get_integer("new_value", vm)?.clone();
+    let dict_ptr = ids_manager.get_relocatable("dict_ptr", vm)?.clone();
 
     //Get tracker for dictionary
     let tracker = if let Some(tracker) = vm.dict_manager.trackers.get_mut(&dict_ptr.segment_index) {
@@ -402,20 +251,76 @@ pub fn dict_update(
         return Err(VirtualMachineError::NoDictTracker(dict_ptr.segment_index));
     };
     //Check that prev_value is equal to the current value at the given key
-    let current_value = tracker.data.get(key);
-    if current_value != Some(prev_value) {
+    let current_value = tracker.data.get(&key);
+    if current_value != Some(&prev_value) {
         return Err(VirtualMachineError::WrongPrevValue(
-            prev_value.clone(),
+            prev_value,
             current_value.cloned(),
-            key.clone(),
+            key,
         ));
     }
     //Update Value
-    tracker.data.insert(key, new_value);
+    tracker.data.insert(&key, &new_value);
     tracker.current_ptr.offset += DICT_ACCESS_SIZE;
     Ok(())
 }
 
+/* Implements hint:
+    # Prepare arguments for dict_new. In particular, the same dictionary values should be copied
+    # to the new (squashed) dictionary.
+    vm_enter_scope({
+        # Make __dict_manager accessible.
+        '__dict_manager': __dict_manager,
+        # Create a copy of the dict, in case it changes in the future.
+        'initial_dict': dict(__dict_manager.get_dict(ids.dict_accesses_end)),
+    })
+*/
+pub fn dict_squash_copy_dict(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let dict_accesses_end_addr =
+        get_relocatable_from_var_name("dict_accesses_end", &ids, vm, hint_ap_tracking)?;
+    let dict_accesses_end = vm.memory.get_relocatable(&dict_accesses_end_addr)?;
+    let dict_copy = vm
+        .dict_manager
+        .get_tracker(dict_accesses_end)?
+        .data
+        .get_dictionary_copy();
+    //__dict_manager is owned by the vm, so only the initial_dict is added to the new scope
+    vm.exec_scopes.enter_scope(HashMap::from([(
+        String::from("initial_dict"),
+        PyValueType::Dictionary(dict_copy),
+    )]));
+    Ok(())
+}
+
+/* Implements hint:
+    # Update the DictTracker's current_ptr to point to the end of the squashed dict.
+    __dict_manager.get_tracker(ids.squashed_dict_start).current_ptr = \
+        ids.squashed_dict_end.address_
+*/
+pub fn dict_squash_update_ptr(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let squashed_dict_start_addr =
+        get_relocatable_from_var_name("squashed_dict_start", &ids, vm, hint_ap_tracking)?;
+    let squashed_dict_end_addr =
+        get_relocatable_from_var_name("squashed_dict_end", &ids, vm, hint_ap_tracking)?;
+    let squashed_dict_start = vm
+        .memory
+        .get_relocatable(&squashed_dict_start_addr)?
+        .clone();
+    let squashed_dict_end = vm.memory.get_relocatable(&squashed_dict_end_addr)?.clone();
+    vm.dict_manager
+        .get_tracker_mut(&squashed_dict_start)?
+        .current_ptr = squashed_dict_end;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -423,6 +328,7 @@ mod tests {
     use num_bigint::{BigInt, Sign};
     use num_traits::FromPrimitive;
 
+    use crate::serde::deserialize_program::Member;
     use crate::types::instruction::Register;
     use crate::types::relocatable::Relocatable;
     use crate::vm::errors::memory_errors::MemoryError;
@@ -435,6 +341,23 @@ mod tests {
     };
 
     use super::*;
+
+    //Members of starkware.cairo.common.dict_access.DictAccess
+    fn dict_access_struct_types() -> HashMap<String, HashMap<String, Member>> {
+        let member = |offset| Member {
+            cairo_type: String::from("felt"),
+            offset,
+        };
+        HashMap::from([(
+            String::from("starkware.cairo.common.dict_access.DictAccess"),
+            HashMap::from([
+                (String::from("key"), member(0)),
+                (String::from("prev_value"), member(1)),
+                (String::from("new_value"), member(2)),
+            ]),
+        )])
+    }
+
     #[test]
     fn run_dict_new_with_initial_dict_empty() {
         let hint_code = "if '__dict_manager' not in globals():\n    from starkware.cairo.common.dict import DictManager\n    __dict_manager = DictManager()\n\nmemory[ap] = __dict_manager.new_dict(segments, initial_dict)\ndel initial_dict".as_bytes();
@@ -562,31 +485,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -657,31 +589,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -734,31 +675,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -797,11 +747,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         execute_hint(&mut vm, hint_code, ids, &ApTracking::new())
@@ -843,11 +796,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         assert_eq!(
@@ -910,36 +866,49 @@ mod tests {
         ids.insert(String::from("key"), bigint!(0));
         ids.insert(String::from("new_value"), bigint!(1));
         ids.insert(String::from("dict_ptr"), bigint!(2));
+        //Store the definition of the DictAccess struct
+        vm.struct_types = dict_access_struct_types();
         //Create references
         vm.references = HashMap::from([
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: Some(String::from(
+                        "starkware.cairo.common.dict_access.DictAccess*",
+                    )),
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1024,36 +993,49 @@ mod tests {
         ids.insert(String::from("key"), bigint!(0));
         ids.insert(String::from("new_value"), bigint!(1));
         ids.insert(String::from("dict_ptr"), bigint!(2));
+        //Store the definition of the DictAccess struct
+        vm.struct_types = dict_access_struct_types();
         //Create references
         vm.references = HashMap::from([
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: Some(String::from(
+                        "starkware.cairo.common.dict_access.DictAccess*",
+                    )),
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1138,36 +1120,49 @@ mod tests {
         ids.insert(String::from("key"), bigint!(0));
         ids.insert(String::from("new_value"), bigint!(1));
         ids.insert(String::from("dict_ptr"), bigint!(2));
+        //Store the definition of the DictAccess struct
+        vm.struct_types = dict_access_struct_types();
         //Create references
         vm.references = HashMap::from([
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: Some(String::from(
+                        "starkware.cairo.common.dict_access.DictAccess*",
+                    )),
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1250,36 +1245,49 @@ mod tests {
         ids.insert(String::from("key"), bigint!(0));
         ids.insert(String::from("new_value"), bigint!(1));
         ids.insert(String::from("dict_ptr"), bigint!(2));
+        //Store the definition of the DictAccess struct
+        vm.struct_types = dict_access_struct_types();
         //Create references
         vm.references = HashMap::from([
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: Some(String::from(
+                        "starkware.cairo.common.dict_access.DictAccess*",
+                    )),
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1358,41 +1366,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1486,41 +1506,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1614,41 +1646,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1731,41 +1775,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1849,41 +1905,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1978,41 +2046,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2107,41 +2187,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2225,41 +2317,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2341,41 +2445,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2400,4 +2516,292 @@ mod tests {
             relocatable!(1, 3)
         );
     }
+
+    #[test]
+    fn run_dict_new_with_initial_dict_deletes_initial_dict() {
+        let hint_code = "if '__dict_manager' not in globals():\n    from starkware.cairo.common.dict import DictManager\n    __dict_manager = DictManager()\n\nmemory[ap] = __dict_manager.new_dict(segments, initial_dict)\ndel initial_dict".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            //ap value is (0,0)
+            Vec::new(),
+            false,
+        );
+        vm.segments.add(&mut vm.memory, None);
+        //Store initial dict in scope
+        vm.exec_scopes.assign_or_update_variable(
+            "initial_dict",
+            PyValueType::Dictionary(HashMap::from([(bigint!(1), bigint!(2))])),
+        );
+        //ids and references are not needed for this test
+        execute_hint(&mut vm, hint_code, HashMap::new(), &ApTracking::new())
+            .expect("Error while executing hint");
+        //Check the dict manager has a tracker for segment 1 with the initial dict
+        assert_eq!(
+            vm.dict_manager.trackers.get(&1),
+            Some(&DictTracker::new_with_initial(
+                &relocatable!(1, 0),
+                HashMap::from([(bigint!(1), bigint!(2))])
+            ))
+        );
+        //Check that initial_dict was removed from scope
+        assert_eq!(get_initial_dict(&mut vm), None);
+    }
+
+    #[test]
+    fn run_dict_squash_copy_dict_valid() {
+        let hint_code = "# Prepare arguments for dict_new. In particular, the same dictionary values should be copied\n# to the new (squashed) dictionary.\nvm_enter_scope({\n    # Make __dict_manager accessible.\n    '__dict_manager': __dict_manager,\n    # Create a copy of the dict, in case it changes in the future.\n    'initial_dict': dict(__dict_manager.get_dict(ids.dict_accesses_end)),\n})".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..2 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 1));
+        //Create tracker
+        //current_ptr = dict_accesses_end = (1, 6)
+        let mut tracker = DictTracker::new_default_dict(
+            &relocatable!(1, 0),
+            &bigint!(17),
+            Some(HashMap::from([(bigint!(5), bigint!(10))])),
+        );
+        tracker.current_ptr = relocatable!(1, 6);
+        vm.dict_manager.trackers.insert(1, tracker);
+        //ids.dict_accesses_end
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from((1, 6)),
+            )
+            .unwrap();
+        //Create ids
+        let ids = HashMap::from([(String::from("dict_accesses_end"), bigint!(0))]);
+        //Create references
+        vm.references = HashMap::from([(
+            0,
+            HintReference {
+                register: Some(Register::FP),
+                offset1: -1,
+                offset2: 0,
+                inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
+                ap_tracking_data: None,
+                value: None,
+            },
+        )]);
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //Check that a new scope was entered with a copy of the dict as initial_dict
+        assert_eq!(vm.exec_scopes.data.len(), 2);
+        assert_eq!(
+            get_initial_dict(&mut vm),
+            Some(HashMap::from([(bigint!(5), bigint!(10))]))
+        );
+    }
+
+    #[test]
+    fn run_dict_squash_copy_dict_mismatched_ptr() {
+        let hint_code = "# Prepare arguments for dict_new. In particular, the same dictionary values should be copied\n# to the new (squashed) dictionary.\nvm_enter_scope({\n    # Make __dict_manager accessible.\n    '__dict_manager': __dict_manager,\n    # Create a copy of the dict, in case it changes in the future.\n    'initial_dict': dict(__dict_manager.get_dict(ids.dict_accesses_end)),\n})".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..2 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 1));
+        //Create tracker
+        //current_ptr = (1, 3) != dict_accesses_end
+        let mut tracker = DictTracker::new_empty(&relocatable!(1, 0));
+        tracker.current_ptr = relocatable!(1, 3);
+        vm.dict_manager.trackers.insert(1, tracker);
+        //ids.dict_accesses_end
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from((1, 6)),
+            )
+            .unwrap();
+        //Create ids
+        let ids = HashMap::from([(String::from("dict_accesses_end"), bigint!(0))]);
+        //Create references
+        vm.references = HashMap::from([(
+            0,
+            HintReference {
+                register: Some(Register::FP),
+                offset1: -1,
+                offset2: 0,
+                inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
+                ap_tracking_data: None,
+                value: None,
+            },
+        )]);
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Err(VirtualMachineError::MismatchedDictPtr(
+                relocatable!(1, 3),
+                relocatable!(1, 6)
+            ))
+        );
+    }
+
+    #[test]
+    fn run_dict_squash_update_ptr_valid() {
+        let hint_code = "# Update the DictTracker's current_ptr to point to the end of the squashed dict.\n__dict_manager.get_tracker(ids.squashed_dict_start).current_ptr = \\\n    ids.squashed_dict_end.address_".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..2 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 2));
+        //Create tracker for the squashed dict
+        //current_ptr = squashed_dict_start = (1, 0)
+        vm.dict_manager
+            .trackers
+            .insert(1, DictTracker::new_empty(&relocatable!(1, 0)));
+        //ids.squashed_dict_start
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from((1, 0)),
+            )
+            .unwrap();
+        //ids.squashed_dict_end
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 1)),
+                &MaybeRelocatable::from((1, 9)),
+            )
+            .unwrap();
+        //Create ids
+        let ids = HashMap::from([
+            (String::from("squashed_dict_start"), bigint!(0)),
+            (String::from("squashed_dict_end"), bigint!(1)),
+        ]);
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //Check that the tracker's current_ptr now points to the end of the squashed dict
+        assert_eq!(
+            vm.dict_manager.trackers.get(&1).unwrap().current_ptr,
+            relocatable!(1, 9)
+        );
+    }
+
+    #[test]
+    fn run_dict_squash_update_ptr_mismatched_dict_ptr() {
+        let hint_code = "# Update the DictTracker's current_ptr to point to the end of the squashed dict.\n__dict_manager.get_tracker(ids.squashed_dict_start).current_ptr = \\\n    ids.squashed_dict_end.address_".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..2 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 2));
+        //Create tracker whose current_ptr doesn't match squashed_dict_start
+        vm.dict_manager
+            .trackers
+            .insert(1, DictTracker::new_empty(&relocatable!(1, 3)));
+        //ids.squashed_dict_start
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from((1, 0)),
+            )
+            .unwrap();
+        //ids.squashed_dict_end
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 1)),
+                &MaybeRelocatable::from((1, 9)),
+            )
+            .unwrap();
+        //Create ids
+        let ids = HashMap::from([
+            (String::from("squashed_dict_start"), bigint!(0)),
+            (String::from("squashed_dict_end"), bigint!(1)),
+        ]);
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Err(VirtualMachineError::MismatchedDictPtr(
+                relocatable!(1, 3),
+                relocatable!(1, 0)
+            ))
+        );
+    }
 }
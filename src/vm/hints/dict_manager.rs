@@ -59,6 +59,17 @@ impl Dictionary {
         };
         dict.insert(key.clone(), value.clone());
     }
+
+    //Returns a copy of the key-value pairs currently stored in the dictionary
+    pub fn get_dictionary_copy(&self) -> HashMap<BigInt, BigInt> {
+        match self {
+            Self::SimpleDictionary(dict) => dict.clone(),
+            Self::DefaultDictionary {
+                dict,
+                default_value: _,
+            } => dict.clone(),
+        }
+    }
 }
 
 impl DictManager {
@@ -69,7 +80,7 @@ impl DictManager {
     }
     //Creates a new Cairo dictionary. The values of initial_dict can be integers, tuples or
     //lists. See MemorySegments.gen_arg().
-    //For now, no initial dict will be processed (Assumes initial_dict = None)
+    //For now, only integer values are supported in initial_dict
     pub fn new_dict(
         &mut self,
         segments: &mut MemorySegmentManager,
@@ -109,6 +120,39 @@ impl DictManager {
         );
         Ok(MaybeRelocatable::RelocatableValue(base))
     }
+
+    //Returns the tracker which's current_ptr matches with the given dict_ptr
+    pub fn get_tracker_mut(
+        &mut self,
+        dict_ptr: &Relocatable,
+    ) -> Result<&mut DictTracker, VirtualMachineError> {
+        let tracker = self
+            .trackers
+            .get_mut(&dict_ptr.segment_index)
+            .ok_or(VirtualMachineError::NoDictTracker(dict_ptr.segment_index))?;
+        if tracker.current_ptr != *dict_ptr {
+            return Err(VirtualMachineError::MismatchedDictPtr(
+                tracker.current_ptr.clone(),
+                dict_ptr.clone(),
+            ));
+        }
+        Ok(tracker)
+    }
+
+    //Returns the tracker which's current_ptr matches with the given dict_ptr
+    pub fn get_tracker(&self, dict_ptr: &Relocatable) -> Result<&DictTracker, VirtualMachineError> {
+        let tracker = self
+            .trackers
+            .get(&dict_ptr.segment_index)
+            .ok_or(VirtualMachineError::NoDictTracker(dict_ptr.segment_index))?;
+        if tracker.current_ptr != *dict_ptr {
+            return Err(VirtualMachineError::MismatchedDictPtr(
+                tracker.current_ptr.clone(),
+                dict_ptr.clone(),
+            ));
+        }
+        Ok(tracker)
+    }
 }
 
 impl Default for DictManager {
@@ -313,4 +357,53 @@ mod tests {
         assert_eq!(dictionary.get(&bigint!(1)), Some(&bigint!(2)));
         assert_eq!(dictionary.get(&bigint!(2)), Some(&bigint!(7)));
     }
+
+    #[test]
+    fn dictionary_get_copy_default() {
+        let mut dictionary = Dictionary::DefaultDictionary {
+            dict: HashMap::new(),
+            default_value: bigint!(7),
+        };
+        dictionary.insert(&bigint!(1), &bigint!(2));
+        assert_eq!(
+            dictionary.get_dictionary_copy(),
+            HashMap::from([(bigint!(1), bigint!(2))])
+        );
+    }
+
+    #[test]
+    fn dict_manager_get_tracker_valid() {
+        let mut dict_manager = DictManager::new();
+        dict_manager
+            .trackers
+            .insert(1, DictTracker::new_empty(&relocatable!(1, 3)));
+        assert_eq!(
+            dict_manager.get_tracker(&relocatable!(1, 3)),
+            Ok(&DictTracker::new_empty(&relocatable!(1, 3)))
+        );
+    }
+
+    #[test]
+    fn dict_manager_get_tracker_mismatched_ptr() {
+        let mut dict_manager = DictManager::new();
+        dict_manager
+            .trackers
+            .insert(1, DictTracker::new_empty(&relocatable!(1, 3)));
+        assert_eq!(
+            dict_manager.get_tracker_mut(&relocatable!(1, 0)),
+            Err(VirtualMachineError::MismatchedDictPtr(
+                relocatable!(1, 3),
+                relocatable!(1, 0)
+            ))
+        );
+    }
+
+    #[test]
+    fn dict_manager_get_tracker_no_tracker() {
+        let dict_manager = DictManager::new();
+        assert_eq!(
+            dict_manager.get_tracker(&relocatable!(2, 0)),
+            Err(VirtualMachineError::NoDictTracker(2))
+        );
+    }
 }
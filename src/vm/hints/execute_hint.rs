@@ -3,17 +3,21 @@ use std::collections::HashMap;
 use num_bigint::BigInt;
 
 use crate::serde::deserialize_program::ApTracking;
+use crate::serde::deserialize_utils::ReferenceExpr;
 use crate::types::instruction::Register;
 use crate::vm::errors::vm_errors::VirtualMachineError;
 use crate::vm::hints::dict_hint_utils::{
-    default_dict_new, dict_new, dict_read, dict_update, dict_write,
+    default_dict_new, dict_new, dict_read, dict_squash_copy_dict, dict_squash_update_ptr,
+    dict_update, dict_write,
 };
-use crate::vm::hints::find_element_hint::find_element;
+use crate::vm::hints::find_element_hint::{find_element, search_sorted_lower};
 use crate::vm::hints::hint_utils::{
-    add_segment, assert_250_bit, assert_le_felt, assert_lt_felt, assert_nn, assert_not_equal,
-    assert_not_zero, enter_scope, exit_scope, is_le_felt, is_nn, is_nn_out_of_range, is_positive,
-    memcpy_continue_copying, memcpy_enter_scope, signed_div_rem, split_felt, split_int,
-    split_int_assert_range, sqrt, unsigned_div_rem,
+    a_b_bitand_1, add_segment, assert_250_bit, assert_le_felt, assert_le_felt_excluded,
+    assert_le_felt_excluded_2, assert_le_felt_v_0_10, assert_lt_felt, assert_nn, assert_not_equal,
+    assert_not_zero, enter_scope, exit_scope, is_addr_bounded, is_le_felt, is_nn,
+    is_nn_out_of_range, is_positive, is_quad_residue, memcpy_continue_copying, memcpy_enter_scope,
+    signed_div_rem, split_felt, split_felt_max_high, split_int, split_int_assert_range, sqrt,
+    unsigned_div_rem,
 };
 use crate::vm::hints::memset_utils::{memset_continue_loop, memset_enter_scope};
 use crate::vm::hints::pow_utils::pow;
@@ -24,16 +28,31 @@ use crate::vm::hints::squash_dict_utils::{
     squash_dict_inner_len_assert, squash_dict_inner_next_key, squash_dict_inner_skip_loop,
     squash_dict_inner_used_accesses_assert,
 };
-use crate::vm::hints::uint256_utils::{split_64, uint256_add};
+use crate::vm::hints::uint256_utils::{
+    split_64, split_xx, uint256_add, uint256_mul_div_mod, uint256_signed_nn, uint256_sqrt,
+    uint256_unsigned_div_rem,
+};
+use crate::vm::hints::usort::{
+    usort_body, usort_enter_scope, verify_multiplicity_assert, verify_multiplicity_body,
+    verify_usort,
+};
 use crate::vm::vm_core::VirtualMachine;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct HintReference {
-    pub register: Register,
+    //None if the reference isn't of the form reg + offsets (e.g. [fp] * 2), in which case the
+    //offsets are unused and resolving the reference fails
+    pub register: Option<Register>,
     pub offset1: i32,
     pub offset2: i32,
     pub inner_dereference: bool,
     pub ap_tracking_data: Option<ApTracking>,
+    //Whether the reference is the value stored at the computed address or the address itself
+    pub dereference: bool,
+    //Cairo type of the referenced value, used to resolve struct members (ids.a.b)
+    pub cairo_type: Option<String>,
+    //Expression the reference was compiled from, None for references that aren't loaded from a program
+    pub value: Option<ReferenceExpr>,
 }
 
 pub fn execute_hint(
@@ -44,52 +63,64 @@ pub fn execute_hint(
 ) -> Result<(), VirtualMachineError> {
     match std::str::from_utf8(hint_code) {
         Ok("memory[ap] = segments.add()") => add_segment(vm),
-        Ok("memory[ap] = 0 if 0 <= (ids.a % PRIME) < range_check_builtin.bound else 1") => is_nn(vm, ids, None),
+        Ok("memory[ap] = 0 if 0 <= (ids.a % PRIME) < range_check_builtin.bound else 1") => is_nn(vm, ids, Some(ap_tracking)),
         Ok("memory[ap] = 0 if 0 <= ((-ids.a - 1) % PRIME) < range_check_builtin.bound else 1") => {
-            is_nn_out_of_range(vm, ids, None)
+            is_nn_out_of_range(vm, ids, Some(ap_tracking))
         }
-        Ok("memory[ap] = 0 if (ids.a % PRIME) <= (ids.b % PRIME) else 1") => is_le_felt(vm, ids, None),
+        Ok("memory[ap] = 0 if (ids.a % PRIME) <= (ids.b % PRIME) else 1") => is_le_felt(vm, ids, Some(ap_tracking)),
         Ok("from starkware.cairo.common.math_utils import assert_integer\nassert_integer(ids.a)\nassert_integer(ids.b)\na = ids.a % PRIME\nb = ids.b % PRIME\nassert a <= b, f'a = {a} is not less than or equal to b = {b}.'\n\nids.small_inputs = int(\n    a < range_check_builtin.bound and (b - a) < range_check_builtin.bound)",
-        ) => assert_le_felt(vm, ids, None),
+        ) => assert_le_felt(vm, ids, Some(ap_tracking)),
+        Ok("import itertools\n\nfrom starkware.cairo.common.math_utils import assert_integer\nassert_integer(ids.a)\nassert_integer(ids.b)\na = ids.a % PRIME\nb = ids.b % PRIME\nassert a <= b, f'a = {a} is not less than or equal to b = {b}.'\n\n# Find an arc less than PRIME / 3, and another less than PRIME / 2.\nlengths_and_indices = [(a, 0), (b - a, 1), (PRIME - 1 - b, 2)]\nlengths_and_indices.sort()\nassert lengths_and_indices[0][0] <= PRIME // 3 and lengths_and_indices[1][0] <= PRIME // 2\nexcluded = lengths_and_indices[2][1]\n\nmemory[ids.range_check_ptr + 1], memory[ids.range_check_ptr + 0] = (\n    divmod(lengths_and_indices[0][0], ids.PRIME_OVER_3_HIGH))\nmemory[ids.range_check_ptr + 3], memory[ids.range_check_ptr + 2] = (\n    divmod(lengths_and_indices[1][0], ids.PRIME_OVER_2_HIGH))"
+        ) => assert_le_felt_v_0_10(vm, ids, Some(ap_tracking)),
+        Ok("memory[ap] = 1 if excluded != 0 else 0") => assert_le_felt_excluded(vm, 0),
+        Ok("memory[ap] = 1 if excluded != 1 else 0") => assert_le_felt_excluded(vm, 1),
+        Ok("assert excluded == 2") => assert_le_felt_excluded_2(vm),
+        Ok("from starkware.crypto.signature.signature import FIELD_PRIME\nfrom starkware.python.math_utils import div_mod, is_quad_residue, sqrt\n\nx = ids.x\nif is_quad_residue(x, FIELD_PRIME):\n    ids.y = sqrt(x, FIELD_PRIME)\nelse:\n    ids.y = sqrt(div_mod(x, 3, FIELD_PRIME), FIELD_PRIME)"
+        ) => is_quad_residue(vm, ids, Some(ap_tracking)),
+        Ok("# Verify the assumptions on the relationship between 2**250, ADDR_BOUND and PRIME.\nADDR_BOUND = ids.ADDR_BOUND % PRIME\nassert (2**250 < ADDR_BOUND <= 2**251) and (2 * 2**250 < PRIME) and (\n        ADDR_BOUND * 2 > PRIME), \\\n    'normalize_address() cannot be used with the current constants.'\nids.is_small = 1 if ids.addr < ADDR_BOUND else 0"
+        ) => is_addr_bounded(vm, ids, Some(ap_tracking)),
+        Ok("ids.a_lsb = ids.a & 1\nids.b_lsb = ids.b & 1") => a_b_bitand_1(vm, ids, Some(ap_tracking)),
         Ok("from starkware.cairo.common.math_utils import as_int\n\n# Correctness check.\nvalue = as_int(ids.value, PRIME) % PRIME\nassert value < ids.UPPER_BOUND, f'{value} is outside of the range [0, 2**250).'\n\n# Calculation for the assertion.\nids.high, ids.low = divmod(ids.value, ids.SHIFT)",
-        ) => assert_250_bit(vm, ids, None),
+        ) => assert_250_bit(vm, ids, Some(ap_tracking)),
         Ok("from starkware.cairo.common.math_utils import is_positive\nids.is_positive = 1 if is_positive(\n    value=ids.value, prime=PRIME, rc_bound=range_check_builtin.bound) else 0"
         ) => is_positive(vm, ids, Some(ap_tracking)),
         Ok("assert ids.value == 0, 'split_int(): value is out of range.'"
-        ) => split_int_assert_range(vm, ids, None),
+        ) => split_int_assert_range(vm, ids, Some(ap_tracking)),
         Ok("memory[ids.output] = res = (int(ids.value) % PRIME) % ids.base\nassert res < ids.bound, f'split_int(): Limb {res} is out of range.'"
-        ) => split_int(vm, ids, None),
+        ) => split_int(vm, ids, Some(ap_tracking)),
         Ok("from starkware.cairo.lang.vm.relocatable import RelocatableValue\nboth_ints = isinstance(ids.a, int) and isinstance(ids.b, int)\nboth_relocatable = (\n    isinstance(ids.a, RelocatableValue) and isinstance(ids.b, RelocatableValue) and\n    ids.a.segment_index == ids.b.segment_index)\nassert both_ints or both_relocatable, \\\n    f'assert_not_equal failed: non-comparable values: {ids.a}, {ids.b}.'\nassert (ids.a - ids.b) % PRIME != 0, f'assert_not_equal failed: {ids.a} = {ids.b}.'"
-        ) => assert_not_equal(vm, ids, None),
+        ) => assert_not_equal(vm, ids, Some(ap_tracking)),
         Ok("from starkware.cairo.common.math_utils import assert_integer\nassert_integer(ids.a)\nassert 0 <= ids.a % PRIME < range_check_builtin.bound, f'a = {ids.a} is out of range.'"
-        ) => assert_nn(vm, ids, None),
+        ) => assert_nn(vm, ids, Some(ap_tracking)),
         Ok("from starkware.python.math_utils import isqrt\nvalue = ids.value % PRIME\nassert value < 2 ** 250, f\"value={value} is outside of the range [0, 2**250).\"\nassert 2 ** 250 < PRIME\nids.root = isqrt(value)"
-        ) => sqrt(vm, ids, None),
+        ) => sqrt(vm, ids, Some(ap_tracking)),
         Ok("from starkware.cairo.common.math_utils import assert_integer\nassert_integer(ids.value)\nassert ids.value % PRIME != 0, f'assert_not_zero failed: {ids.value} = 0.'"
-        ) => assert_not_zero(vm, ids, None),
+        ) => assert_not_zero(vm, ids, Some(ap_tracking)),
         Ok("vm_exit_scope()") => exit_scope(vm),
         Ok("vm_enter_scope({'n': ids.len})") => memcpy_enter_scope(vm, ids, Some(ap_tracking)),
         Ok("vm_enter_scope({'n': ids.n})") => memset_enter_scope(vm, ids, Some(ap_tracking)),
         Ok("n -= 1\nids.continue_copying = 1 if n > 0 else 0") => memcpy_continue_copying(vm, ids, Some(ap_tracking)),
         Ok("n -= 1\nids.continue_loop = 1 if n > 0 else 0") => memset_continue_loop(vm, ids, Some(ap_tracking)),
         Ok("from starkware.cairo.common.math_utils import assert_integer\nassert ids.MAX_HIGH < 2**128 and ids.MAX_LOW < 2**128\nassert PRIME - 1 == ids.MAX_HIGH * 2**128 + ids.MAX_LOW\nassert_integer(ids.value)\nids.low = ids.value & ((1 << 128) - 1)\nids.high = ids.value >> 128"
-        ) => split_felt(vm, ids, None),
+        ) => split_felt_max_high(vm, ids, Some(ap_tracking)),
+        Ok("from starkware.cairo.common.math_utils import assert_integer\nassert_integer(ids.value)\nids.low = ids.value & ((1 << 128) - 1)\nids.high = ids.value >> 128"
+        ) => split_felt(vm, ids, Some(ap_tracking)),
         Ok("from starkware.cairo.common.math_utils import assert_integer\nassert_integer(ids.div)\nassert 0 < ids.div <= PRIME // range_check_builtin.bound, \\\n    f'div={hex(ids.div)} is out of the valid range.'\nids.q, ids.r = divmod(ids.value, ids.div)"
-        ) => unsigned_div_rem(vm, ids, None),
+        ) => unsigned_div_rem(vm, ids, Some(ap_tracking)),
         Ok("from starkware.cairo.common.math_utils import as_int, assert_integer\n\nassert_integer(ids.div)\nassert 0 < ids.div <= PRIME // range_check_builtin.bound, \\\n    f'div={hex(ids.div)} is out of the valid range.'\n\nassert_integer(ids.bound)\nassert ids.bound <= range_check_builtin.bound // 2, \\\n    f'bound={hex(ids.bound)} is out of the valid range.'\n\nint_value = as_int(ids.value, PRIME)\nq, ids.r = divmod(int_value, ids.div)\n\nassert -ids.bound <= q < ids.bound, \\\n    f'{int_value} / {ids.div} = {q} is out of the range [{-ids.bound}, {ids.bound}).'\n\nids.biased_q = q + ids.bound"
-        ) => signed_div_rem(vm, ids, None),
+        ) => signed_div_rem(vm, ids, Some(ap_tracking)),
         Ok("from starkware.cairo.common.math_utils import assert_integer\nassert_integer(ids.a)\nassert_integer(ids.b)\nassert (ids.a % PRIME) < (ids.b % PRIME), \\\n    f'a = {ids.a % PRIME} is not less than b = {ids.b % PRIME}.'"
-        ) => assert_lt_felt(vm, ids, None),
+        ) => assert_lt_felt(vm, ids, Some(ap_tracking)),
         Ok("array_ptr = ids.array_ptr\nelm_size = ids.elm_size\nassert isinstance(elm_size, int) and elm_size > 0, \\\n    f'Invalid value for elm_size. Got: {elm_size}.'\nkey = ids.key\n\nif '__find_element_index' in globals():\n    ids.index = __find_element_index\n    found_key = memory[array_ptr + elm_size * __find_element_index]\n    assert found_key == key, \\\n        f'Invalid index found in __find_element_index. index: {__find_element_index}, ' \\\n        f'expected key {key}, found key: {found_key}.'\n    # Delete __find_element_index to make sure it's not used for the next calls.\n    del __find_element_index\nelse:\n    n_elms = ids.n_elms\n    assert isinstance(n_elms, int) and n_elms >= 0, \\\n        f'Invalid value for n_elms. Got: {n_elms}.'\n    if '__find_element_max_size' in globals():\n        assert n_elms <= __find_element_max_size, \\\n            f'find_element() can only be used with n_elms<={__find_element_max_size}. ' \\\n            f'Got: n_elms={n_elms}.'\n\n    for i in range(n_elms):\n        if memory[array_ptr + elm_size * i] == key:\n            ids.index = i\n            break\n    else:\n        raise ValueError(f'Key {key} was not found.')"
-        ) => find_element(vm, ids, None),
+        ) => find_element(vm, ids, Some(ap_tracking)),
         Ok("ids.locs.bit = (ids.prev_locs.exp % PRIME) & 1") => pow(vm, ids, Some(ap_tracking)),
-        Ok("assert ids.elm_size > 0\nassert ids.set_ptr <= ids.set_end_ptr\nelm_list = memory.get_range(ids.elm_ptr, ids.elm_size)\nfor i in range(0, ids.set_end_ptr - ids.set_ptr, ids.elm_size):\n    if memory.get_range(ids.set_ptr + i, ids.elm_size) == elm_list:\n        ids.index = i // ids.elm_size\n        ids.is_elm_in_set = 1\n        break\nelse:\n    ids.is_elm_in_set = 0") => set_add(vm, ids, None),
+        Ok("assert ids.elm_size > 0\nassert ids.set_ptr <= ids.set_end_ptr\nelm_list = memory.get_range(ids.elm_ptr, ids.elm_size)\nfor i in range(0, ids.set_end_ptr - ids.set_ptr, ids.elm_size):\n    if memory.get_range(ids.set_ptr + i, ids.elm_size) == elm_list:\n        ids.index = i // ids.elm_size\n        ids.is_elm_in_set = 1\n        break\nelse:\n    ids.is_elm_in_set = 0") => set_add(vm, ids, Some(ap_tracking)),
         Ok("if '__dict_manager' not in globals():\n    from starkware.cairo.common.dict import DictManager\n    __dict_manager = DictManager()\n\nmemory[ap] = __dict_manager.new_dict(segments, initial_dict)\ndel initial_dict"
         ) => dict_new(vm),
         Ok("dict_tracker = __dict_manager.get_tracker(ids.dict_ptr)\ndict_tracker.current_ptr += ids.DictAccess.SIZE\nids.value = dict_tracker.data[ids.key]"
-        ) => dict_read(vm, ids, None),
+        ) => dict_read(vm, ids, Some(ap_tracking)),
         Ok("dict_tracker = __dict_manager.get_tracker(ids.dict_ptr)\ndict_tracker.current_ptr += ids.DictAccess.SIZE\nids.dict_ptr.prev_value = dict_tracker.data[ids.key]\ndict_tracker.data[ids.key] = ids.new_value"
-        ) => dict_write(vm, ids, None),
+        ) => dict_write(vm, ids, Some(ap_tracking)),
         Ok("if '__dict_manager' not in globals():\n    from starkware.cairo.common.dict import DictManager\n    __dict_manager = DictManager()\n\nmemory[ap] = __dict_manager.new_default_dict(segments, ids.default_value)"
         ) => default_dict_new(vm, ids, Some(ap_tracking)),
         Ok("current_access_indices = sorted(access_indices[key])[::-1]\ncurrent_access_index = current_access_indices.pop()\nmemory[ids.range_check_ptr] = current_access_index"
@@ -107,12 +138,33 @@ pub fn execute_hint(
         ) => squash_dict_inner_next_key(vm, ids, Some(ap_tracking)),
         Ok("dict_access_size = ids.DictAccess.SIZE\naddress = ids.dict_accesses.address_\nassert ids.ptr_diff % dict_access_size == 0, \\\n    'Accesses array size must be divisible by DictAccess.SIZE'\nn_accesses = ids.n_accesses\nif '__squash_dict_max_size' in globals():\n    assert n_accesses <= __squash_dict_max_size, \\\n        f'squash_dict() can only be used with n_accesses<={__squash_dict_max_size}. ' \\\n        f'Got: n_accesses={n_accesses}.'\n# A map from key to the list of indices accessing it.\naccess_indices = {}\nfor i in range(n_accesses):\n    key = memory[address + dict_access_size * i]\n    access_indices.setdefault(key, []).append(i)\n# Descending list of keys.\nkeys = sorted(access_indices.keys(), reverse=True)\n# Are the keys used bigger than range_check bound.\nids.big_keys = 1 if keys[0] >= range_check_builtin.bound else 0\nids.first_key = key = keys.pop()"
         ) => squash_dict(vm, ids, Some(ap_tracking)),
-        Ok("vm_enter_scope()") => enter_scope(vm),
+        Ok("vm_enter_scope()") => enter_scope(vm, HashMap::new()),
         Ok("# Verify dict pointer and prev value.\ndict_tracker = __dict_manager.get_tracker(ids.dict_ptr)\ncurrent_value = dict_tracker.data[ids.key]\nassert current_value == ids.prev_value, \\\n    f'Wrong previous value in dict. Got {ids.prev_value}, expected {current_value}.'\n\n# Update value.\ndict_tracker.data[ids.key] = ids.new_value\ndict_tracker.current_ptr += ids.DictAccess.SIZE"
-        ) => dict_update(vm, ids, None),
+        ) => dict_update(vm, ids, Some(ap_tracking)),
+        Ok("# Prepare arguments for dict_new. In particular, the same dictionary values should be copied\n# to the new (squashed) dictionary.\nvm_enter_scope({\n    # Make __dict_manager accessible.\n    '__dict_manager': __dict_manager,\n    # Create a copy of the dict, in case it changes in the future.\n    'initial_dict': dict(__dict_manager.get_dict(ids.dict_accesses_end)),\n})"
+        ) => dict_squash_copy_dict(vm, ids, Some(ap_tracking)),
+        Ok("# Update the DictTracker's current_ptr to point to the end of the squashed dict.\n__dict_manager.get_tracker(ids.squashed_dict_start).current_ptr = \\\n    ids.squashed_dict_end.address_"
+        ) => dict_squash_update_ptr(vm, ids, Some(ap_tracking)),
         Ok("sum_low = ids.a.low + ids.b.low\nids.carry_low = 1 if sum_low >= ids.SHIFT else 0\nsum_high = ids.a.high + ids.b.high + ids.carry_low\nids.carry_high = 1 if sum_high >= ids.SHIFT else 0"
-        ) => uint256_add(vm, ids, None),
-        Ok("ids.low = ids.a & ((1<<64) - 1)\nids.high = ids.a >> 64") => split_64(vm, ids, None),
+        ) => uint256_add(vm, ids, Some(ap_tracking)),
+        Ok("ids.low = ids.a & ((1<<64) - 1)\nids.high = ids.a >> 64") => split_64(vm, ids, Some(ap_tracking)),
+        Ok("from starkware.python.math_utils import isqrt\nn = (ids.n.high << 128) + ids.n.low\nroot = isqrt(n)\nassert 0 <= root < 2 ** 128\nids.root.low = root\nids.root.high = 0"
+        ) => uint256_sqrt(vm, ids, Some(ap_tracking)),
+        Ok("PRIME = 2**255 - 19\nII = pow(2, (PRIME - 1) // 4, PRIME)\n\nxx = ids.xx.low + (ids.xx.high<<128)\nx = pow(xx, (PRIME + 3) // 8, PRIME)\nif (x * x - xx) % PRIME != 0:\n    x = (x * II) % PRIME\nif x % 2 != 0:\n    x = PRIME - x\nids.x.low = x & ((1<<128)-1)\nids.x.high = x >> 128"
+        ) => split_xx(vm, ids, Some(ap_tracking)),
+        Ok("memory[ap] = 1 if 0 <= (ids.a.high % PRIME) < 2 ** 127 else 0") => uint256_signed_nn(vm, ids, Some(ap_tracking)),
+        Ok("a = (ids.a.high << 128) + ids.a.low\ndiv = (ids.div.high << 128) + ids.div.low\nquotient, remainder = divmod(a, div)\n\nids.quotient.low = quotient & ((1 << 128) - 1)\nids.quotient.high = quotient >> 128\nids.remainder.low = remainder & ((1 << 128) - 1)\nids.remainder.high = remainder >> 128"
+        ) => uint256_unsigned_div_rem(vm, ids, Some(ap_tracking)),
+        Ok("a = (ids.a.high << 128) + ids.a.low\nb = (ids.b.high << 128) + ids.b.low\ndiv = (ids.div.high << 128) + ids.div.low\nquotient, remainder = divmod(a * b, div)\n\nids.quotient_low.low = quotient & ((1 << 128) - 1)\nids.quotient_low.high = (quotient >> 128) & ((1 << 128) - 1)\nids.quotient_high.low = (quotient >> 256) & ((1 << 128) - 1)\nids.quotient_high.high = quotient >> 384\nids.remainder.low = remainder & ((1 << 128) - 1)\nids.remainder.high = remainder >> 128"
+        ) => uint256_mul_div_mod(vm, ids, Some(ap_tracking)),
+        Ok("array_ptr = ids.array_ptr\nelm_size = ids.elm_size\nassert isinstance(elm_size, int) and elm_size > 0, \\\n    f'Invalid value for elm_size. Got: {elm_size}.'\n\nn_elms = ids.n_elms\nassert isinstance(n_elms, int) and n_elms >= 0, \\\n    f'Invalid value for n_elms. Got: {n_elms}.'\nif '__find_element_max_size' in globals():\n    assert n_elms <= __find_element_max_size, \\\n        f'find_element() can only be used with n_elms<={__find_element_max_size}. ' \\\n        f'Got: n_elms={n_elms}.'\n\nfor i in range(n_elms):\n    if memory[array_ptr + elm_size * i] >= ids.key:\n        ids.index = i\n        break\nelse:\n    ids.index = n_elms"
+        ) => search_sorted_lower(vm, ids, Some(ap_tracking)),
+        Ok("vm_enter_scope(dict(__usort_max_size = globals().get('__usort_max_size')))") => usort_enter_scope(vm),
+        Ok("from collections import defaultdict\n\ninput_ptr = ids.input\ninput_len = int(ids.input_len)\nif __usort_max_size is not None:\n    assert input_len <= __usort_max_size, (\n        f\"usort() can only be used with input_len<={__usort_max_size}. \"\n        f\"Got: input_len={input_len}.\"\n    )\n\npositions_dict = defaultdict(list)\nfor i in range(input_len):\n    val = memory[input_ptr + i]\n    positions_dict[val].append(i)\n\noutput = sorted(positions_dict.keys())\nids.output_len = len(output)\nids.output = segments.gen_arg(output)\nids.multiplicities = segments.gen_arg([len(positions_dict[k]) for k in output])"
+        ) => usort_body(vm, ids, Some(ap_tracking)),
+        Ok("last_pos = 0\npositions = positions_dict[ids.value][::-1]") => verify_usort(vm, ids, Some(ap_tracking)),
+        Ok("assert len(positions) == 0") => verify_multiplicity_assert(vm),
+        Ok("current_pos = positions.pop()\nids.next_item_index = current_pos - last_pos\nlast_pos = current_pos + 1") => verify_multiplicity_body(vm, ids, Some(ap_tracking)),
         Ok(hint_code) => Err(VirtualMachineError::UnknownHint(String::from(hint_code))),
         Err(_) => Err(VirtualMachineError::InvalidHintEncoding(
             vm.run_context.pc.clone(),
@@ -264,11 +316,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -313,11 +368,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -367,11 +425,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -427,11 +488,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -497,11 +561,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -545,11 +612,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -610,31 +680,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -687,21 +766,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -746,21 +831,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -811,21 +902,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -879,21 +976,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -938,11 +1041,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -4,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -985,11 +1091,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -4,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1031,11 +1140,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -4,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1080,11 +1192,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: 10,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1126,11 +1241,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -4,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1171,11 +1289,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -4,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1209,11 +1330,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -4,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1272,31 +1396,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1356,31 +1489,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1440,31 +1582,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1526,31 +1677,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1596,11 +1756,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1645,11 +1808,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1697,21 +1863,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1762,21 +1934,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1828,21 +2006,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1895,21 +2079,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1960,21 +2150,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2022,21 +2218,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2087,21 +2289,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2128,11 +2336,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         vm.segments.add(&mut vm.memory, None);
@@ -2170,11 +2381,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         vm.segments.add(&mut vm.memory, None);
@@ -2212,11 +2426,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         vm.segments.add(&mut vm.memory, None);
@@ -2257,11 +2474,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         vm.segments.add(&mut vm.memory, None);
@@ -2299,11 +2519,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         vm.segments.add(&mut vm.memory, None);
@@ -2343,11 +2566,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         vm.segments.add(&mut vm.memory, None);
@@ -2402,11 +2628,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -2444,11 +2673,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -2512,41 +2744,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2615,41 +2859,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2695,21 +2951,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2758,21 +3020,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2824,21 +3092,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2892,21 +3166,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2953,21 +3233,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3013,21 +3299,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3077,21 +3369,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3149,41 +3447,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3240,41 +3550,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3326,41 +3648,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3418,41 +3752,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3510,41 +3856,53 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3604,61 +3962,79 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -6,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 5,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3723,61 +4099,79 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -6,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 5,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3842,61 +4236,79 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -6,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 5,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -3956,61 +4368,79 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -6,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 5,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4076,61 +4506,79 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -6,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 5,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4196,61 +4644,79 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -6,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 5,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4269,6 +4735,17 @@ mod tests {
             Vec::new(),
             false,
         );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.assert_250_bit.UPPER_BOUND"),
+                bigint!(1).shl(250_i32),
+            ),
+            (
+                String::from("starkware.cairo.common.math.assert_250_bit.SHIFT"),
+                bigint!(1).shl(128_i32),
+            ),
+        ]);
         for _ in 0..2 {
             vm.segments.add(&mut vm.memory, None);
         }
@@ -4292,31 +4769,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4337,6 +4823,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn run_assert_250_bit_unexpected_constant() {
+        let hint_code = "from starkware.cairo.common.math_utils import as_int\n\n# Correctness check.\nvalue = as_int(ids.value, PRIME) % PRIME\nassert value < ids.UPPER_BOUND, f'{value} is outside of the range [0, 2**250).'\n\n# Calculation for the assertion.\nids.high, ids.low = divmod(ids.value, ids.SHIFT)"
+             .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        //The hint's scopes resolve SHIFT to the library constant, not to __main__.SHIFT
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.assert_250_bit.UPPER_BOUND"),
+                bigint!(1).shl(250_i32),
+            ),
+            (
+                String::from("starkware.cairo.common.math.assert_250_bit.SHIFT"),
+                bigint!(1).shl(127_i32),
+            ),
+            (String::from("__main__.SHIFT"), bigint!(1).shl(128_i32)),
+        ]);
+        vm.accessible_scopes = vec![
+            String::from("starkware.cairo.common.math"),
+            String::from("starkware.cairo.common.math.assert_250_bit"),
+        ];
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, HashMap::new(), &ApTracking::new()),
+            Err(VirtualMachineError::UnexpectedConstantValue(
+                String::from("SHIFT"),
+                bigint!(1).shl(128_i32),
+                bigint!(1).shl(127_i32)
+            ))
+        );
+    }
+
     #[test]
     fn run_assert_250_bit_invalid() {
         let hint_code = "from starkware.cairo.common.math_utils import as_int\n\n# Correctness check.\nvalue = as_int(ids.value, PRIME) % PRIME\nassert value < ids.UPPER_BOUND, f'{value} is outside of the range [0, 2**250).'\n\n# Calculation for the assertion.\nids.high, ids.low = divmod(ids.value, ids.SHIFT)"
@@ -4346,6 +4867,17 @@ mod tests {
             Vec::new(),
             false,
         );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.assert_250_bit.UPPER_BOUND"),
+                bigint!(1).shl(250_i32),
+            ),
+            (
+                String::from("starkware.cairo.common.math.assert_250_bit.SHIFT"),
+                bigint!(1).shl(128_i32),
+            ),
+        ]);
         for _ in 0..2 {
             vm.segments.add(&mut vm.memory, None);
         }
@@ -4369,31 +4901,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4419,6 +4960,17 @@ mod tests {
             )],
             false,
         );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_HIGH"),
+                bigint_str!(b"10633823966279327296825105735305134080"),
+            ),
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_LOW"),
+                bigint!(0),
+            ),
+        ]);
         for _ in 0..3 {
             vm.segments.add(&mut vm.memory, None);
         }
@@ -4453,31 +5005,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 1,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4513,6 +5074,17 @@ mod tests {
             )],
             false,
         );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_HIGH"),
+                bigint_str!(b"10633823966279327296825105735305134080"),
+            ),
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_LOW"),
+                bigint!(0),
+            ),
+        ]);
         for _ in 0..3 {
             vm.segments.add(&mut vm.memory, None);
         }
@@ -4545,31 +5117,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 1,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4599,6 +5180,17 @@ mod tests {
             )],
             false,
         );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_HIGH"),
+                bigint_str!(b"10633823966279327296825105735305134080"),
+            ),
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_LOW"),
+                bigint!(0),
+            ),
+        ]);
         for _ in 0..3 {
             vm.segments.add(&mut vm.memory, None);
         }
@@ -4634,31 +5226,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 0,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 1,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4682,6 +5283,17 @@ mod tests {
             )],
             false,
         );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_HIGH"),
+                bigint_str!(b"10633823966279327296825105735305134080"),
+            ),
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_LOW"),
+                bigint!(0),
+            ),
+        ]);
         for _ in 0..3 {
             vm.segments.add(&mut vm.memory, None);
         }
@@ -4716,31 +5328,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 1,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4779,6 +5400,17 @@ mod tests {
             )],
             false,
         );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_HIGH"),
+                bigint_str!(b"10633823966279327296825105735305134080"),
+            ),
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_LOW"),
+                bigint!(0),
+            ),
+        ]);
         for _ in 0..3 {
             vm.segments.add(&mut vm.memory, None);
         }
@@ -4813,31 +5445,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 1,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4876,6 +5517,17 @@ mod tests {
             )],
             false,
         );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_HIGH"),
+                bigint_str!(b"10633823966279327296825105735305134080"),
+            ),
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_LOW"),
+                bigint!(0),
+            ),
+        ]);
         for _ in 0..3 {
             vm.segments.add(&mut vm.memory, None);
         }
@@ -4910,31 +5562,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 1,
                     inner_dereference: true,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -4994,21 +5655,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -5066,21 +5733,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -5137,21 +5810,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -5213,21 +5892,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 0,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -5285,21 +5970,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -5359,21 +6050,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -5433,21 +6130,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -5488,17 +6191,37 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
         assert!(execute_hint(&mut vm, hint_code, ids, &ApTracking::new()).is_ok());
     }
 
+    #[test]
+    fn enter_scope_with_unknown_locals() {
+        let hint_code = "vm_enter_scope({'n': 3})";
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+
+        assert_eq!(
+            execute_hint(
+                &mut vm,
+                hint_code.as_bytes(),
+                HashMap::new(),
+                &ApTracking::new()
+            ),
+            Err(VirtualMachineError::UnknownHint(String::from(hint_code)))
+        );
+        assert_eq!(vm.exec_scopes.data.len(), 1);
+    }
+
     #[test]
     fn memcpy_enter_scope_invalid() {
         let hint_code = "vm_enter_scope({'n': ids.len})".as_bytes();
@@ -5530,11 +6253,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -5581,11 +6307,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -5627,11 +6356,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -5678,11 +6410,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -5760,4 +6495,928 @@ mod tests {
         let expected_scope = vec![HashMap::new(), HashMap::new()];
         assert_eq!(vm.exec_scopes.data, expected_scope)
     }
+
+    #[test]
+    fn run_assert_le_felt_v_0_10_valid() {
+        let hint_code = "import itertools\n\nfrom starkware.cairo.common.math_utils import assert_integer\nassert_integer(ids.a)\nassert_integer(ids.b)\na = ids.a % PRIME\nb = ids.b % PRIME\nassert a <= b, f'a = {a} is not less than or equal to b = {b}.'\n\n# Find an arc less than PRIME / 3, and another less than PRIME / 2.\nlengths_and_indices = [(a, 0), (b - a, 1), (PRIME - 1 - b, 2)]\nlengths_and_indices.sort()\nassert lengths_and_indices[0][0] <= PRIME // 3 and lengths_and_indices[1][0] <= PRIME // 2\nexcluded = lengths_and_indices[2][1]\n\nmemory[ids.range_check_ptr + 1], memory[ids.range_check_ptr + 0] = (\n    divmod(lengths_and_indices[0][0], ids.PRIME_OVER_3_HIGH))\nmemory[ids.range_check_ptr + 3], memory[ids.range_check_ptr + 2] = (\n    divmod(lengths_and_indices[1][0], ids.PRIME_OVER_2_HIGH))"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.assert_le_felt.PRIME_OVER_3_HIGH"),
+                bigint_str!(b"3544607988759775765608368578435044694"),
+            ),
+            (
+                String::from("starkware.cairo.common.math.assert_le_felt.PRIME_OVER_2_HIGH"),
+                bigint_str!(b"5316911983139663648412552867652567041"),
+            ),
+        ]);
+        for _ in 0..2 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 3));
+        //Insert ids into memory
+        //ids.a
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+        //ids.b
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 1)),
+                &MaybeRelocatable::from(bigint!(2)),
+            )
+            .unwrap();
+        //ids.range_check_ptr
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 2)),
+                &MaybeRelocatable::from((1, 0)),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("a"), bigint!(0));
+        ids.insert(String::from("b"), bigint!(1));
+        ids.insert(String::from("range_check_ptr"), bigint!(2));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -3,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                2,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //lengths_and_indices = [(1, 0), (1, 1), (PRIME - 3, 2)], so excluded = 2
+        assert_eq!(
+            vm.exec_scopes
+                .get_local_variables()
+                .unwrap()
+                .get("excluded"),
+            Some(&PyValueType::BigInt(bigint!(2)))
+        );
+        //Check the values written into the range_check segment
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 0))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(1))))
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 1))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 2))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(1))))
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 3))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+    }
+
+    #[test]
+    fn run_assert_le_felt_v_0_10_a_bigger_than_b() {
+        let hint_code = "import itertools\n\nfrom starkware.cairo.common.math_utils import assert_integer\nassert_integer(ids.a)\nassert_integer(ids.b)\na = ids.a % PRIME\nb = ids.b % PRIME\nassert a <= b, f'a = {a} is not less than or equal to b = {b}.'\n\n# Find an arc less than PRIME / 3, and another less than PRIME / 2.\nlengths_and_indices = [(a, 0), (b - a, 1), (PRIME - 1 - b, 2)]\nlengths_and_indices.sort()\nassert lengths_and_indices[0][0] <= PRIME // 3 and lengths_and_indices[1][0] <= PRIME // 2\nexcluded = lengths_and_indices[2][1]\n\nmemory[ids.range_check_ptr + 1], memory[ids.range_check_ptr + 0] = (\n    divmod(lengths_and_indices[0][0], ids.PRIME_OVER_3_HIGH))\nmemory[ids.range_check_ptr + 3], memory[ids.range_check_ptr + 2] = (\n    divmod(lengths_and_indices[1][0], ids.PRIME_OVER_2_HIGH))"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.assert_le_felt.PRIME_OVER_3_HIGH"),
+                bigint_str!(b"3544607988759775765608368578435044694"),
+            ),
+            (
+                String::from("starkware.cairo.common.math.assert_le_felt.PRIME_OVER_2_HIGH"),
+                bigint_str!(b"5316911983139663648412552867652567041"),
+            ),
+        ]);
+        for _ in 0..2 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 3));
+        //Insert ids into memory
+        //ids.a
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(3)),
+            )
+            .unwrap();
+        //ids.b
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 1)),
+                &MaybeRelocatable::from(bigint!(2)),
+            )
+            .unwrap();
+        //ids.range_check_ptr
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 2)),
+                &MaybeRelocatable::from((1, 0)),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("a"), bigint!(0));
+        ids.insert(String::from("b"), bigint!(1));
+        ids.insert(String::from("range_check_ptr"), bigint!(2));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -3,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                2,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Err(VirtualMachineError::NonLeFelt(bigint!(3), bigint!(2)))
+        );
+    }
+
+    #[test]
+    fn run_assert_le_felt_excluded_0_true() {
+        let hint_code = "memory[ap] = 1 if excluded != 0 else 0".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Store excluded in scope
+        vm.exec_scopes
+            .assign_or_update_variable("excluded", PyValueType::BigInt(bigint!(2)));
+        //ids and references are not needed for this test
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, HashMap::new(), &ApTracking::new()),
+            Ok(())
+        );
+        //Check the value written into ap
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 0))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(1))))
+        );
+    }
+
+    #[test]
+    fn run_assert_le_felt_excluded_1_false() {
+        let hint_code = "memory[ap] = 1 if excluded != 1 else 0".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Store excluded in scope
+        vm.exec_scopes
+            .assign_or_update_variable("excluded", PyValueType::BigInt(bigint!(1)));
+        //ids and references are not needed for this test
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, HashMap::new(), &ApTracking::new()),
+            Ok(())
+        );
+        //Check the value written into ap
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 0))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+    }
+
+    #[test]
+    fn run_assert_le_felt_excluded_0_no_excluded() {
+        let hint_code = "memory[ap] = 1 if excluded != 0 else 0".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //ids and references are not needed for this test
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, HashMap::new(), &ApTracking::new()),
+            Err(VirtualMachineError::VariableNotInScopeError(String::from(
+                "excluded"
+            )))
+        );
+    }
+
+    #[test]
+    fn run_assert_le_felt_excluded_2_invalid() {
+        let hint_code = "assert excluded == 2".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        //Store excluded in scope
+        vm.exec_scopes
+            .assign_or_update_variable("excluded", PyValueType::BigInt(bigint!(0)));
+        //ids and references are not needed for this test
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, HashMap::new(), &ApTracking::new()),
+            Err(VirtualMachineError::AssertionFailed(String::from(
+                "assert 0 == 2"
+            )))
+        );
+    }
+
+    #[test]
+    fn run_is_quad_residue_true() {
+        let hint_code = "from starkware.crypto.signature.signature import FIELD_PRIME\nfrom starkware.python.math_utils import div_mod, is_quad_residue, sqrt\n\nx = ids.x\nif is_quad_residue(x, FIELD_PRIME):\n    ids.y = sqrt(x, FIELD_PRIME)\nelse:\n    ids.y = sqrt(div_mod(x, 3, FIELD_PRIME), FIELD_PRIME)"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 2));
+        //Insert ids into memory
+        //ids.x
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(4)),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("x"), bigint!(0));
+        ids.insert(String::from("y"), bigint!(1));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //Check ids.y
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 1))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(2))))
+        );
+    }
+
+    #[test]
+    fn run_is_quad_residue_false() {
+        let hint_code = "from starkware.crypto.signature.signature import FIELD_PRIME\nfrom starkware.python.math_utils import div_mod, is_quad_residue, sqrt\n\nx = ids.x\nif is_quad_residue(x, FIELD_PRIME):\n    ids.y = sqrt(x, FIELD_PRIME)\nelse:\n    ids.y = sqrt(div_mod(x, 3, FIELD_PRIME), FIELD_PRIME)"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 2));
+        //Insert ids into memory
+        //ids.x
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(3)),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("x"), bigint!(0));
+        ids.insert(String::from("y"), bigint!(1));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //3 is not a quadratic residue, so y = sqrt(3 / 3)
+        //Check ids.y
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 1))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(1))))
+        );
+    }
+
+    #[test]
+    fn run_is_addr_bounded_small() {
+        let hint_code = "# Verify the assumptions on the relationship between 2**250, ADDR_BOUND and PRIME.\nADDR_BOUND = ids.ADDR_BOUND % PRIME\nassert (2**250 < ADDR_BOUND <= 2**251) and (2 * 2**250 < PRIME) and (\n        ADDR_BOUND * 2 > PRIME), \\\n    'normalize_address() cannot be used with the current constants.'\nids.is_small = 1 if ids.addr < ADDR_BOUND else 0"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([(
+            String::from("starkware.starknet.common.storage.ADDR_BOUND"),
+            bigint!(1).shl(251_i32) - 256_i32,
+        )]);
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 2));
+        //Insert ids into memory
+        //ids.addr
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("addr"), bigint!(0));
+        ids.insert(String::from("is_small"), bigint!(1));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //Check ids.is_small
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 1))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(1))))
+        );
+    }
+
+    #[test]
+    fn run_is_addr_bounded_not_small() {
+        let hint_code = "# Verify the assumptions on the relationship between 2**250, ADDR_BOUND and PRIME.\nADDR_BOUND = ids.ADDR_BOUND % PRIME\nassert (2**250 < ADDR_BOUND <= 2**251) and (2 * 2**250 < PRIME) and (\n        ADDR_BOUND * 2 > PRIME), \\\n    'normalize_address() cannot be used with the current constants.'\nids.is_small = 1 if ids.addr < ADDR_BOUND else 0"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([(
+            String::from("starkware.starknet.common.storage.ADDR_BOUND"),
+            bigint!(1).shl(251_i32) - 256_i32,
+        )]);
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 2));
+        //Insert ids into memory
+        //ids.addr = ADDR_BOUND
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(1).shl(251_i32) - 256_i32),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("addr"), bigint!(0));
+        ids.insert(String::from("is_small"), bigint!(1));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //Check ids.is_small
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 1))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+    }
+
+    #[test]
+    fn run_a_b_bitand_1() {
+        let hint_code = "ids.a_lsb = ids.a & 1\nids.b_lsb = ids.b & 1".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 4));
+        //Insert ids into memory
+        //ids.a
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(5)),
+            )
+            .unwrap();
+        //ids.b
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 1)),
+                &MaybeRelocatable::from(bigint!(6)),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("a"), bigint!(0));
+        ids.insert(String::from("b"), bigint!(1));
+        ids.insert(String::from("a_lsb"), bigint!(2));
+        ids.insert(String::from("b_lsb"), bigint!(3));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -4,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -3,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                2,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                3,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //Check ids.a_lsb and ids.b_lsb
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 2))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(1))))
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 3))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+    }
+
+    #[test]
+    fn run_split_felt_no_max_high_constants() {
+        let hint_code = "from starkware.cairo.common.math_utils import assert_integer\nassert_integer(ids.value)\nids.low = ids.value & ((1 << 128) - 1)\nids.high = ids.value >> 128"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 3));
+        //Insert ids into memory
+        //ids.value
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(1).shl(128_i32) + 5_i32),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("value"), bigint!(0));
+        ids.insert(String::from("low"), bigint!(1));
+        ids.insert(String::from("high"), bigint!(2));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -3,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                2,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //Check ids.low and ids.high
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 1))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(5))))
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 2))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(1))))
+        );
+    }
+
+    #[test]
+    fn run_assert_250_bit_upper_bound_excluded() {
+        let hint_code = "from starkware.cairo.common.math_utils import as_int\n\n# Correctness check.\nvalue = as_int(ids.value, PRIME) % PRIME\nassert value < ids.UPPER_BOUND, f'{value} is outside of the range [0, 2**250).'\n\n# Calculation for the assertion.\nids.high, ids.low = divmod(ids.value, ids.SHIFT)"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.assert_250_bit.UPPER_BOUND"),
+                bigint!(1).shl(250_i32),
+            ),
+            (
+                String::from("starkware.cairo.common.math.assert_250_bit.SHIFT"),
+                bigint!(1).shl(128_i32),
+            ),
+        ]);
+        for _ in 0..2 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 3));
+        //Insert ids into memory
+        //ids.value = 2**250
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(1).shl(250_i32)),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("value"), bigint!(0));
+        ids.insert(String::from("high"), bigint!(1));
+        ids.insert(String::from("low"), bigint!(2));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -3,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                2,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -1,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Err(VirtualMachineError::ValueOutside250BitRange(
+                bigint!(1).shl(250_i32)
+            ))
+        );
+    }
+
+    #[test]
+    fn run_split_felt_wrong_max_high_constant() {
+        let hint_code = "from starkware.cairo.common.math_utils import assert_integer\nassert ids.MAX_HIGH < 2**128 and ids.MAX_LOW < 2**128\nassert PRIME - 1 == ids.MAX_HIGH * 2**128 + ids.MAX_LOW\nassert_integer(ids.value)\nids.low = ids.value & ((1 << 128) - 1)\nids.high = ids.value >> 128"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        //Store program constants that don't match the prime
+        vm.constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_HIGH"),
+                bigint!(1),
+            ),
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_LOW"),
+                bigint!(0),
+            ),
+        ]);
+        //ids and references are not needed for this test
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, HashMap::new(), &ApTracking::new()),
+            Err(VirtualMachineError::AssertionFailed(String::from(
+                "assert PRIME - 1 == 1 * 2**128 + 0"
+            )))
+        );
+    }
+
+    #[test]
+    fn run_split_felt_missing_constants() {
+        let hint_code = "from starkware.cairo.common.math_utils import assert_integer\nassert ids.MAX_HIGH < 2**128 and ids.MAX_LOW < 2**128\nassert PRIME - 1 == ids.MAX_HIGH * 2**128 + ids.MAX_LOW\nassert_integer(ids.value)\nids.low = ids.value & ((1 << 128) - 1)\nids.high = ids.value >> 128"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        //ids and references are not needed for this test
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, HashMap::new(), &ApTracking::new()),
+            Err(VirtualMachineError::MissingConstant(String::from(
+                "MAX_HIGH"
+            )))
+        );
+    }
 }
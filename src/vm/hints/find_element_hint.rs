@@ -2,8 +2,13 @@ use crate::bigint;
 use crate::serde::deserialize_program::ApTracking;
 use crate::types::{exec_scope::PyValueType, relocatable::MaybeRelocatable};
 use crate::vm::{
-    errors::vm_errors::VirtualMachineError, hints::hint_utils::get_address_from_var_name,
-    runners::builtin_runner::RangeCheckBuiltinRunner, vm_core::VirtualMachine,
+    errors::vm_errors::VirtualMachineError,
+    hints::hint_utils::{
+        get_address_from_var_name, get_int_from_scope, get_integer_from_var_name,
+        get_range_check_builtin,
+    },
+    runners::builtin_runner::RangeCheckBuiltinRunner,
+    vm_core::VirtualMachine,
 };
 use num_bigint::BigInt;
 use num_traits::{FromPrimitive, Signed, ToPrimitive};
@@ -165,16 +170,110 @@ pub fn find_element(
     }
 }
 
+/*
+Implements hint:
+%{
+    array_ptr = ids.array_ptr
+    elm_size = ids.elm_size
+    assert isinstance(elm_size, int) and elm_size > 0, \
+        f'Invalid value for elm_size. Got: {elm_size}.'
+
+    n_elms = ids.n_elms
+    assert isinstance(n_elms, int) and n_elms >= 0, \
+        f'Invalid value for n_elms. Got: {n_elms}.'
+    if '__find_element_max_size' in globals():
+        assert n_elms <= __find_element_max_size, \
+            f'find_element() can only be used with n_elms<={__find_element_max_size}. ' \
+            f'Got: n_elms={n_elms}.'
+
+    for i in range(n_elms):
+        if memory[array_ptr + elm_size * i] >= ids.key:
+            ids.index = i
+            break
+    else:
+        ids.index = n_elms
+%}
+*/
+pub fn search_sorted_lower(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let find_element_max_size = get_int_from_scope(vm, "find_element_max_size");
+    get_range_check_builtin(vm)?;
+
+    let array_ptr_addr = get_address_from_var_name("array_ptr", &ids, vm, hint_ap_tracking)?;
+    let index_addr = get_address_from_var_name("index", &ids, vm, hint_ap_tracking)?;
+    let elm_size = get_integer_from_var_name("elm_size", &ids, vm, hint_ap_tracking)?.clone();
+    let n_elms = get_integer_from_var_name("n_elms", &ids, vm, hint_ap_tracking)?.clone();
+    let key = get_integer_from_var_name("key", &ids, vm, hint_ap_tracking)?;
+
+    if !elm_size.is_positive() {
+        return Err(VirtualMachineError::ValueOutOfRange(elm_size));
+    }
+
+    if n_elms.is_negative() {
+        return Err(VirtualMachineError::ValueOutOfRange(n_elms));
+    }
+
+    if let Some(find_element_max_size) = find_element_max_size {
+        if n_elms > find_element_max_size {
+            return Err(VirtualMachineError::FindElemMaxSize(
+                find_element_max_size,
+                n_elms,
+            ));
+        }
+    }
+
+    let n_elms_iter: i32 = n_elms
+        .to_i32()
+        .ok_or_else(|| VirtualMachineError::OffsetExceeded(n_elms.clone()))?;
+
+    let mut array_iter = vm
+        .memory
+        .get(&array_ptr_addr)
+        .map_err(VirtualMachineError::MemoryError)?
+        .ok_or_else(|| VirtualMachineError::MemoryGet(array_ptr_addr.clone()))?
+        // This clone is needed in order to be able to use memory.get below
+        .clone();
+
+    let mut index = n_elms.clone();
+    for i in 0..n_elms_iter {
+        let value = match vm
+            .memory
+            .get(&array_iter)
+            .map_err(VirtualMachineError::MemoryError)?
+        {
+            Some(MaybeRelocatable::Int(value)) => value,
+            Some(_) => return Err(VirtualMachineError::ExpectedInteger(array_iter)),
+            None => return Err(VirtualMachineError::MemoryGet(array_iter)),
+        };
+        if value >= key {
+            index = bigint!(i);
+            break;
+        }
+        array_iter = array_iter.add_int_mod(&elm_size, &vm.prime)?;
+    }
+
+    vm.memory
+        .insert(&index_addr, &MaybeRelocatable::Int(index))
+        .map_err(VirtualMachineError::MemoryError)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::relocatable;
     use crate::types::instruction::Register;
+    use crate::types::relocatable::Relocatable;
     use crate::vm::hints::execute_hint::{execute_hint, HintReference};
     use crate::vm::runners::builtin_runner::OutputBuiltinRunner;
     use num_bigint::Sign;
 
     const FIND_ELEMENT_HINT: &[u8] = "array_ptr = ids.array_ptr\nelm_size = ids.elm_size\nassert isinstance(elm_size, int) and elm_size > 0, \\\n    f'Invalid value for elm_size. Got: {elm_size}.'\nkey = ids.key\n\nif '__find_element_index' in globals():\n    ids.index = __find_element_index\n    found_key = memory[array_ptr + elm_size * __find_element_index]\n    assert found_key == key, \\\n        f'Invalid index found in __find_element_index. index: {__find_element_index}, ' \\\n        f'expected key {key}, found key: {found_key}.'\n    # Delete __find_element_index to make sure it's not used for the next calls.\n    del __find_element_index\nelse:\n    n_elms = ids.n_elms\n    assert isinstance(n_elms, int) and n_elms >= 0, \\\n        f'Invalid value for n_elms. Got: {n_elms}.'\n    if '__find_element_max_size' in globals():\n        assert n_elms <= __find_element_max_size, \\\n            f'find_element() can only be used with n_elms<={__find_element_max_size}. ' \\\n            f'Got: n_elms={n_elms}.'\n\n    for i in range(n_elms):\n        if memory[array_ptr + elm_size * i] == key:\n            ids.index = i\n            break\n    else:\n        raise ValueError(f'Key {key} was not found.')".as_bytes();
 
+    const SEARCH_SORTED_LOWER_HINT: &[u8] = "array_ptr = ids.array_ptr\nelm_size = ids.elm_size\nassert isinstance(elm_size, int) and elm_size > 0, \\\n    f'Invalid value for elm_size. Got: {elm_size}.'\n\nn_elms = ids.n_elms\nassert isinstance(n_elms, int) and n_elms >= 0, \\\n    f'Invalid value for n_elms. Got: {n_elms}.'\nif '__find_element_max_size' in globals():\n    assert n_elms <= __find_element_max_size, \\\n        f'find_element() can only be used with n_elms<={__find_element_max_size}. ' \\\n        f'Got: n_elms={n_elms}.'\n\nfor i in range(n_elms):\n    if memory[array_ptr + elm_size * i] >= ids.key:\n        ids.index = i\n        break\nelse:\n    ids.index = n_elms".as_bytes();
+
     fn init_vm_ids(
         elm_size: Option<&MaybeRelocatable>,
         n_elms: Option<&MaybeRelocatable>,
@@ -271,51 +370,66 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 0,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -392,17 +506,23 @@ mod tests {
         vm.references.insert(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -7,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         );
 
         assert_eq!(
             execute_hint(&mut vm, FIND_ELEMENT_HINT, ids, &ApTracking::new()),
-            Err(VirtualMachineError::FailedToGetIds)
+            Err(VirtualMachineError::InvalidReferenceAddress(
+                relocatable!(0, 4),
+                -7
+            ))
         );
     }
 
@@ -537,4 +657,96 @@ mod tests {
             Err(VirtualMachineError::ExpectedInteger(relocatable.clone()))
         );
     }
+
+    #[test]
+    fn search_sorted_lower_found() {
+        let (mut vm, ids) = init_vm_ids(None, None, None, false);
+
+        assert_eq!(
+            execute_hint(&mut vm, SEARCH_SORTED_LOWER_HINT, ids, &ApTracking::new()),
+            Ok(())
+        );
+
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 3))),
+            Ok(Some(&MaybeRelocatable::Int(bigint!(1))))
+        )
+    }
+
+    #[test]
+    fn search_sorted_lower_lower_than_first() {
+        let (mut vm, ids) =
+            init_vm_ids(None, None, Some(&MaybeRelocatable::from(bigint!(0))), false);
+
+        assert_eq!(
+            execute_hint(&mut vm, SEARCH_SORTED_LOWER_HINT, ids, &ApTracking::new()),
+            Ok(())
+        );
+
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 3))),
+            Ok(Some(&MaybeRelocatable::Int(bigint!(0))))
+        )
+    }
+
+    #[test]
+    fn search_sorted_lower_not_found_returns_n_elms() {
+        let (mut vm, ids) =
+            init_vm_ids(None, None, Some(&MaybeRelocatable::from(bigint!(7))), false);
+
+        assert_eq!(
+            execute_hint(&mut vm, SEARCH_SORTED_LOWER_HINT, ids, &ApTracking::new()),
+            Ok(())
+        );
+
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 3))),
+            Ok(Some(&MaybeRelocatable::Int(bigint!(2))))
+        )
+    }
+
+    #[test]
+    fn search_sorted_lower_zero_elm_size() {
+        let (mut vm, ids) =
+            init_vm_ids(Some(&MaybeRelocatable::Int(bigint!(0))), None, None, false);
+
+        assert_eq!(
+            execute_hint(&mut vm, SEARCH_SORTED_LOWER_HINT, ids, &ApTracking::new()),
+            Err(VirtualMachineError::ValueOutOfRange(bigint!(0)))
+        );
+    }
+
+    #[test]
+    fn search_sorted_lower_negative_n_elms() {
+        let (mut vm, ids) =
+            init_vm_ids(None, Some(&MaybeRelocatable::Int(bigint!(-1))), None, false);
+
+        assert_eq!(
+            execute_hint(&mut vm, SEARCH_SORTED_LOWER_HINT, ids, &ApTracking::new()),
+            Err(VirtualMachineError::ValueOutOfRange(bigint!(-1)))
+        );
+    }
+
+    #[test]
+    fn search_sorted_lower_n_elms_gt_max_size() {
+        let (mut vm, ids) = init_vm_ids(None, None, None, false);
+        vm.exec_scopes
+            .assign_or_update_variable("find_element_max_size", PyValueType::BigInt(bigint!(1)));
+
+        assert_eq!(
+            execute_hint(&mut vm, SEARCH_SORTED_LOWER_HINT, ids, &ApTracking::new()),
+            Err(VirtualMachineError::FindElemMaxSize(bigint!(1), bigint!(2)))
+        );
+    }
+
+    #[test]
+    fn search_sorted_lower_range_check_not_present() {
+        let (mut vm, ids) = init_vm_ids(None, None, None, false);
+        _ = vm.builtin_runners.pop();
+
+        assert_eq!(
+            execute_hint(&mut vm, SEARCH_SORTED_LOWER_HINT, ids, &ApTracking::new()),
+            Err(VirtualMachineError::NoRangeCheckBuiltin)
+        );
+    }
 }
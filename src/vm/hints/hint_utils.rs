@@ -1,14 +1,16 @@
 use crate::bigint;
 use crate::math_utils::as_int;
 use crate::math_utils::isqrt;
+use crate::math_utils::{self, div_mod, sqrt_prime};
 use crate::serde::deserialize_program::ApTracking;
+use crate::serde::deserialize_utils::{ReferenceExpr, ReferenceParseError};
 use crate::types::exec_scope::PyValueType;
 use crate::types::relocatable::Relocatable;
 use crate::types::{instruction::Register, relocatable::MaybeRelocatable};
 use crate::vm::{
     context::run_context::RunContext, errors::vm_errors::VirtualMachineError,
     hints::execute_hint::HintReference, runners::builtin_runner::RangeCheckBuiltinRunner,
-    vm_core::VirtualMachine,
+    vm_core::VirtualMachine, vm_memory::memory_segments::CairoArg,
 };
 use num_bigint::BigInt;
 use num_integer::Integer;
@@ -16,26 +18,141 @@ use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
 use std::collections::HashMap;
 use std::ops::{Neg, Shl, Shr};
 
+//Returns the value of the program constant var_name as seen by the hint: the innermost accessible
+//scope defining it wins, as in the Python VM. Without accessible scopes, the constant is looked up
+//by the last component of its full name, failing if more than one constant matches
+pub fn get_constant_from_var_name<'a>(
+    var_name: &str,
+    constants: &'a HashMap<String, BigInt>,
+    accessible_scopes: &[String],
+) -> Result<&'a BigInt, VirtualMachineError> {
+    if !accessible_scopes.is_empty() {
+        return accessible_scopes
+            .iter()
+            .rev()
+            .find_map(|scope| constants.get(&format!("{}.{}", scope, var_name)))
+            .ok_or_else(|| VirtualMachineError::MissingConstant(var_name.to_string()));
+    }
+    let mut matches = constants
+        .iter()
+        .filter(|(full_name, _)| full_name.rsplit('.').next() == Some(var_name));
+    match (matches.next(), matches.next()) {
+        (Some((_, value)), None) => Ok(value),
+        (Some(_), Some(_)) => Err(VirtualMachineError::AmbiguousConstant(var_name.to_string())),
+        (None, _) => Err(VirtualMachineError::MissingConstant(var_name.to_string())),
+    }
+}
+
+//Fails if the program constant var_name doesn't have the value the hint relies on
+pub fn check_constant(
+    var_name: &str,
+    value: &BigInt,
+    expected: &BigInt,
+) -> Result<(), VirtualMachineError> {
+    if value != expected {
+        return Err(VirtualMachineError::UnexpectedConstantValue(
+            var_name.to_string(),
+            expected.clone(),
+            value.clone(),
+        ));
+    }
+    Ok(())
+}
+
 //Returns the value in the current execution scope that matches the name and is of type BigInt
 pub fn get_int_from_scope(vm: &mut VirtualMachine, name: &str) -> Option<BigInt> {
-    let mut val: Option<BigInt> = None;
-    if let Some(variables) = vm.exec_scopes.get_local_variables() {
-        if let Some(PyValueType::BigInt(py_val)) = variables.get(name) {
-            val = Some(py_val.clone());
-        }
-    }
-    val
+    vm.exec_scopes.get::<BigInt>(name).ok().cloned()
 }
 
 //Returns the value in the current execution scope that matches the name and is of type List
 pub fn get_list_from_scope(vm: &mut VirtualMachine, name: &str) -> Option<Vec<BigInt>> {
-    let mut val: Option<Vec<BigInt>> = None;
-    if let Some(variables) = vm.exec_scopes.get_local_variables() {
-        if let Some(PyValueType::List(py_val)) = variables.get(name) {
-            val = Some(py_val.clone());
+    vm.exec_scopes.get::<Vec<BigInt>>(name).ok().cloned()
+}
+
+//Returns the value of the program input at path, a dot separated list of object keys and list
+//indices (e.g. "transactions.0.amount"). An empty path returns the whole program input
+pub fn get_program_input_value<'a>(
+    vm: &'a VirtualMachine,
+    path: &str,
+) -> Result<&'a serde_json::Value, VirtualMachineError> {
+    let mut value = vm
+        .exec_scopes
+        .get_from_main_scope::<serde_json::Value>("program_input")?;
+    for key in path.split('.').filter(|key| !key.is_empty()) {
+        value = match value {
+            serde_json::Value::Array(values) => {
+                key.parse::<usize>().ok().and_then(|i| values.get(i))
+            }
+            value => value.get(key),
         }
+        .ok_or_else(|| VirtualMachineError::ProgramInputNotFound(path.to_string()))?;
+    }
+    Ok(value)
+}
+
+//Returns the program input at path as a CairoArg. Integers can be given as JSON numbers or as
+//decimal or 0x prefixed hexadecimal strings, and lists are converted recursively
+pub fn get_program_input_arg(
+    vm: &VirtualMachine,
+    path: &str,
+) -> Result<CairoArg, VirtualMachineError> {
+    json_to_cairo_arg(get_program_input_value(vm, path)?)
+        .ok_or_else(|| VirtualMachineError::InvalidProgramInput(path.to_string()))
+}
+
+//Returns the integer program input at path
+pub fn get_int_from_program_input(
+    vm: &VirtualMachine,
+    path: &str,
+) -> Result<BigInt, VirtualMachineError> {
+    match get_program_input_arg(vm, path)? {
+        CairoArg::Single(MaybeRelocatable::Int(value)) => Ok(value),
+        _ => Err(VirtualMachineError::InvalidProgramInput(path.to_string())),
     }
-    val
+}
+
+//Writes the program input at path into memory at ptr, reducing integers modulo the prime. Lists
+//are written into new segments, and ptr receives a pointer to them.
+//Returns the first address after the written value
+pub fn write_program_input(
+    vm: &mut VirtualMachine,
+    path: &str,
+    ptr: &MaybeRelocatable,
+) -> Result<MaybeRelocatable, VirtualMachineError> {
+    let arg = match get_program_input_arg(vm, path)? {
+        CairoArg::Single(value) => CairoArg::Single(value),
+        list => CairoArg::Array(vec![list]),
+    };
+    vm.segments
+        .write_arg(&mut vm.memory, ptr, &arg, Some(&vm.prime))
+        .map_err(VirtualMachineError::MemoryError)
+}
+
+//Numbers keep their original digits (serde_json's arbitrary_precision feature), so integers of
+//any size are parsed exactly, while floats such as 1.5 or 1e30 are rejected.
+//Strings can hold decimal or hex integers, e.g. "-5", "0x10" or "-0x10"
+fn json_to_cairo_arg(value: &serde_json::Value) -> Option<CairoArg> {
+    let int = match value {
+        serde_json::Value::Number(number) => number.to_string().parse::<BigInt>().ok()?,
+        serde_json::Value::String(string) => {
+            if let Some(hex) = string.strip_prefix("-0x") {
+                -BigInt::parse_bytes(hex.as_bytes(), 16)?
+            } else if let Some(hex) = string.strip_prefix("0x") {
+                BigInt::parse_bytes(hex.as_bytes(), 16)?
+            } else {
+                string.parse::<BigInt>().ok()?
+            }
+        }
+        serde_json::Value::Array(values) => {
+            return values
+                .iter()
+                .map(json_to_cairo_arg)
+                .collect::<Option<Vec<CairoArg>>>()
+                .map(CairoArg::Array)
+        }
+        _ => return None,
+    };
+    Some(CairoArg::Single(MaybeRelocatable::Int(int)))
 }
 //Returns a reference to the  RangeCheckBuiltinRunner struct if range_check builtin is present
 pub fn get_range_check_builtin(
@@ -53,11 +170,25 @@ pub fn get_range_check_builtin(
     Err(VirtualMachineError::NoRangeCheckBuiltin)
 }
 
+//Returns base + offset, failing if the resulting offset falls outside the segment
+fn apply_offset(base: &Relocatable, offset: i64) -> Result<Relocatable, VirtualMachineError> {
+    (base.offset as i64)
+        .checked_add(offset)
+        .and_then(|new_offset| new_offset.to_usize())
+        .map(|new_offset| Relocatable::from((base.segment_index, new_offset)))
+        .ok_or_else(|| VirtualMachineError::InvalidReferenceAddress(base.clone(), offset))
+}
+
+//Computes the value ap had when the reference was created, from the current ap.
+//As in cairo-lang's flow tracking, ap-based references can only be used within the
+//tracking group they were defined in, as ap changes by an unknown amount between groups.
+//When the compiler can carry a reference into a new group it re-binds it as a new reference,
+//which the hint's reference_ids already point to, so any other group mismatch is a revoked reference
 fn apply_ap_tracking_correction(
     ap: &Relocatable,
     ref_ap_tracking: &ApTracking,
     hint_ap_tracking: &ApTracking,
-) -> Result<MaybeRelocatable, VirtualMachineError> {
+) -> Result<Relocatable, VirtualMachineError> {
     // check that both groups are the same
     if ref_ap_tracking.group != hint_ap_tracking.group {
         return Err(VirtualMachineError::InvalidTrackingGroup(
@@ -65,12 +196,8 @@ fn apply_ap_tracking_correction(
             hint_ap_tracking.group,
         ));
     }
-    let ap_diff = hint_ap_tracking.offset - ref_ap_tracking.offset;
-
-    Ok(MaybeRelocatable::from((
-        ap.segment_index,
-        ap.offset - ap_diff,
-    )))
+    let ap_diff = hint_ap_tracking.offset as i64 - ref_ap_tracking.offset as i64;
+    apply_offset(ap, -ap_diff)
 }
 
 ///Computes the memory address indicated by the HintReference
@@ -79,62 +206,54 @@ pub fn compute_addr_from_reference(
     run_context: &RunContext,
     vm: &VirtualMachine,
     hint_ap_tracking: Option<&ApTracking>,
-) -> Result<Option<MaybeRelocatable>, VirtualMachineError> {
+) -> Result<MaybeRelocatable, VirtualMachineError> {
     let base_addr = match hint_reference.register {
-        Register::FP => run_context.fp.clone(),
-        Register::AP => {
-            if hint_ap_tracking.is_none() || hint_reference.ap_tracking_data.is_none() {
-                return Err(VirtualMachineError::NoneApTrackingData);
-            }
-
-            if let MaybeRelocatable::RelocatableValue(ref relocatable) = run_context.ap {
-                apply_ap_tracking_correction(
-                    relocatable,
-                    // it is safe to call these unrwaps here, since it has been checked
-                    // they are not None's
-                    // this could be refactored to use pattern match but it will be
-                    // unnecesarily verbose
-                    hint_reference.ap_tracking_data.as_ref().unwrap(),
-                    hint_ap_tracking.unwrap(),
-                )?
-            } else {
-                return Err(VirtualMachineError::InvalidApValue(run_context.ap.clone()));
+        Some(Register::FP) => match run_context.fp {
+            MaybeRelocatable::RelocatableValue(ref fp) => fp.clone(),
+            ref fp => return Err(VirtualMachineError::InvalidFpValue(fp.clone())),
+        },
+        Some(Register::AP) => {
+            let (ref_ap_tracking, hint_ap_tracking) =
+                match (hint_reference.ap_tracking_data.as_ref(), hint_ap_tracking) {
+                    (Some(ref_ap_tracking), Some(hint_ap_tracking)) => {
+                        (ref_ap_tracking, hint_ap_tracking)
+                    }
+                    _ => return Err(VirtualMachineError::NoneApTrackingData),
+                };
+            match run_context.ap {
+                MaybeRelocatable::RelocatableValue(ref ap) => {
+                    apply_ap_tracking_correction(ap, ref_ap_tracking, hint_ap_tracking)?
+                }
+                ref ap => return Err(VirtualMachineError::InvalidApValue(ap.clone())),
             }
         }
+        None => return Err(unresolvable_reference_error(hint_reference)),
     };
 
-    if let MaybeRelocatable::RelocatableValue(relocatable) = base_addr {
-        if hint_reference.offset1.is_negative()
-            && relocatable.offset < hint_reference.offset1.abs() as usize
-        {
-            return Ok(None);
-        }
-        if !hint_reference.inner_dereference {
-            return Ok(Some(MaybeRelocatable::from((
-                relocatable.segment_index,
-                (relocatable.offset as i32 + hint_reference.offset1 + hint_reference.offset2)
-                    as usize,
-            ))));
-        } else {
-            let addr = MaybeRelocatable::from((
-                relocatable.segment_index,
-                (relocatable.offset as i32 + hint_reference.offset1) as usize,
-            ));
-
-            match vm.memory.get(&addr) {
-                Ok(Some(&MaybeRelocatable::RelocatableValue(ref dereferenced_addr))) => {
-                    return Ok(Some(MaybeRelocatable::from((
-                        dereferenced_addr.segment_index,
-                        (dereferenced_addr.offset as i32 + hint_reference.offset2) as usize,
-                    ))))
-                }
-
-                _none_or_error => return Ok(None),
-            }
-        }
-    }
+    let addr = if !hint_reference.inner_dereference {
+        apply_offset(
+            &base_addr,
+            hint_reference.offset1 as i64 + hint_reference.offset2 as i64,
+        )?
+    } else {
+        //[reg + offset1] + offset2
+        let pointer_addr = apply_offset(&base_addr, hint_reference.offset1 as i64)?;
+        let dereferenced_addr = vm.memory.get_relocatable(&pointer_addr)?;
+        apply_offset(dereferenced_addr, hint_reference.offset2 as i64)?
+    };
+    Ok(MaybeRelocatable::RelocatableValue(addr))
+}
 
-    Ok(None)
+//Error for a reference without a register. Its expression either can't be reduced to a register
+//and offsets, or is an immediate, which has no address
+fn unresolvable_reference_error(hint_reference: &HintReference) -> VirtualMachineError {
+    let value = hint_reference.value.as_ref();
+    VirtualMachineError::ReferenceParseError(match value.map(ReferenceExpr::value_address) {
+        Some(Err(error)) => error,
+        _ => ReferenceParseError::UnsupportedExpression(
+            value.map(ToString::to_string).unwrap_or_default(),
+        ),
+    })
 }
 
 ///Computes the memory address given by the reference id
@@ -144,20 +263,12 @@ pub fn get_address_from_reference(
     run_context: &RunContext,
     vm: &VirtualMachine,
     hint_ap_tracking: Option<&ApTracking>,
-) -> Result<Option<MaybeRelocatable>, VirtualMachineError> {
-    if let Some(index) = reference_id.to_usize() {
-        if index < references.len() {
-            if let Some(hint_reference) = references.get(&index) {
-                return compute_addr_from_reference(
-                    hint_reference,
-                    run_context,
-                    vm,
-                    hint_ap_tracking,
-                );
-            }
-        }
-    }
-    Ok(None)
+) -> Result<MaybeRelocatable, VirtualMachineError> {
+    let hint_reference = reference_id
+        .to_usize()
+        .and_then(|index| references.get(&index))
+        .ok_or_else(|| VirtualMachineError::FailedToGetReference(reference_id.clone()))?;
+    compute_addr_from_reference(hint_reference, run_context, vm, hint_ap_tracking)
 }
 
 pub fn get_address_from_var_name(
@@ -176,8 +287,6 @@ pub fn get_address_from_var_name(
         vm,
         hint_ap_tracking,
     )
-    .map_err(|_| VirtualMachineError::FailedToGetIds)?
-    .ok_or(VirtualMachineError::FailedToGetIds)
 }
 
 //Gets the address of a variable name.
@@ -249,13 +358,8 @@ pub fn is_nn(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let a_addr = if let Ok(Some(a_addr)) =
-        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)
-    {
-        a_addr
-    } else {
-        return Err(VirtualMachineError::FailedToGetReference(a_ref.clone()));
-    };
+    let a_addr =
+        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
 
     //Check that the ids are in memory
     match vm.memory.get(&a_addr) {
@@ -315,13 +419,8 @@ pub fn is_nn_out_of_range(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let a_addr = if let Ok(Some(a_addr)) =
-        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)
-    {
-        a_addr
-    } else {
-        return Err(VirtualMachineError::FailedToGetReference(a_ref.clone()));
-    };
+    let a_addr =
+        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
     //Check that the ids are in memory
     match vm.memory.get(&a_addr) {
         Ok(Some(maybe_rel_a)) => {
@@ -397,25 +496,17 @@ pub fn assert_le_felt(
             ));
         };
     //Check that each reference id corresponds to a value in the reference manager
-    let (a_addr, b_addr, small_inputs_addr) = if let (
-        Ok(Some(a_addr)),
-        Ok(Some(b_addr)),
-        Ok(Some(small_inputs_addr)),
-    ) = (
-        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-        get_address_from_reference(b_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-        get_address_from_reference(
-            small_inputs_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-    ) {
-        (a_addr, b_addr, small_inputs_addr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
+    let a_addr =
+        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
+    let b_addr =
+        get_address_from_reference(b_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
+    let small_inputs_addr = get_address_from_reference(
+        small_inputs_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
     //Check that the ids are in memory (except for small_inputs which is local, and should contain None)
     //small_inputs needs to be None, as we cant change it value otherwise
     match (
@@ -489,14 +580,10 @@ pub fn is_le_felt(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let (a_addr, b_addr) = if let (Ok(Some(a_addr)), Ok(Some(b_addr))) = (
-        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-        get_address_from_reference(b_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-    ) {
-        (a_addr, b_addr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
+    let a_addr =
+        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
+    let b_addr =
+        get_address_from_reference(b_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
     match (vm.memory.get(&a_addr), vm.memory.get(&b_addr)) {
         (Ok(Some(maybe_rel_a)), Ok(Some(maybe_rel_b))) => {
             for (name, builtin) in &vm.builtin_runners {
@@ -563,14 +650,10 @@ pub fn assert_not_equal(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let (a_addr, b_addr) = if let (Ok(Some(a_addr)), Ok(Some(b_addr))) = (
-        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-        get_address_from_reference(b_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-    ) {
-        (a_addr, b_addr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
+    let a_addr =
+        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
+    let b_addr =
+        get_address_from_reference(b_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
     //Check that the ids are in memory
     match (vm.memory.get(&a_addr), vm.memory.get(&b_addr)) {
         (Ok(Some(maybe_rel_a)), Ok(Some(maybe_rel_b))) => match (maybe_rel_a, maybe_rel_b) {
@@ -625,13 +708,8 @@ pub fn assert_nn(
         ));
     };
     //Check that 'a' reference id corresponds to a value in the reference manager
-    let a_addr = if let Ok(Some(a_addr)) =
-        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)
-    {
-        a_addr
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
+    let a_addr =
+        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
 
     //Check that the 'a' id is in memory
     let maybe_rel_a = if let Ok(Some(maybe_rel_a)) = vm.memory.get(&a_addr) {
@@ -689,17 +767,13 @@ pub fn assert_not_zero(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let value_addr = if let Ok(Some(value_addr)) = get_address_from_reference(
+    let value_addr = get_address_from_reference(
         value_ref,
         &vm.references,
         &vm.run_context,
         vm,
         hint_ap_tracking,
-    ) {
-        value_addr
-    } else {
-        return Err(VirtualMachineError::FailedToGetReference(value_ref.clone()));
-    };
+    )?;
     match vm.memory.get(&value_addr) {
         Ok(Some(maybe_rel_value)) => {
             //Check that the value at the ids address is an Int
@@ -736,17 +810,13 @@ pub fn split_int_assert_range(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let value_addr = if let Ok(Some(value_addr)) = get_address_from_reference(
+    let value_addr = get_address_from_reference(
         value_ref,
         &vm.references,
         &vm.run_context,
         vm,
         hint_ap_tracking,
-    ) {
-        value_addr
-    } else {
-        return Err(VirtualMachineError::FailedToGetReference(value_ref.clone()));
-    };
+    )?;
     //Check that the ids are in memory
     match vm.memory.get(&value_addr) {
         Ok(Some(maybe_rel_value)) => {
@@ -796,45 +866,34 @@ pub fn split_int(
         };
     //Check that the ids are in memory (except for small_inputs which is local, and should contain None)
     //small_inputs needs to be None, as we cant change it value otherwise
-    let (output_addr, value_addr, base_addr, bound_addr) = if let (
-        Ok(Some(output_addr)),
-        Ok(Some(value_addr)),
-        Ok(Some(base_addr)),
-        Ok(Some(bound_addr)),
-    ) = (
-        get_address_from_reference(
-            output_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            value_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            base_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            bound_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-    ) {
-        (output_addr, value_addr, base_addr, bound_addr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
+    let output_addr = get_address_from_reference(
+        output_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let value_addr = get_address_from_reference(
+        value_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let base_addr = get_address_from_reference(
+        base_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let bound_addr = get_address_from_reference(
+        bound_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
     //Check that the ids are in memory
     let (mr_output, mr_value, mr_base, mr_bound) =
         if let (Ok(Some(mr_output)), Ok(Some(mr_value)), Ok(Some(mr_base)), Ok(Some(mr_bound))) = (
@@ -891,26 +950,20 @@ pub fn is_positive(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let (value_addr, is_positive_addr) = if let (Ok(Some(value_addr)), Ok(Some(is_positive_addr))) = (
-        get_address_from_reference(
-            value_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            is_positive_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-    ) {
-        (value_addr, is_positive_addr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
+    let value_addr = get_address_from_reference(
+        value_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let is_positive_addr = get_address_from_reference(
+        is_positive_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
 
     //Check that the ids are in memory
     match (vm.memory.get(&value_addr), vm.memory.get(&is_positive_addr)) {
@@ -965,6 +1018,35 @@ pub fn is_positive(
 //     ids.low = ids.value & ((1 << 128) - 1)
 //     ids.high = ids.value >> 128
 // %}
+pub fn split_felt_max_high(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let max_high = get_constant_from_var_name("MAX_HIGH", &vm.constants, &vm.accessible_scopes)?;
+    let max_low = get_constant_from_var_name("MAX_LOW", &vm.constants, &vm.accessible_scopes)?;
+    let bound = bigint!(1).shl(128_i32);
+    if !(max_high < &bound && max_low < &bound) {
+        return Err(VirtualMachineError::AssertionFailed(format!(
+            "assert {} < 2**128 and {} < 2**128",
+            max_high, max_low
+        )));
+    }
+    if &vm.prime - 1_i32 != max_high * &bound + max_low {
+        return Err(VirtualMachineError::AssertionFailed(format!(
+            "assert PRIME - 1 == {} * 2**128 + {}",
+            max_high, max_low
+        )));
+    }
+    split_felt(vm, ids, hint_ap_tracking)
+}
+
+// %{
+//     from starkware.cairo.common.math_utils import assert_integer
+//     assert_integer(ids.value)
+//     ids.low = ids.value & ((1 << 128) - 1)
+//     ids.high = ids.value >> 128
+// %}
 pub fn split_felt(
     vm: &mut VirtualMachine,
     ids: HashMap<String, BigInt>,
@@ -989,34 +1071,27 @@ pub fn split_felt(
     };
 
     // Get the addresses of the variables used in the hints
-    let (high_addr, low_addr, value_addr) =
-        if let (Ok(Some(high_addr)), Ok(Some(low_addr)), Ok(Some(value_addr))) = (
-            get_address_from_reference(
-                high_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-            get_address_from_reference(
-                low_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-            get_address_from_reference(
-                value_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-        ) {
-            (high_addr, low_addr, value_addr)
-        } else {
-            return Err(VirtualMachineError::FailedToGetIds);
-        };
+    let high_addr = get_address_from_reference(
+        high_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let low_addr = get_address_from_reference(
+        low_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let value_addr = get_address_from_reference(
+        value_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
 
     //Check that the 'value' variable is in memory
     match vm.memory.get(&value_addr) {
@@ -1065,26 +1140,20 @@ pub fn sqrt(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let (value_addr, root_addr) = if let (Ok(Some(value_addr)), Ok(Some(root_addr))) = (
-        get_address_from_reference(
-            value_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            root_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-    ) {
-        (value_addr, root_addr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
+    let value_addr = get_address_from_reference(
+        value_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let root_addr = get_address_from_reference(
+        root_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
     //Check that the ids are in memory
     match (vm.memory.get(&value_addr), vm.memory.get(&root_addr)) {
         (Ok(Some(maybe_rel_value)), Ok(_)) => {
@@ -1151,62 +1220,43 @@ pub fn signed_div_rem(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let (r_addr, biased_q_addr, range_check_ptr_addr, div_addr, value_addr, bound_addr) = if let (
-        Ok(Some(r_addr)),
-        Ok(Some(biased_q_addr)),
-        Ok(Some(range_check_ptr_addr)),
-        Ok(Some(div_addr)),
-        Ok(Some(value_addr)),
-        Ok(Some(bound_addr)),
-    ) = (
-        get_address_from_reference(r_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-        get_address_from_reference(
-            biased_q_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            range_check_ptr_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            div_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            value_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            bound_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-    ) {
-        (
-            r_addr,
-            biased_q_addr,
-            range_check_ptr_addr,
-            div_addr,
-            value_addr,
-            bound_addr,
-        )
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
+    let r_addr =
+        get_address_from_reference(r_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
+    let biased_q_addr = get_address_from_reference(
+        biased_q_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let range_check_ptr_addr = get_address_from_reference(
+        range_check_ptr_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let div_addr = get_address_from_reference(
+        div_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let value_addr = get_address_from_reference(
+        value_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let bound_addr = get_address_from_reference(
+        bound_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
     match (
         vm.memory.get(&r_addr),
         vm.memory.get(&biased_q_addr),
@@ -1335,41 +1385,32 @@ pub fn unsigned_div_rem(
             ));
         };
     //Check that each reference id corresponds to a value in the reference manager
-    let (r_addr, q_addr, div_addr, value_addr) = if let (
-        Ok(Some(r_addr)),
-        Ok(Some(q_addr)),
-        Ok(Some(div_addr)),
-        Ok(Some(value_addr)),
-    ) = (
-        get_address_from_reference(r_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-        get_address_from_reference(q_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-        get_address_from_reference(
-            div_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            value_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-    ) {
-        (r_addr, q_addr, div_addr, value_addr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
-    match (
-        vm.memory.get(&r_addr),
-        vm.memory.get(&q_addr),
-        vm.memory.get(&div_addr),
-        vm.memory.get(&value_addr),
-    ) {
-        (Ok(_), Ok(_), Ok(Some(maybe_rel_div)), Ok(Some(maybe_rel_value))) => {
-            let div = if let MaybeRelocatable::Int(ref div) = maybe_rel_div {
+    let r_addr =
+        get_address_from_reference(r_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
+    let q_addr =
+        get_address_from_reference(q_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
+    let div_addr = get_address_from_reference(
+        div_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let value_addr = get_address_from_reference(
+        value_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    match (
+        vm.memory.get(&r_addr),
+        vm.memory.get(&q_addr),
+        vm.memory.get(&div_addr),
+        vm.memory.get(&value_addr),
+    ) {
+        (Ok(_), Ok(_), Ok(Some(maybe_rel_div)), Ok(Some(maybe_rel_value))) => {
+            let div = if let MaybeRelocatable::Int(ref div) = maybe_rel_div {
                 div
             } else {
                 return Err(VirtualMachineError::ExpectedInteger(div_addr.clone()));
@@ -1417,8 +1458,13 @@ pub fn unsigned_div_rem(
 }
 
 //Implements hint: vm_enter_scope()
-pub fn enter_scope(vm: &mut VirtualMachine) -> Result<(), VirtualMachineError> {
-    vm.exec_scopes.enter_scope(HashMap::new());
+//Custom hints of the form vm_enter_scope({'name': value, ...}) can compute their initial locals
+//and enter the new scope through this function
+pub fn enter_scope(
+    vm: &mut VirtualMachine,
+    new_scope_locals: HashMap<String, PyValueType>,
+) -> Result<(), VirtualMachineError> {
+    vm.exec_scopes.enter_scope(new_scope_locals);
     Ok(())
 }
 
@@ -1516,9 +1562,12 @@ pub fn assert_250_bit(
     ids: HashMap<String, BigInt>,
     hint_ap_tracking: Option<&ApTracking>,
 ) -> Result<(), VirtualMachineError> {
-    //Declare constant values
-    let upper_bound = bigint!(1).shl(250_i32);
-    let shift = bigint!(1).shl(128_i32);
+    //Get constant values, the range checks following the hint rely on them being 2**250 and 2**128
+    let upper_bound =
+        get_constant_from_var_name("UPPER_BOUND", &vm.constants, &vm.accessible_scopes)?.clone();
+    let shift = get_constant_from_var_name("SHIFT", &vm.constants, &vm.accessible_scopes)?.clone();
+    check_constant("UPPER_BOUND", &upper_bound, &bigint!(1).shl(250_i32))?;
+    check_constant("SHIFT", &shift, &bigint!(1).shl(128_i32))?;
     //Check that ids contains the reference id for each variable used by the hint
     let (value_ref, high_ref, low_ref) = if let (Some(value_ref), Some(high_ref), Some(low_ref)) = (
         ids.get(&String::from("value")),
@@ -1537,34 +1586,27 @@ pub fn assert_250_bit(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let (value_addr, high_addr, low_addr) =
-        if let (Ok(Some(value_addr)), Ok(Some(high_addr)), Ok(Some(low_addr))) = (
-            get_address_from_reference(
-                value_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-            get_address_from_reference(
-                high_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-            get_address_from_reference(
-                low_ref,
-                &vm.references,
-                &vm.run_context,
-                vm,
-                hint_ap_tracking,
-            ),
-        ) {
-            (value_addr, high_addr, low_addr)
-        } else {
-            return Err(VirtualMachineError::FailedToGetIds);
-        };
+    let value_addr = get_address_from_reference(
+        value_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let high_addr = get_address_from_reference(
+        high_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let low_addr = get_address_from_reference(
+        low_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
     //Check that the ids.value is in memory
     match vm.memory.get(&value_addr) {
         Ok(Some(maybe_rel_value)) => {
@@ -1576,7 +1618,7 @@ pub fn assert_250_bit(
             };
             //Main logic
             let int_value = as_int(value, &vm.prime).mod_floor(&vm.prime);
-            if int_value > upper_bound {
+            if int_value >= upper_bound {
                 return Err(VirtualMachineError::ValueOutside250BitRange(int_value));
             }
 
@@ -1622,14 +1664,10 @@ pub fn assert_lt_felt(
         ));
     };
     //Check that each reference id corresponds to a value in the reference manager
-    let (a_addr, b_addr) = if let (Ok(Some(a_addr)), Ok(Some(b_addr))) = (
-        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-        get_address_from_reference(b_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking),
-    ) {
-        (a_addr, b_addr)
-    } else {
-        return Err(VirtualMachineError::FailedToGetIds);
-    };
+    let a_addr =
+        get_address_from_reference(a_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
+    let b_addr =
+        get_address_from_reference(b_ref, &vm.references, &vm.run_context, vm, hint_ap_tracking)?;
 
     match (vm.memory.get(&a_addr), vm.memory.get(&b_addr)) {
         (Ok(Some(MaybeRelocatable::Int(ref a))), Ok(Some(MaybeRelocatable::Int(ref b)))) => {
@@ -1655,9 +1693,224 @@ pub fn assert_lt_felt(
     }
 }
 
+/*
+Implements hint:
+%{
+    import itertools
+
+    from starkware.cairo.common.math_utils import assert_integer
+    assert_integer(ids.a)
+    assert_integer(ids.b)
+    a = ids.a % PRIME
+    b = ids.b % PRIME
+    assert a <= b, f'a = {a} is not less than or equal to b = {b}.'
+
+    # Find an arc less than PRIME / 3, and another less than PRIME / 2.
+    lengths_and_indices = [(a, 0), (b - a, 1), (PRIME - 1 - b, 2)]
+    lengths_and_indices.sort()
+    assert lengths_and_indices[0][0] <= PRIME // 3 and lengths_and_indices[1][0] <= PRIME // 2
+    excluded = lengths_and_indices[2][1]
+
+    memory[ids.range_check_ptr + 1], memory[ids.range_check_ptr + 0] = (
+        divmod(lengths_and_indices[0][0], ids.PRIME_OVER_3_HIGH))
+    memory[ids.range_check_ptr + 3], memory[ids.range_check_ptr + 2] = (
+        divmod(lengths_and_indices[1][0], ids.PRIME_OVER_2_HIGH))
+%}
+*/
+pub fn assert_le_felt_v_0_10(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    //Get constant values, which must be (PRIME // 3 >> 128) + 1 and (PRIME // 2 >> 128) + 1
+    let prime_over_3_high =
+        get_constant_from_var_name("PRIME_OVER_3_HIGH", &vm.constants, &vm.accessible_scopes)?
+            .clone();
+    let prime_over_2_high =
+        get_constant_from_var_name("PRIME_OVER_2_HIGH", &vm.constants, &vm.accessible_scopes)?
+            .clone();
+    check_constant(
+        "PRIME_OVER_3_HIGH",
+        &prime_over_3_high,
+        &((&vm.prime / 3_i32).shr(128_i32) + 1_i32),
+    )?;
+    check_constant(
+        "PRIME_OVER_2_HIGH",
+        &prime_over_2_high,
+        &((&vm.prime / 2_i32).shr(128_i32) + 1_i32),
+    )?;
+    let a = get_integer_from_var_name("a", &ids, vm, hint_ap_tracking)?.mod_floor(&vm.prime);
+    let b = get_integer_from_var_name("b", &ids, vm, hint_ap_tracking)?.mod_floor(&vm.prime);
+    let range_check_ptr_addr =
+        get_relocatable_from_var_name("range_check_ptr", &ids, vm, hint_ap_tracking)?;
+    let range_check_ptr = vm.memory.get_relocatable(&range_check_ptr_addr)?.clone();
+    if a > b {
+        return Err(VirtualMachineError::NonLeFelt(a, b));
+    }
+    //Find an arc less than PRIME / 3, and another less than PRIME / 2
+    let mut lengths_and_indices = [
+        (a.clone(), 0_i32),
+        (&b - &a, 1),
+        (&vm.prime - 1_i32 - &b, 2),
+    ];
+    lengths_and_indices.sort();
+    if lengths_and_indices[0].0 > vm.prime.div_floor(&bigint!(3))
+        || lengths_and_indices[1].0 > vm.prime.div_floor(&bigint!(2))
+    {
+        return Err(VirtualMachineError::AssertionFailed(format!(
+            "assert {} <= PRIME // 3 and {} <= PRIME // 2",
+            lengths_and_indices[0].0, lengths_and_indices[1].0
+        )));
+    }
+    let excluded = lengths_and_indices[2].1;
+    vm.exec_scopes
+        .assign_or_update_variable("excluded", PyValueType::BigInt(bigint!(excluded)));
+
+    let (q_0, r_0) = lengths_and_indices[0].0.div_mod_floor(&prime_over_3_high);
+    let (q_1, r_1) = lengths_and_indices[1].0.div_mod_floor(&prime_over_2_high);
+    for (offset, value) in [r_0, q_0, r_1, q_1].into_iter().enumerate() {
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((
+                    range_check_ptr.segment_index,
+                    range_check_ptr.offset + offset,
+                )),
+                &MaybeRelocatable::from(value),
+            )
+            .map_err(VirtualMachineError::MemoryError)?;
+    }
+    Ok(())
+}
+
+//Implements hints:
+//  memory[ap] = 1 if excluded != 0 else 0
+//  memory[ap] = 1 if excluded != 1 else 0
+pub fn assert_le_felt_excluded(
+    vm: &mut VirtualMachine,
+    excluded_value: i32,
+) -> Result<(), VirtualMachineError> {
+    let excluded = get_int_from_scope(vm, "excluded")
+        .ok_or_else(|| VirtualMachineError::VariableNotInScopeError(String::from("excluded")))?;
+    let value = if excluded != bigint!(excluded_value) {
+        bigint!(1)
+    } else {
+        bigint!(0)
+    };
+    vm.memory
+        .insert(&vm.run_context.ap, &MaybeRelocatable::from(value))
+        .map_err(VirtualMachineError::MemoryError)
+}
+
+//Implements hint: assert excluded == 2
+pub fn assert_le_felt_excluded_2(vm: &mut VirtualMachine) -> Result<(), VirtualMachineError> {
+    let excluded = get_int_from_scope(vm, "excluded")
+        .ok_or_else(|| VirtualMachineError::VariableNotInScopeError(String::from("excluded")))?;
+    if excluded != bigint!(2) {
+        return Err(VirtualMachineError::AssertionFailed(format!(
+            "assert {} == 2",
+            excluded
+        )));
+    }
+    Ok(())
+}
+
+/*
+Implements hint:
+%{
+    from starkware.crypto.signature.signature import FIELD_PRIME
+    from starkware.python.math_utils import div_mod, is_quad_residue, sqrt
+
+    x = ids.x
+    if is_quad_residue(x, FIELD_PRIME):
+        ids.y = sqrt(x, FIELD_PRIME)
+    else:
+        ids.y = sqrt(div_mod(x, 3, FIELD_PRIME), FIELD_PRIME)
+%}
+*/
+pub fn is_quad_residue(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let x = get_integer_from_var_name("x", &ids, vm, hint_ap_tracking)?.clone();
+    let y_addr = get_address_from_var_name("y", &ids, vm, hint_ap_tracking)?;
+    let value = if math_utils::is_quad_residue(&x, &vm.prime) {
+        x.clone()
+    } else {
+        div_mod(x.clone(), bigint!(3), vm.prime.clone())
+    };
+    let y = sqrt_prime(&value, &vm.prime).ok_or(VirtualMachineError::FailedToGetSqrt(x))?;
+    vm.memory
+        .insert(&y_addr, &MaybeRelocatable::from(y))
+        .map_err(VirtualMachineError::MemoryError)
+}
+
+/*
+Implements hint:
+%{
+    # Verify the assumptions on the relationship between 2**250, ADDR_BOUND and PRIME.
+    ADDR_BOUND = ids.ADDR_BOUND % PRIME
+    assert (2**250 < ADDR_BOUND <= 2**251) and (2 * 2**250 < PRIME) and (
+            ADDR_BOUND * 2 > PRIME), \
+        'normalize_address() cannot be used with the current constants.'
+    ids.is_small = 1 if ids.addr < ADDR_BOUND else 0
+%}
+*/
+pub fn is_addr_bounded(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    //Get constant values
+    let addr_bound =
+        get_constant_from_var_name("ADDR_BOUND", &vm.constants, &vm.accessible_scopes)?
+            .mod_floor(&vm.prime);
+    let addr = get_integer_from_var_name("addr", &ids, vm, hint_ap_tracking)?;
+    let is_small_addr = get_address_from_var_name("is_small", &ids, vm, hint_ap_tracking)?;
+    if !(bigint!(1).shl(250_i32) < addr_bound
+        && addr_bound <= bigint!(1).shl(251_i32)
+        && bigint!(2).shl(250_i32) < vm.prime
+        && &addr_bound * 2_i32 > vm.prime)
+    {
+        return Err(VirtualMachineError::AssertionFailed(String::from(
+            "normalize_address() cannot be used with the current constants.",
+        )));
+    }
+    let is_small = if addr < &addr_bound {
+        bigint!(1)
+    } else {
+        bigint!(0)
+    };
+    vm.memory
+        .insert(&is_small_addr, &MaybeRelocatable::from(is_small))
+        .map_err(VirtualMachineError::MemoryError)
+}
+
+//Implements hint:
+//  ids.a_lsb = ids.a & 1
+//  ids.b_lsb = ids.b & 1
+pub fn a_b_bitand_1(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let a_lsb = get_integer_from_var_name("a", &ids, vm, hint_ap_tracking)? & bigint!(1);
+    let b_lsb = get_integer_from_var_name("b", &ids, vm, hint_ap_tracking)? & bigint!(1);
+    let a_lsb_addr = get_address_from_var_name("a_lsb", &ids, vm, hint_ap_tracking)?;
+    let b_lsb_addr = get_address_from_var_name("b_lsb", &ids, vm, hint_ap_tracking)?;
+    vm.memory
+        .insert(&a_lsb_addr, &MaybeRelocatable::from(a_lsb))
+        .map_err(VirtualMachineError::MemoryError)?;
+    vm.memory
+        .insert(&b_lsb_addr, &MaybeRelocatable::from(b_lsb))
+        .map_err(VirtualMachineError::MemoryError)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bigint64;
+    use crate::serde::deserialize_utils::parse_reference_expr;
     use num_bigint::Sign;
 
     #[test]
@@ -1677,11 +1930,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -1722,11 +1978,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -1793,4 +2052,507 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn get_constant_from_var_name_valid() {
+        let constants = HashMap::from([
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_HIGH"),
+                bigint!(7),
+            ),
+            (
+                String::from("starkware.cairo.common.math.split_felt.MAX_LOW"),
+                bigint!(0),
+            ),
+        ]);
+        assert_eq!(
+            get_constant_from_var_name("MAX_HIGH", &constants, &[]),
+            Ok(&bigint!(7))
+        );
+    }
+
+    #[test]
+    fn get_constant_from_var_name_accessible_scopes() {
+        let constants = HashMap::from([
+            (String::from("__main__.SHIFT"), bigint!(1)),
+            (
+                String::from("starkware.cairo.common.math.assert_250_bit.SHIFT"),
+                bigint!(2),
+            ),
+            (
+                String::from("starkware.cairo.common.uint256.SHIFT"),
+                bigint!(3),
+            ),
+        ]);
+        let scopes = [
+            String::from("starkware.cairo.common.math"),
+            String::from("starkware.cairo.common.math.assert_250_bit"),
+        ];
+        assert_eq!(
+            get_constant_from_var_name("SHIFT", &constants, &scopes),
+            Ok(&bigint!(2))
+        );
+        // The innermost scope wins
+        let scopes = [
+            String::from("__main__"),
+            String::from("starkware.cairo.common.uint256"),
+        ];
+        assert_eq!(
+            get_constant_from_var_name("SHIFT", &constants, &scopes),
+            Ok(&bigint!(3))
+        );
+        assert_eq!(
+            get_constant_from_var_name("SHIFT", &constants, &scopes[..1]),
+            Ok(&bigint!(1))
+        );
+        assert_eq!(
+            get_constant_from_var_name(
+                "SHIFT",
+                &constants,
+                &[String::from("starkware.cairo.common.math")]
+            ),
+            Err(VirtualMachineError::MissingConstant(String::from("SHIFT")))
+        );
+    }
+
+    #[test]
+    fn get_constant_from_var_name_ambiguous() {
+        let constants = HashMap::from([
+            (String::from("__main__.SHIFT"), bigint!(1)),
+            (
+                String::from("starkware.cairo.common.uint256.SHIFT"),
+                bigint!(3),
+            ),
+        ]);
+        assert_eq!(
+            get_constant_from_var_name("SHIFT", &constants, &[]),
+            Err(VirtualMachineError::AmbiguousConstant(String::from(
+                "SHIFT"
+            )))
+        );
+    }
+
+    #[test]
+    fn get_constant_from_var_name_missing() {
+        let constants = HashMap::from([(
+            String::from("starkware.cairo.common.math.split_felt.MAX_HIGH_BOUND"),
+            bigint!(7),
+        )]);
+        assert_eq!(
+            get_constant_from_var_name("MAX_HIGH", &constants, &[]),
+            Err(VirtualMachineError::MissingConstant(String::from(
+                "MAX_HIGH"
+            )))
+        );
+    }
+
+    fn ap_reference(offset1: i32, offset2: i32, inner_dereference: bool) -> HintReference {
+        HintReference {
+            register: Some(Register::AP),
+            offset1,
+            offset2,
+            inner_dereference,
+            dereference: true,
+            cairo_type: None,
+            ap_tracking_data: Some(ApTracking {
+                group: 1,
+                offset: 2,
+            }),
+            value: None,
+        }
+    }
+
+    #[test]
+    fn compute_addr_from_reference_ap_tracking_correction() {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        vm.run_context.ap = MaybeRelocatable::from((1, 10));
+        //ap advanced by 3 since the reference was created
+        let hint_ap_tracking = ApTracking {
+            group: 1,
+            offset: 5,
+        };
+
+        assert_eq!(
+            compute_addr_from_reference(
+                &ap_reference(-1, 0, false),
+                &vm.run_context,
+                &vm,
+                Some(&hint_ap_tracking)
+            ),
+            Ok(MaybeRelocatable::from((1, 6)))
+        );
+    }
+
+    #[test]
+    fn compute_addr_from_reference_ap_inner_dereference() {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        vm.run_context.ap = MaybeRelocatable::from((1, 4));
+        //[ap + (-1)] = (2, 0), with ap at the time of the reference being (1, 4)
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 3)),
+                &MaybeRelocatable::from((2, 0)),
+            )
+            .unwrap();
+        let hint_ap_tracking = ApTracking {
+            group: 1,
+            offset: 2,
+        };
+
+        assert_eq!(
+            compute_addr_from_reference(
+                &ap_reference(-1, 2, true),
+                &vm.run_context,
+                &vm,
+                Some(&hint_ap_tracking)
+            ),
+            Ok(MaybeRelocatable::from((2, 2)))
+        );
+    }
+
+    #[test]
+    fn compute_addr_from_reference_inner_dereference_not_relocatable() {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        for _ in 0..2 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        vm.run_context.ap = MaybeRelocatable::from((1, 4));
+        let hint_ap_tracking = ApTracking {
+            group: 1,
+            offset: 2,
+        };
+
+        assert_eq!(
+            compute_addr_from_reference(
+                &ap_reference(-1, 2, true),
+                &vm.run_context,
+                &vm,
+                Some(&hint_ap_tracking)
+            ),
+            Err(VirtualMachineError::ExpectedRelocatable(
+                MaybeRelocatable::from((1, 3))
+            ))
+        );
+    }
+
+    #[test]
+    fn compute_addr_from_reference_different_tracking_group() {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        vm.run_context.ap = MaybeRelocatable::from((1, 10));
+        let hint_ap_tracking = ApTracking {
+            group: 2,
+            offset: 0,
+        };
+
+        assert_eq!(
+            compute_addr_from_reference(
+                &ap_reference(-1, 0, false),
+                &vm.run_context,
+                &vm,
+                Some(&hint_ap_tracking)
+            ),
+            Err(VirtualMachineError::InvalidTrackingGroup(1, 2))
+        );
+    }
+
+    #[test]
+    fn compute_addr_from_reference_fp_ignores_tracking_group() {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+        let reference = HintReference {
+            register: Some(Register::FP),
+            offset1: -3,
+            offset2: 0,
+            inner_dereference: false,
+            dereference: true,
+            cairo_type: None,
+            ap_tracking_data: None,
+            value: None,
+        };
+        let hint_ap_tracking = ApTracking {
+            group: 7,
+            offset: 0,
+        };
+
+        assert_eq!(
+            compute_addr_from_reference(&reference, &vm.run_context, &vm, Some(&hint_ap_tracking)),
+            Ok(MaybeRelocatable::from((1, 7)))
+        );
+    }
+
+    #[test]
+    fn get_address_from_var_name_rebound_reference() {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        vm.run_context.ap = MaybeRelocatable::from((1, 10));
+        //x was defined in group 1 and re-bound by the compiler as reference 1 in group 2
+        let mut rebound_reference = ap_reference(-2, 0, false);
+        rebound_reference.ap_tracking_data = Some(ApTracking {
+            group: 2,
+            offset: 1,
+        });
+        vm.references = HashMap::from([(0, ap_reference(-1, 0, false)), (1, rebound_reference)]);
+        let ids = HashMap::from([(String::from("x"), bigint!(1))]);
+        let hint_ap_tracking = ApTracking {
+            group: 2,
+            offset: 3,
+        };
+
+        assert_eq!(
+            get_address_from_var_name("x", &ids, &vm, Some(&hint_ap_tracking)),
+            Ok(MaybeRelocatable::from((1, 6)))
+        );
+    }
+
+    #[test]
+    fn compute_addr_from_reference_no_hint_ap_tracking() {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        vm.run_context.ap = MaybeRelocatable::from((1, 10));
+
+        assert_eq!(
+            compute_addr_from_reference(&ap_reference(-1, 0, false), &vm.run_context, &vm, None),
+            Err(VirtualMachineError::NoneApTrackingData)
+        );
+    }
+
+    #[test]
+    fn compute_addr_from_reference_offset_before_segment_start() {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        vm.run_context.ap = MaybeRelocatable::from((1, 1));
+        let hint_ap_tracking = ApTracking {
+            group: 1,
+            offset: 2,
+        };
+
+        assert_eq!(
+            compute_addr_from_reference(
+                &ap_reference(-2, 0, false),
+                &vm.run_context,
+                &vm,
+                Some(&hint_ap_tracking)
+            ),
+            Err(VirtualMachineError::InvalidReferenceAddress(
+                Relocatable::from((1, 1)),
+                -2
+            ))
+        );
+    }
+
+    #[test]
+    fn compute_addr_from_unsupported_reference() {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+        let reference = HintReference {
+            register: None,
+            offset1: 0,
+            offset2: 0,
+            inner_dereference: false,
+            dereference: false,
+            cairo_type: None,
+            ap_tracking_data: None,
+            value: Some(parse_reference_expr("[fp] * 2").unwrap()),
+        };
+
+        assert_eq!(
+            compute_addr_from_reference(&reference, &vm.run_context, &vm, None),
+            Err(VirtualMachineError::ReferenceParseError(
+                ReferenceParseError::UnsupportedExpression(String::from("[fp] * 2"))
+            ))
+        );
+    }
+
+    #[test]
+    fn compute_addr_from_reference_with_offset_out_of_range() {
+        let vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        let reference = HintReference {
+            register: None,
+            offset1: 0,
+            offset2: 0,
+            inner_dereference: false,
+            dereference: false,
+            cairo_type: None,
+            ap_tracking_data: None,
+            value: Some(parse_reference_expr("[cast(fp + 4294967296, felt*)]").unwrap()),
+        };
+
+        assert_eq!(
+            compute_addr_from_reference(&reference, &vm.run_context, &vm, None),
+            Err(VirtualMachineError::ReferenceParseError(
+                ReferenceParseError::OffsetOutOfRange(bigint64!(4294967296))
+            ))
+        );
+    }
+
+    #[test]
+    fn get_address_from_reference_missing_reference() {
+        let vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+
+        assert_eq!(
+            get_address_from_reference(&bigint!(3), &vm.references, &vm.run_context, &vm, None),
+            Err(VirtualMachineError::FailedToGetReference(bigint!(3)))
+        );
+    }
+
+    #[test]
+    fn enter_scope_with_typed_locals() {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        let new_scope_locals = HashMap::from([
+            (String::from("n"), PyValueType::BigInt(bigint!(3))),
+            (
+                String::from("ptr"),
+                PyValueType::new_any(Relocatable::from((1, 4))),
+            ),
+        ]);
+
+        assert_eq!(enter_scope(&mut vm, new_scope_locals), Ok(()));
+        assert_eq!(vm.exec_scopes.data.len(), 2);
+        assert_eq!(vm.exec_scopes.get::<BigInt>("n"), Ok(&bigint!(3)));
+        assert_eq!(
+            vm.exec_scopes.get::<Relocatable>("ptr"),
+            Ok(&Relocatable::from((1, 4)))
+        );
+    }
+
+    fn vm_with_program_input(program_input: serde_json::Value) -> VirtualMachine {
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        vm.exec_scopes
+            .assign_or_update_variable("program_input", PyValueType::new_any(program_input));
+        vm
+    }
+
+    #[test]
+    fn get_program_input_nested_values() {
+        let mut vm = vm_with_program_input(serde_json::json!({
+            "n": 3,
+            "txs": [{"amount": "0x10"}, {"amount": "-5"}, {"amount": "-0x10"}]
+        }));
+        // The program input is visible from inner scopes
+        vm.exec_scopes.enter_scope(HashMap::new());
+
+        assert_eq!(get_int_from_program_input(&vm, "n"), Ok(bigint!(3)));
+        assert_eq!(
+            get_int_from_program_input(&vm, "txs.0.amount"),
+            Ok(bigint!(16))
+        );
+        assert_eq!(
+            get_int_from_program_input(&vm, "txs.1.amount"),
+            Ok(bigint!(-5))
+        );
+        assert_eq!(
+            get_int_from_program_input(&vm, "txs.2.amount"),
+            Ok(bigint!(-16))
+        );
+        assert_eq!(
+            get_program_input_value(&vm, "txs.1"),
+            Ok(&serde_json::json!({"amount": "-5"}))
+        );
+    }
+
+    #[test]
+    fn get_program_input_big_numbers() {
+        let vm = vm_with_program_input(
+            serde_json::from_str(
+                r#"{"big": 3618502788666131213697322783095070105623107215331596699973092056135872020481, "negative": -18446744073709551616}"#,
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(
+            get_int_from_program_input(&vm, "big"),
+            Ok(BigInt::new(
+                Sign::Plus,
+                vec![1, 0, 0, 0, 0, 0, 17, 134217728]
+            ))
+        );
+        assert_eq!(
+            get_int_from_program_input(&vm, "negative"),
+            Ok(-(bigint!(1) << 64usize))
+        );
+    }
+
+    #[test]
+    fn get_program_input_invalid_paths() {
+        let vm = vm_with_program_input(
+            serde_json::json!({"txs": [{"amount": 1}], "name": "a", "float": 1e30}),
+        );
+
+        assert_eq!(
+            get_int_from_program_input(&vm, "txs.1.amount"),
+            Err(VirtualMachineError::ProgramInputNotFound(String::from(
+                "txs.1.amount"
+            )))
+        );
+        assert_eq!(
+            get_int_from_program_input(&vm, "txs"),
+            Err(VirtualMachineError::InvalidProgramInput(String::from(
+                "txs"
+            )))
+        );
+        assert_eq!(
+            get_int_from_program_input(&vm, "name"),
+            Err(VirtualMachineError::InvalidProgramInput(String::from(
+                "name"
+            )))
+        );
+        assert_eq!(
+            get_int_from_program_input(&vm, "float"),
+            Err(VirtualMachineError::InvalidProgramInput(String::from(
+                "float"
+            )))
+        );
+    }
+
+    #[test]
+    fn get_program_input_without_program_input() {
+        let vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        assert_eq!(
+            get_program_input_value(&vm, "n"),
+            Err(VirtualMachineError::VariableNotInScopeError(String::from(
+                "program_input"
+            )))
+        );
+    }
+
+    #[test]
+    fn write_program_input_list() {
+        let mut vm = vm_with_program_input(serde_json::json!({"values": [1, [2], -1]}));
+        vm.segments.add(&mut vm.memory, None);
+
+        assert_eq!(
+            write_program_input(&mut vm, "values", &MaybeRelocatable::from((0, 0))),
+            Ok(MaybeRelocatable::from((0, 1)))
+        );
+        // The list is written into a new segment, and its nested list into another one
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 0))),
+            Ok(Some(&MaybeRelocatable::from((1, 0))))
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 0))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(1))))
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 1))),
+            Ok(Some(&MaybeRelocatable::from((2, 0))))
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((2, 0))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(2))))
+        );
+        // Negative integers are reduced modulo the prime
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 2))),
+            Ok(Some(&MaybeRelocatable::from(vm.prime.clone() - 1)))
+        );
+    }
 }
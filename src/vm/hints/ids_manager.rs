@@ -0,0 +1,363 @@
+use crate::serde::deserialize_program::ApTracking;
+use crate::types::relocatable::{MaybeRelocatable, Relocatable};
+use crate::vm::{
+    errors::vm_errors::VirtualMachineError,
+    hints::{execute_hint::HintReference, hint_utils::compute_addr_from_reference},
+    vm_core::VirtualMachine,
+};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use std::collections::HashMap;
+
+///Resolves the `ids` variables available to a hint, including struct members (ids.a.b.c),
+///using the Cairo types of the references and the structs defined in the program
+pub struct IdsManager<'a> {
+    ids: &'a HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&'a ApTracking>,
+}
+
+impl<'a> IdsManager<'a> {
+    pub fn new(
+        ids: &'a HashMap<String, BigInt>,
+        hint_ap_tracking: Option<&'a ApTracking>,
+    ) -> IdsManager<'a> {
+        IdsManager {
+            ids,
+            hint_ap_tracking,
+        }
+    }
+
+    fn get_reference<'b>(
+        &self,
+        name: &str,
+        vm: &'b VirtualMachine,
+    ) -> Result<&'b HintReference, VirtualMachineError> {
+        self.ids
+            .get(name)
+            .and_then(|reference_id| reference_id.to_usize())
+            .and_then(|reference_id| vm.references.get(&reference_id))
+            .ok_or(VirtualMachineError::FailedToGetIds)
+    }
+
+    ///Computes the address of the value named by path (ie: "a", "a.b.c")
+    pub fn get_address(
+        &self,
+        path: &str,
+        vm: &VirtualMachine,
+    ) -> Result<Relocatable, VirtualMachineError> {
+        let mut components = path.split('.');
+        //split always yields at least one component
+        let name = components.next().unwrap_or_default();
+        let reference = self.get_reference(name, vm)?;
+        let mut address = match compute_addr_from_reference(
+            reference,
+            &vm.run_context,
+            vm,
+            self.hint_ap_tracking,
+        )? {
+            MaybeRelocatable::RelocatableValue(address) => address,
+            address => return Err(VirtualMachineError::ExpectedRelocatable(address)),
+        };
+        let mut cairo_type = reference.cairo_type.clone();
+        //References that are not dereferenced hold the address itself as their value
+        let mut in_memory = reference.dereference;
+
+        for member_name in components {
+            let cairo_type_name = cairo_type
+                .ok_or_else(|| VirtualMachineError::MissingReferenceType(String::from(name)))?;
+            //Members of a pointer to a struct are accessed through the pointer
+            let (struct_name, struct_address) = match cairo_type_name.strip_suffix('*') {
+                Some(struct_name) if in_memory => {
+                    (struct_name, vm.memory.get_relocatable(&address)?.clone())
+                }
+                Some(struct_name) => (struct_name, address),
+                None => (cairo_type_name.as_str(), address),
+            };
+            let member = vm
+                .struct_types
+                .get(struct_name)
+                .ok_or_else(|| VirtualMachineError::UnknownStruct(String::from(struct_name)))?
+                .get(member_name)
+                .ok_or_else(|| {
+                    VirtualMachineError::UnknownStructMember(
+                        String::from(struct_name),
+                        String::from(member_name),
+                    )
+                })?;
+            address = Relocatable::from((
+                struct_address.segment_index,
+                struct_address.offset + member.offset,
+            ));
+            cairo_type = Some(member.cairo_type.clone());
+            in_memory = true;
+        }
+        Ok(address)
+    }
+
+    ///Gets the integer value named by path
+    pub fn get_integer<'b>(
+        &self,
+        path: &str,
+        vm: &'b VirtualMachine,
+    ) -> Result<&'b BigInt, VirtualMachineError> {
+        vm.memory.get_integer(&self.get_address(path, vm)?)
+    }
+
+    ///Gets the relocatable value named by path
+    pub fn get_relocatable<'b>(
+        &self,
+        path: &str,
+        vm: &'b VirtualMachine,
+    ) -> Result<&'b Relocatable, VirtualMachineError> {
+        vm.memory.get_relocatable(&self.get_address(path, vm)?)
+    }
+
+    ///Writes value into the memory cell named by path
+    pub fn insert_value(
+        &self,
+        path: &str,
+        value: &MaybeRelocatable,
+        vm: &mut VirtualMachine,
+    ) -> Result<(), VirtualMachineError> {
+        let address = MaybeRelocatable::RelocatableValue(self.get_address(path, vm)?);
+        vm.memory
+            .insert(&address, value)
+            .map_err(VirtualMachineError::MemoryError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint;
+    use crate::serde::deserialize_program::Member;
+    use crate::types::instruction::Register;
+    use num_traits::FromPrimitive;
+
+    fn reference(offset1: i32, dereference: bool, cairo_type: &str) -> HintReference {
+        HintReference {
+            register: Some(Register::FP),
+            offset1,
+            offset2: 0,
+            inner_dereference: false,
+            ap_tracking_data: None,
+            dereference,
+            cairo_type: Some(String::from(cairo_type)),
+            value: None,
+        }
+    }
+
+    //Builds a vm with a Point {x, y} struct and a Segment {start: Point, end: Point*} struct
+    fn vm_with_structs() -> VirtualMachine {
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        vm.run_context.fp = MaybeRelocatable::from((1, 5));
+        vm.struct_types = HashMap::from([
+            (
+                String::from("__main__.Point"),
+                HashMap::from([
+                    (
+                        String::from("x"),
+                        Member {
+                            cairo_type: String::from("felt"),
+                            offset: 0,
+                        },
+                    ),
+                    (
+                        String::from("y"),
+                        Member {
+                            cairo_type: String::from("felt"),
+                            offset: 1,
+                        },
+                    ),
+                ]),
+            ),
+            (
+                String::from("__main__.Segment"),
+                HashMap::from([
+                    (
+                        String::from("start"),
+                        Member {
+                            cairo_type: String::from("__main__.Point"),
+                            offset: 0,
+                        },
+                    ),
+                    (
+                        String::from("end"),
+                        Member {
+                            cairo_type: String::from("__main__.Point*"),
+                            offset: 2,
+                        },
+                    ),
+                ]),
+            ),
+        ]);
+        vm
+    }
+
+    #[test]
+    fn get_address_of_plain_variable() {
+        let mut vm = vm_with_structs();
+        vm.references = HashMap::from([(0, reference(-2, true, "felt"))]);
+        let ids = HashMap::from([(String::from("a"), bigint!(0))]);
+        let ids_manager = IdsManager::new(&ids, None);
+
+        assert_eq!(
+            ids_manager.get_address("a", &vm),
+            Ok(Relocatable::from((1, 3)))
+        );
+    }
+
+    #[test]
+    fn get_integer_of_struct_member() {
+        let mut vm = vm_with_structs();
+        vm.references = HashMap::from([(0, reference(-3, true, "__main__.Point"))]);
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 3)),
+                &MaybeRelocatable::from(bigint!(7)),
+            )
+            .unwrap();
+        let ids = HashMap::from([(String::from("p"), bigint!(0))]);
+        let ids_manager = IdsManager::new(&ids, None);
+
+        assert_eq!(ids_manager.get_integer("p.y", &vm), Ok(&bigint!(7)));
+    }
+
+    #[test]
+    fn get_address_of_member_through_pointer() {
+        let mut vm = vm_with_structs();
+        vm.references = HashMap::from([(0, reference(-1, true, "__main__.Point*"))]);
+        //ids.p holds a pointer to the Point at (2, 4)
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 4)),
+                &MaybeRelocatable::from((2, 4)),
+            )
+            .unwrap();
+        let ids = HashMap::from([(String::from("p"), bigint!(0))]);
+        let ids_manager = IdsManager::new(&ids, None);
+
+        assert_eq!(
+            ids_manager.get_address("p.y", &vm),
+            Ok(Relocatable::from((2, 5)))
+        );
+    }
+
+    #[test]
+    fn get_address_of_member_through_non_dereferenced_pointer() {
+        let mut vm = vm_with_structs();
+        //ids.p = cast(fp + 1, Point*)
+        vm.references = HashMap::from([(0, reference(1, false, "__main__.Point*"))]);
+        let ids = HashMap::from([(String::from("p"), bigint!(0))]);
+        let ids_manager = IdsManager::new(&ids, None);
+
+        assert_eq!(
+            ids_manager.get_address("p.y", &vm),
+            Ok(Relocatable::from((1, 7)))
+        );
+    }
+
+    #[test]
+    fn get_address_of_nested_members() {
+        let mut vm = vm_with_structs();
+        vm.references = HashMap::from([(0, reference(-5, true, "__main__.Segment"))]);
+        //ids.s.end points to (2, 0)
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 2)),
+                &MaybeRelocatable::from((2, 0)),
+            )
+            .unwrap();
+        let ids = HashMap::from([(String::from("s"), bigint!(0))]);
+        let ids_manager = IdsManager::new(&ids, None);
+
+        assert_eq!(
+            ids_manager.get_address("s.start.y", &vm),
+            Ok(Relocatable::from((1, 1)))
+        );
+        assert_eq!(
+            ids_manager.get_address("s.end.y", &vm),
+            Ok(Relocatable::from((2, 1)))
+        );
+        assert_eq!(
+            ids_manager.get_relocatable("s.end", &vm),
+            Ok(&Relocatable::from((2, 0)))
+        );
+    }
+
+    #[test]
+    fn insert_value_into_struct_member() {
+        let mut vm = vm_with_structs();
+        vm.references = HashMap::from([(0, reference(-3, true, "__main__.Point"))]);
+        let ids = HashMap::from([(String::from("p"), bigint!(0))]);
+        let ids_manager = IdsManager::new(&ids, None);
+
+        assert_eq!(
+            ids_manager.insert_value("p.x", &MaybeRelocatable::from(bigint!(3)), &mut vm),
+            Ok(())
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 2))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(3))))
+        );
+    }
+
+    #[test]
+    fn get_address_missing_ids() {
+        let vm = vm_with_structs();
+        let ids = HashMap::new();
+        let ids_manager = IdsManager::new(&ids, None);
+
+        assert_eq!(
+            ids_manager.get_address("a", &vm),
+            Err(VirtualMachineError::FailedToGetIds)
+        );
+    }
+
+    #[test]
+    fn get_address_member_of_untyped_reference() {
+        let mut vm = vm_with_structs();
+        let mut untyped_reference = reference(-3, true, "felt");
+        untyped_reference.cairo_type = None;
+        vm.references = HashMap::from([(0, untyped_reference)]);
+        let ids = HashMap::from([(String::from("p"), bigint!(0))]);
+        let ids_manager = IdsManager::new(&ids, None);
+
+        assert_eq!(
+            ids_manager.get_address("p.x", &vm),
+            Err(VirtualMachineError::MissingReferenceType(String::from("p")))
+        );
+    }
+
+    #[test]
+    fn get_address_member_of_felt() {
+        let mut vm = vm_with_structs();
+        vm.references = HashMap::from([(0, reference(-3, true, "felt"))]);
+        let ids = HashMap::from([(String::from("a"), bigint!(0))]);
+        let ids_manager = IdsManager::new(&ids, None);
+
+        assert_eq!(
+            ids_manager.get_address("a.x", &vm),
+            Err(VirtualMachineError::UnknownStruct(String::from("felt")))
+        );
+    }
+
+    #[test]
+    fn get_address_unknown_member() {
+        let mut vm = vm_with_structs();
+        vm.references = HashMap::from([(0, reference(-3, true, "__main__.Point"))]);
+        let ids = HashMap::from([(String::from("p"), bigint!(0))]);
+        let ids_manager = IdsManager::new(&ids, None);
+
+        assert_eq!(
+            ids_manager.get_address("p.z", &vm),
+            Err(VirtualMachineError::UnknownStructMember(
+                String::from("__main__.Point"),
+                String::from("z")
+            ))
+        );
+    }
+}
@@ -127,11 +127,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -169,11 +172,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -220,11 +226,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -272,11 +281,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -323,11 +335,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -374,11 +389,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -2,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
 
@@ -3,8 +3,10 @@ pub mod dict_manager;
 pub mod execute_hint;
 pub mod find_element_hint;
 pub mod hint_utils;
+pub mod ids_manager;
 pub mod memset_utils;
 pub mod pow_utils;
 pub mod set;
 pub mod squash_dict_utils;
 pub mod uint256_utils;
+pub mod usort;
@@ -32,25 +32,25 @@ pub fn pow(
     };
 
     // Get the addresses of the variables used in the hints
+    let prev_locs_addr = get_address_from_reference(
+        prev_locs_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
+    let locs_addr = get_address_from_reference(
+        locs_ref,
+        &vm.references,
+        &vm.run_context,
+        vm,
+        hint_ap_tracking,
+    )?;
     let (prev_locs_addr, locs_addr) = if let (
-        Ok(Some(MaybeRelocatable::RelocatableValue(prev_locs_addr))),
-        Ok(Some(MaybeRelocatable::RelocatableValue(locs_addr))),
-    ) = (
-        get_address_from_reference(
-            prev_locs_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-        get_address_from_reference(
-            locs_ref,
-            &vm.references,
-            &vm.run_context,
-            vm,
-            hint_ap_tracking,
-        ),
-    ) {
+        MaybeRelocatable::RelocatableValue(prev_locs_addr),
+        MaybeRelocatable::RelocatableValue(locs_addr),
+    ) = (prev_locs_addr, locs_addr)
+    {
         (prev_locs_addr, locs_addr)
     } else {
         return Err(VirtualMachineError::FailedToGetIds);
@@ -81,9 +81,10 @@ mod tests {
 
     use crate::types::instruction::Register;
     use crate::types::relocatable::MaybeRelocatable;
+    use crate::types::relocatable::Relocatable;
     use crate::vm::errors::memory_errors::MemoryError;
     use crate::vm::hints::execute_hint::{execute_hint, HintReference};
-    use crate::{bigint, vm::runners::builtin_runner::RangeCheckBuiltinRunner};
+    use crate::{bigint, relocatable, vm::runners::builtin_runner::RangeCheckBuiltinRunner};
     use num_bigint::{BigInt, Sign};
     use num_traits::FromPrimitive;
 
@@ -117,27 +118,33 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::AP,
+                    register: Some(Register::AP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: Some(ApTracking {
                         group: 4,
                         offset: 3,
                     }),
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::AP,
+                    register: Some(Register::AP),
                     offset1: 0,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: Some(ApTracking {
                         group: 4,
                         offset: 3,
                     }),
+                    value: None,
                 },
             ),
         ]);
@@ -227,22 +234,28 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::AP,
+                    register: Some(Register::AP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: Some(ApTracking::new()),
+                    value: None,
                 },
             ),
             // Incorrect reference, offset1 out of range
             (
                 1,
                 HintReference {
-                    register: Register::AP,
+                    register: Some(Register::AP),
                     offset1: -12,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: Some(ApTracking::new()),
+                    value: None,
                 },
             ),
         ]);
@@ -252,7 +265,10 @@ mod tests {
         //Execute the hint
         assert_eq!(
             execute_hint(&mut vm, hint_code, ids, &ap_tracking),
-            Err(VirtualMachineError::FailedToGetIds)
+            Err(VirtualMachineError::InvalidReferenceAddress(
+                relocatable!(1, 11),
+                -12
+            ))
         );
     }
 
@@ -284,21 +300,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::AP,
+                    register: Some(Register::AP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: Some(ApTracking::new()),
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::AP,
+                    register: Some(Register::AP),
                     offset1: 0,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: Some(ApTracking::new()),
+                    value: None,
                 },
             ),
         ]);
@@ -350,21 +372,27 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::AP,
+                    register: Some(Register::AP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: Some(ApTracking::new()),
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::AP,
+                    register: Some(Register::AP),
                     offset1: 0,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: Some(ApTracking::new()),
+                    value: None,
                 },
             ),
         ]);
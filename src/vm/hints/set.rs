@@ -114,7 +114,9 @@ pub fn set_add(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::relocatable;
     use crate::types::instruction::Register;
+    use crate::types::relocatable::Relocatable;
     use crate::vm::{
         hints::execute_hint::{execute_hint, HintReference},
         runners::builtin_runner::OutputBuiltinRunner,
@@ -214,61 +216,79 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 5,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 0,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -385,17 +405,23 @@ mod tests {
         vm.references.insert(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -7,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         );
 
         assert_eq!(
             execute_hint(&mut vm, HINT_CODE, ids, &ApTracking::new()),
-            Err(VirtualMachineError::FailedToGetIds)
+            Err(VirtualMachineError::InvalidReferenceAddress(
+                relocatable!(0, 5),
+                -7
+            ))
         );
     }
 
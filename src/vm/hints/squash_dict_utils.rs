@@ -466,11 +466,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -533,11 +536,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -576,11 +582,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -620,11 +629,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -667,11 +679,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -716,11 +731,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -785,11 +803,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -827,11 +848,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -874,11 +898,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -978,11 +1005,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1030,11 +1060,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1085,11 +1118,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1185,11 +1221,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1236,11 +1275,14 @@ mod tests {
         vm.references = HashMap::from([(
             0,
             HintReference {
-                register: Register::FP,
+                register: Some(Register::FP),
                 offset1: -1,
                 offset2: 0,
                 inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
                 ap_tracking_data: None,
+                value: None,
             },
         )]);
         //Execute the hint
@@ -1347,51 +1389,66 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1564,51 +1621,66 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1745,51 +1817,66 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1923,51 +2010,66 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2078,51 +2180,66 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2232,51 +2349,66 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -2393,51 +2525,66 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -5,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 4,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -1,15 +1,20 @@
+use crate::math_utils::isqrt;
 use crate::serde::deserialize_program::ApTracking;
-use crate::types::relocatable::MaybeRelocatable;
+use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 use crate::vm::errors::vm_errors::VirtualMachineError;
 use crate::vm::hints::hint_utils::{
-    get_address_from_var_name, get_integer_from_relocatable_plus_offset, get_integer_from_var_name,
+    check_constant, get_address_from_var_name, get_constant_from_var_name,
+    get_integer_from_relocatable_plus_offset, get_integer_from_var_name,
     get_relocatable_from_var_name,
 };
+use crate::vm::hints::ids_manager::IdsManager;
 use crate::vm::vm_core::VirtualMachine;
 use crate::{bigint, bigint_u64};
 use num_bigint::BigInt;
-use num_traits::FromPrimitive;
+use num_integer::Integer;
+use num_traits::{FromPrimitive, Zero};
 use std::collections::HashMap;
+use std::ops::{Shl, Shr};
 
 /*
 Implements hint:
@@ -25,17 +30,15 @@ pub fn uint256_add(
     ids: HashMap<String, BigInt>,
     hint_ap_tracking: Option<&ApTracking>,
 ) -> Result<(), VirtualMachineError> {
-    let shift: BigInt = bigint!(2).pow(128);
+    //Uint256 limbs are 128 bits long, so SHIFT must be 2**128
+    let shift = get_constant_from_var_name("SHIFT", &vm.constants, &vm.accessible_scopes)?.clone();
+    check_constant("SHIFT", &shift, &bigint!(1).shl(128_usize))?;
 
-    let a_relocatable = get_relocatable_from_var_name("a", &ids, vm, hint_ap_tracking)?;
-    let b_relocatable = get_relocatable_from_var_name("b", &ids, vm, hint_ap_tracking)?;
-    let carry_high_addr = get_address_from_var_name("carry_high", &ids, vm, hint_ap_tracking)?;
-    let carry_low_addr = get_address_from_var_name("carry_low", &ids, vm, hint_ap_tracking)?;
-
-    let a_low = get_integer_from_relocatable_plus_offset(&a_relocatable, 0, vm)?;
-    let a_high = get_integer_from_relocatable_plus_offset(&a_relocatable, 1, vm)?;
-    let b_low = get_integer_from_relocatable_plus_offset(&b_relocatable, 0, vm)?;
-    let b_high = get_integer_from_relocatable_plus_offset(&b_relocatable, 1, vm)?;
+    let ids_manager = IdsManager::new(&ids, hint_ap_tracking);
+    let a_low = ids_manager.get_integer("a.low", vm)?;
+    let a_high = ids_manager.get_integer("a.high", vm)?;
+    let b_low = ids_manager.get_integer("b.low", vm)?;
+    let b_high = ids_manager.get_integer("b.high", vm)?;
 
     // Hint main logic
     // sum_low = ids.a.low + ids.b.low
@@ -55,13 +58,8 @@ pub fn uint256_add(
         bigint!(0)
     };
 
-    vm.memory
-        .insert(&carry_high_addr, &MaybeRelocatable::from(carry_high))
-        .map_err(VirtualMachineError::MemoryError)?;
-
-    vm.memory
-        .insert(&carry_low_addr, &MaybeRelocatable::from(carry_low))
-        .map_err(VirtualMachineError::MemoryError)
+    ids_manager.insert_value("carry_high", &MaybeRelocatable::from(carry_high), vm)?;
+    ids_manager.insert_value("carry_low", &MaybeRelocatable::from(carry_low), vm)
 }
 
 /*
@@ -92,10 +90,250 @@ pub fn split_64(
         .map_err(VirtualMachineError::MemoryError)
 }
 
+/*
+Implements hint:
+%{
+    from starkware.python.math_utils import isqrt
+    n = (ids.n.high << 128) + ids.n.low
+    root = isqrt(n)
+    assert 0 <= root < 2 ** 128
+    ids.root.low = root
+    ids.root.high = 0
+%}
+*/
+pub fn uint256_sqrt(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let n_addr = get_relocatable_from_var_name("n", &ids, vm, hint_ap_tracking)?;
+    let root_addr = get_relocatable_from_var_name("root", &ids, vm, hint_ap_tracking)?;
+
+    let n_low = get_integer_from_relocatable_plus_offset(&n_addr, 0, vm)?;
+    let n_high = get_integer_from_relocatable_plus_offset(&n_addr, 1, vm)?;
+
+    //Main logic
+    //from starkware.python.math_utils import isqrt
+    //n = (ids.n.high << 128) + ids.n.low
+    //root = isqrt(n)
+    //assert 0 <= root < 2 ** 128
+    //ids.root.low = root
+    //ids.root.high = 0
+
+    let root = isqrt(&(n_high.shl(128_usize) + n_low))?;
+
+    if root >= bigint!(1).shl(128_usize) {
+        return Err(VirtualMachineError::AssertionFailed(format!(
+            "assert 0 <= {} < 2 ** 128",
+            root
+        )));
+    }
+
+    vm.memory
+        .insert(
+            &MaybeRelocatable::from((root_addr.segment_index, root_addr.offset)),
+            &MaybeRelocatable::from(root),
+        )
+        .map_err(VirtualMachineError::MemoryError)?;
+    vm.memory
+        .insert(
+            &MaybeRelocatable::from((root_addr.segment_index, root_addr.offset + 1)),
+            &MaybeRelocatable::from(bigint!(0)),
+        )
+        .map_err(VirtualMachineError::MemoryError)
+}
+
+/*
+Implements hint:
+%{ memory[ap] = 1 if 0 <= (ids.a.high % PRIME) < 2 ** 127 else 0 %}
+*/
+pub fn uint256_signed_nn(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let a_addr = get_relocatable_from_var_name("a", &ids, vm, hint_ap_tracking)?;
+    let a_high = get_integer_from_relocatable_plus_offset(&a_addr, 1, vm)?;
+    //Main logic
+    //memory[ap] = 1 if 0 <= (ids.a.high % PRIME) < 2 ** 127 else 0
+    let result = if a_high.mod_floor(&vm.prime) < bigint!(1).shl(127_usize) {
+        bigint!(1)
+    } else {
+        bigint!(0)
+    };
+    vm.memory
+        .insert(&vm.run_context.ap, &MaybeRelocatable::from(result))
+        .map_err(VirtualMachineError::MemoryError)
+}
+
+/*
+Implements hint:
+%{
+    a = (ids.a.high << 128) + ids.a.low
+    div = (ids.div.high << 128) + ids.div.low
+    quotient, remainder = divmod(a, div)
+
+    ids.quotient.low = quotient & ((1 << 128) - 1)
+    ids.quotient.high = quotient >> 128
+    ids.remainder.low = remainder & ((1 << 128) - 1)
+    ids.remainder.high = remainder >> 128
+%}
+*/
+pub fn uint256_unsigned_div_rem(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let a_addr = get_relocatable_from_var_name("a", &ids, vm, hint_ap_tracking)?;
+    let div_addr = get_relocatable_from_var_name("div", &ids, vm, hint_ap_tracking)?;
+    let quotient_addr = get_relocatable_from_var_name("quotient", &ids, vm, hint_ap_tracking)?;
+    let remainder_addr = get_relocatable_from_var_name("remainder", &ids, vm, hint_ap_tracking)?;
+
+    let a_low = get_integer_from_relocatable_plus_offset(&a_addr, 0, vm)?;
+    let a_high = get_integer_from_relocatable_plus_offset(&a_addr, 1, vm)?;
+    let div_low = get_integer_from_relocatable_plus_offset(&div_addr, 0, vm)?;
+    let div_high = get_integer_from_relocatable_plus_offset(&div_addr, 1, vm)?;
+
+    //Main logic
+    //a = (ids.a.high << 128) + ids.a.low
+    //div = (ids.div.high << 128) + ids.div.low
+    //quotient, remainder = divmod(a, div)
+    let a = a_high.shl(128_usize) + a_low;
+    let div = div_high.shl(128_usize) + div_low;
+    if div.is_zero() {
+        return Err(VirtualMachineError::DividedByZero);
+    }
+    let (quotient, remainder) = a.div_mod_floor(&div);
+
+    insert_uint256(vm, &quotient_addr, &quotient)?;
+    insert_uint256(vm, &remainder_addr, &remainder)
+}
+
+/*
+Implements hint:
+%{
+    a = (ids.a.high << 128) + ids.a.low
+    b = (ids.b.high << 128) + ids.b.low
+    div = (ids.div.high << 128) + ids.div.low
+    quotient, remainder = divmod(a * b, div)
+
+    ids.quotient_low.low = quotient & ((1 << 128) - 1)
+    ids.quotient_low.high = (quotient >> 128) & ((1 << 128) - 1)
+    ids.quotient_high.low = (quotient >> 256) & ((1 << 128) - 1)
+    ids.quotient_high.high = quotient >> 384
+    ids.remainder.low = remainder & ((1 << 128) - 1)
+    ids.remainder.high = remainder >> 128
+%}
+*/
+pub fn uint256_mul_div_mod(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let a_addr = get_relocatable_from_var_name("a", &ids, vm, hint_ap_tracking)?;
+    let b_addr = get_relocatable_from_var_name("b", &ids, vm, hint_ap_tracking)?;
+    let div_addr = get_relocatable_from_var_name("div", &ids, vm, hint_ap_tracking)?;
+    let quotient_low_addr =
+        get_relocatable_from_var_name("quotient_low", &ids, vm, hint_ap_tracking)?;
+    let quotient_high_addr =
+        get_relocatable_from_var_name("quotient_high", &ids, vm, hint_ap_tracking)?;
+    let remainder_addr = get_relocatable_from_var_name("remainder", &ids, vm, hint_ap_tracking)?;
+
+    let a_low = get_integer_from_relocatable_plus_offset(&a_addr, 0, vm)?;
+    let a_high = get_integer_from_relocatable_plus_offset(&a_addr, 1, vm)?;
+    let b_low = get_integer_from_relocatable_plus_offset(&b_addr, 0, vm)?;
+    let b_high = get_integer_from_relocatable_plus_offset(&b_addr, 1, vm)?;
+    let div_low = get_integer_from_relocatable_plus_offset(&div_addr, 0, vm)?;
+    let div_high = get_integer_from_relocatable_plus_offset(&div_addr, 1, vm)?;
+
+    //Main logic
+    //a = (ids.a.high << 128) + ids.a.low
+    //b = (ids.b.high << 128) + ids.b.low
+    //div = (ids.div.high << 128) + ids.div.low
+    //quotient, remainder = divmod(a * b, div)
+    let a = a_high.shl(128_usize) + a_low;
+    let b = b_high.shl(128_usize) + b_low;
+    let div = div_high.shl(128_usize) + div_low;
+    if div.is_zero() {
+        return Err(VirtualMachineError::DividedByZero);
+    }
+    let (quotient, remainder) = (a * b).div_mod_floor(&div);
+
+    // The quotient may take up to 512 bits, so it is split into two Uint256
+    let mask = bigint!(1).shl(256_usize) - 1_i32;
+    insert_uint256(vm, &quotient_low_addr, &(&quotient & &mask))?;
+    insert_uint256(vm, &quotient_high_addr, &quotient.shr(256_usize))?;
+    insert_uint256(vm, &remainder_addr, &remainder)
+}
+
+/*
+Implements hint:
+%{
+    PRIME = 2**255 - 19
+    II = pow(2, (PRIME - 1) // 4, PRIME)
+
+    xx = ids.xx.low + (ids.xx.high<<128)
+    x = pow(xx, (PRIME + 3) // 8, PRIME)
+    if (x * x - xx) % PRIME != 0:
+        x = (x * II) % PRIME
+    if x % 2 != 0:
+        x = PRIME - x
+    ids.x.low = x & ((1<<128)-1)
+    ids.x.high = x >> 128
+%}
+*/
+pub fn split_xx(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    //Declare constant values
+    let prime = bigint!(1).shl(255_usize) - 19_i32;
+    let ii = bigint!(2).modpow(&(&prime - 1_i32).shr(2_usize), &prime);
+
+    let xx_addr = get_relocatable_from_var_name("xx", &ids, vm, hint_ap_tracking)?;
+    let x_addr = get_relocatable_from_var_name("x", &ids, vm, hint_ap_tracking)?;
+
+    let xx_low = get_integer_from_relocatable_plus_offset(&xx_addr, 0, vm)?;
+    let xx_high = get_integer_from_relocatable_plus_offset(&xx_addr, 1, vm)?;
+    let xx = xx_low + xx_high.shl(128_usize);
+
+    let mut x = xx.modpow(&(&prime + 3_i32).shr(3_usize), &prime);
+    if !(&x * &x - &xx).mod_floor(&prime).is_zero() {
+        x = (x * ii).mod_floor(&prime);
+    }
+    if x.is_odd() {
+        x = &prime - x;
+    }
+    insert_uint256(vm, &x_addr, &x)
+}
+
+// Writes the low and high 128-bit limbs of value into the Uint256 struct located at addr
+fn insert_uint256(
+    vm: &mut VirtualMachine,
+    addr: &Relocatable,
+    value: &BigInt,
+) -> Result<(), VirtualMachineError> {
+    let mask = bigint!(1).shl(128_usize) - 1_i32;
+    vm.memory
+        .insert(
+            &MaybeRelocatable::from((addr.segment_index, addr.offset)),
+            &MaybeRelocatable::from(value & &mask),
+        )
+        .map_err(VirtualMachineError::MemoryError)?;
+    vm.memory
+        .insert(
+            &MaybeRelocatable::from((addr.segment_index, addr.offset + 1)),
+            &MaybeRelocatable::from(value.shr(128_usize)),
+        )
+        .map_err(VirtualMachineError::MemoryError)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::bigint_str;
+    use crate::serde::deserialize_program::Member;
     use crate::types::instruction::Register;
     use crate::types::relocatable::MaybeRelocatable;
     use crate::vm::errors::memory_errors::MemoryError;
@@ -115,6 +353,11 @@ mod tests {
             )],
             false,
         );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([(
+            String::from("starkware.cairo.common.uint256.SHIFT"),
+            bigint!(1).shl(128_i32),
+        )]);
         for _ in 0..3 {
             vm.segments.add(&mut vm.memory, None);
         }
@@ -129,46 +372,78 @@ mod tests {
         ids.insert(String::from("carry_high"), bigint!(2));
         ids.insert(String::from("carry_low"), bigint!(3));
 
+        //Store the definition of the Uint256 struct
+        vm.struct_types = HashMap::from([(
+            String::from("starkware.cairo.common.uint256.Uint256"),
+            HashMap::from([
+                (
+                    String::from("low"),
+                    Member {
+                        cairo_type: String::from("felt"),
+                        offset: 0,
+                    },
+                ),
+                (
+                    String::from("high"),
+                    Member {
+                        cairo_type: String::from("felt"),
+                        offset: 1,
+                    },
+                ),
+            ]),
+        )]);
         //Create references
         vm.references = HashMap::from([
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -6,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: Some(String::from("starkware.cairo.common.uint256.Uint256")),
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: Some(String::from("starkware.cairo.common.uint256.Uint256")),
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -230,6 +505,11 @@ mod tests {
             )],
             false,
         );
+        //Store the program constants used by the hint
+        vm.constants = HashMap::from([(
+            String::from("starkware.cairo.common.uint256.SHIFT"),
+            bigint!(1).shl(128_i32),
+        )]);
         for _ in 0..3 {
             vm.segments.add(&mut vm.memory, None);
         }
@@ -244,46 +524,78 @@ mod tests {
         ids.insert(String::from("carry_high"), bigint!(2));
         ids.insert(String::from("carry_low"), bigint!(3));
 
+        //Store the definition of the Uint256 struct
+        vm.struct_types = HashMap::from([(
+            String::from("starkware.cairo.common.uint256.Uint256"),
+            HashMap::from([
+                (
+                    String::from("low"),
+                    Member {
+                        cairo_type: String::from("felt"),
+                        offset: 0,
+                    },
+                ),
+                (
+                    String::from("high"),
+                    Member {
+                        cairo_type: String::from("felt"),
+                        offset: 1,
+                    },
+                ),
+            ]),
+        )]);
         //Create references
         vm.references = HashMap::from([
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -6,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: Some(String::from("starkware.cairo.common.uint256.Uint256")),
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -4,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: Some(String::from("starkware.cairo.common.uint256.Uint256")),
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 3,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 2,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -367,31 +679,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 0,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -456,31 +777,40 @@ mod tests {
             (
                 0,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: -3,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 1,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 1,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
             (
                 2,
                 HintReference {
-                    register: Register::FP,
+                    register: Some(Register::FP),
                     offset1: 0,
                     offset2: 0,
                     inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
                     ap_tracking_data: None,
+                    value: None,
                 },
             ),
         ]);
@@ -513,4 +843,952 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn run_uint256_sqrt_ok() {
+        let hint_code = "from starkware.python.math_utils import isqrt\nn = (ids.n.high << 128) + ids.n.low\nroot = isqrt(n)\nassert 0 <= root < 2 ** 128\nids.root.low = root\nids.root.high = 0".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            vec![(
+                "range_check".to_string(),
+                Box::new(RangeCheckBuiltinRunner::new(true, bigint!(8), 8)),
+            )],
+            false,
+        );
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("n"), bigint!(0));
+        ids.insert(String::from("root"), bigint!(1));
+
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -5,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: 0,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+
+        //Insert ids.n.low into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 5)),
+                &MaybeRelocatable::from(bigint!(17)),
+            )
+            .unwrap();
+        //Insert ids.n.high into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 6)),
+                &MaybeRelocatable::from(bigint!(7)),
+            )
+            .unwrap();
+
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+
+        //Check hint memory inserts
+        // ids.root.low
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 10))),
+            Ok(Some(&MaybeRelocatable::from(bigint_str!(
+                b"48805497317890012913"
+            ))))
+        );
+        // ids.root.high
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 11))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+    }
+
+    #[test]
+    fn run_uint256_sqrt_assert_error() {
+        let hint_code = "from starkware.python.math_utils import isqrt\nn = (ids.n.high << 128) + ids.n.low\nroot = isqrt(n)\nassert 0 <= root < 2 ** 128\nids.root.low = root\nids.root.high = 0".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            vec![(
+                "range_check".to_string(),
+                Box::new(RangeCheckBuiltinRunner::new(true, bigint!(8), 8)),
+            )],
+            false,
+        );
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("n"), bigint!(0));
+        ids.insert(String::from("root"), bigint!(1));
+
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -5,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: 0,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+
+        //Insert ids.n.low into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 5)),
+                &MaybeRelocatable::from(bigint!(0)),
+            )
+            .unwrap();
+        //Insert ids.n.high into memory, n = 2**256 so root = 2**128
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 6)),
+                &MaybeRelocatable::from(bigint!(2).pow(128)),
+            )
+            .unwrap();
+
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Err(VirtualMachineError::AssertionFailed(String::from(
+                "assert 0 <= 340282366920938463463374607431768211456 < 2 ** 128"
+            )))
+        );
+    }
+
+    #[test]
+    fn run_uint256_signed_nn_ok_result_one() {
+        let hint_code = "memory[ap] = 1 if 0 <= (ids.a.high % PRIME) < 2 ** 127 else 0".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            vec![(
+                "range_check".to_string(),
+                Box::new(RangeCheckBuiltinRunner::new(true, bigint!(8), 8)),
+            )],
+            false,
+        );
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+        //Initialize ap
+        vm.run_context.ap = MaybeRelocatable::from((1, 5));
+
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("a"), bigint!(0));
+
+        //Create references
+        vm.references = HashMap::from([(
+            0,
+            HintReference {
+                register: Some(Register::FP),
+                offset1: -4,
+                offset2: 0,
+                inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
+                ap_tracking_data: None,
+                value: None,
+            },
+        )]);
+
+        //Insert ids.a.high into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 7)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+
+        //Check hint memory insert
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 5))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(1))))
+        );
+    }
+
+    #[test]
+    fn run_uint256_signed_nn_ok_result_zero() {
+        let hint_code = "memory[ap] = 1 if 0 <= (ids.a.high % PRIME) < 2 ** 127 else 0".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            vec![(
+                "range_check".to_string(),
+                Box::new(RangeCheckBuiltinRunner::new(true, bigint!(8), 8)),
+            )],
+            false,
+        );
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+        //Initialize ap
+        vm.run_context.ap = MaybeRelocatable::from((1, 5));
+
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("a"), bigint!(0));
+
+        //Create references
+        vm.references = HashMap::from([(
+            0,
+            HintReference {
+                register: Some(Register::FP),
+                offset1: -4,
+                offset2: 0,
+                inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
+                ap_tracking_data: None,
+                value: None,
+            },
+        )]);
+
+        //Insert ids.a.high into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 7)),
+                &MaybeRelocatable::from(bigint!(2).pow(127)),
+            )
+            .unwrap();
+
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+
+        //Check hint memory insert
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 5))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+    }
+
+    #[test]
+    fn run_uint256_signed_nn_invalid_memory_insert() {
+        let hint_code = "memory[ap] = 1 if 0 <= (ids.a.high % PRIME) < 2 ** 127 else 0".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            vec![(
+                "range_check".to_string(),
+                Box::new(RangeCheckBuiltinRunner::new(true, bigint!(8), 8)),
+            )],
+            false,
+        );
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+        //Initialize ap
+        vm.run_context.ap = MaybeRelocatable::from((1, 5));
+
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("a"), bigint!(0));
+
+        //Create references
+        vm.references = HashMap::from([(
+            0,
+            HintReference {
+                register: Some(Register::FP),
+                offset1: -4,
+                offset2: 0,
+                inner_dereference: false,
+                dereference: true,
+                cairo_type: None,
+                ap_tracking_data: None,
+                value: None,
+            },
+        )]);
+
+        //Insert ids.a.high into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 7)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+
+        //Insert a value in ap, so the hint insertion fails
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 5)),
+                &MaybeRelocatable::from(bigint!(55)),
+            )
+            .unwrap();
+
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Err(VirtualMachineError::MemoryError(
+                MemoryError::InconsistentMemory(
+                    MaybeRelocatable::from((1, 5)),
+                    MaybeRelocatable::from(bigint!(55)),
+                    MaybeRelocatable::from(bigint!(1))
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn run_uint256_unsigned_div_rem_ok() {
+        let hint_code = "a = (ids.a.high << 128) + ids.a.low\ndiv = (ids.div.high << 128) + ids.div.low\nquotient, remainder = divmod(a, div)\n\nids.quotient.low = quotient & ((1 << 128) - 1)\nids.quotient.high = quotient >> 128\nids.remainder.low = remainder & ((1 << 128) - 1)\nids.remainder.high = remainder >> 128".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            vec![(
+                "range_check".to_string(),
+                Box::new(RangeCheckBuiltinRunner::new(true, bigint!(8), 8)),
+            )],
+            false,
+        );
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("a"), bigint!(0));
+        ids.insert(String::from("div"), bigint!(1));
+        ids.insert(String::from("quotient"), bigint!(2));
+        ids.insert(String::from("remainder"), bigint!(3));
+
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -6,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -4,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                2,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: 0,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                3,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: 2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+
+        //Insert ids.a.low into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 4)),
+                &MaybeRelocatable::from(bigint!(89)),
+            )
+            .unwrap();
+        //Insert ids.a.high into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 5)),
+                &MaybeRelocatable::from(bigint!(72)),
+            )
+            .unwrap();
+        //Insert ids.div.low into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 6)),
+                &MaybeRelocatable::from(bigint!(3)),
+            )
+            .unwrap();
+        //Insert ids.div.high into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 7)),
+                &MaybeRelocatable::from(bigint!(7)),
+            )
+            .unwrap();
+
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+
+        //Check hint memory inserts
+        // ids.quotient.low
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 10))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(10))))
+        );
+        // ids.quotient.high
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 11))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+        // ids.remainder.low
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 12))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(59))))
+        );
+        // ids.remainder.high
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 13))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(2))))
+        );
+    }
+
+    #[test]
+    fn run_uint256_unsigned_div_rem_divided_by_zero() {
+        let hint_code = "a = (ids.a.high << 128) + ids.a.low\ndiv = (ids.div.high << 128) + ids.div.low\nquotient, remainder = divmod(a, div)\n\nids.quotient.low = quotient & ((1 << 128) - 1)\nids.quotient.high = quotient >> 128\nids.remainder.low = remainder & ((1 << 128) - 1)\nids.remainder.high = remainder >> 128".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            vec![(
+                "range_check".to_string(),
+                Box::new(RangeCheckBuiltinRunner::new(true, bigint!(8), 8)),
+            )],
+            false,
+        );
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("a"), bigint!(0));
+        ids.insert(String::from("div"), bigint!(1));
+        ids.insert(String::from("quotient"), bigint!(2));
+        ids.insert(String::from("remainder"), bigint!(3));
+
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -6,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -4,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                2,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: 0,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                3,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: 2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+
+        //Insert ids.a into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 4)),
+                &MaybeRelocatable::from(bigint!(89)),
+            )
+            .unwrap();
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 5)),
+                &MaybeRelocatable::from(bigint!(72)),
+            )
+            .unwrap();
+        //Insert ids.div = 0 into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 6)),
+                &MaybeRelocatable::from(bigint!(0)),
+            )
+            .unwrap();
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 7)),
+                &MaybeRelocatable::from(bigint!(0)),
+            )
+            .unwrap();
+
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Err(VirtualMachineError::DividedByZero)
+        );
+    }
+
+    #[test]
+    fn run_uint256_mul_div_mod_ok() {
+        let hint_code = "a = (ids.a.high << 128) + ids.a.low\nb = (ids.b.high << 128) + ids.b.low\ndiv = (ids.div.high << 128) + ids.div.low\nquotient, remainder = divmod(a * b, div)\n\nids.quotient_low.low = quotient & ((1 << 128) - 1)\nids.quotient_low.high = (quotient >> 128) & ((1 << 128) - 1)\nids.quotient_high.low = (quotient >> 256) & ((1 << 128) - 1)\nids.quotient_high.high = quotient >> 384\nids.remainder.low = remainder & ((1 << 128) - 1)\nids.remainder.high = remainder >> 128".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            vec![(
+                "range_check".to_string(),
+                Box::new(RangeCheckBuiltinRunner::new(true, bigint!(8), 8)),
+            )],
+            false,
+        );
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("a"), bigint!(0));
+        ids.insert(String::from("b"), bigint!(1));
+        ids.insert(String::from("div"), bigint!(2));
+        ids.insert(String::from("quotient_low"), bigint!(3));
+        ids.insert(String::from("quotient_high"), bigint!(4));
+        ids.insert(String::from("remainder"), bigint!(5));
+
+        //Create references
+        vm.references = HashMap::new();
+        for (i, offset) in [-6, -4, -2, 0, 2, 4].iter().enumerate() {
+            vm.references.insert(
+                i,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: *offset,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            );
+        }
+
+        //Insert ids.a.low and ids.a.high into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 4)),
+                &MaybeRelocatable::from(bigint!(89)),
+            )
+            .unwrap();
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 5)),
+                &MaybeRelocatable::from(bigint!(2).pow(120)),
+            )
+            .unwrap();
+        //Insert ids.b.low and ids.b.high into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 6)),
+                &MaybeRelocatable::from(bigint!(72)),
+            )
+            .unwrap();
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 7)),
+                &MaybeRelocatable::from(bigint!(2).pow(127)),
+            )
+            .unwrap();
+        //Insert ids.div.low and ids.div.high into memory
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 8)),
+                &MaybeRelocatable::from(bigint!(107)),
+            )
+            .unwrap();
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 9)),
+                &MaybeRelocatable::from(bigint!(0)),
+            )
+            .unwrap();
+
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+
+        //Check hint memory inserts
+        // ids.quotient_low.low
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 10))),
+            Ok(Some(&MaybeRelocatable::from(bigint_str!(
+                b"228975050638388498779093193785862721787"
+            ))))
+        );
+        // ids.quotient_low.high
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 11))),
+            Ok(Some(&MaybeRelocatable::from(bigint_str!(
+                b"199657498581823999152429785727342972200"
+            ))))
+        );
+        // ids.quotient_high.low
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 12))),
+            Ok(Some(&MaybeRelocatable::from(bigint_str!(
+                b"190812542198657082315910994821552268106"
+            ))))
+        );
+        // ids.quotient_high.high
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 13))),
+            Ok(Some(&MaybeRelocatable::from(bigint_str!(
+                b"6211345774695868564971061029347404"
+            ))))
+        );
+        // ids.remainder.low
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 14))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(31))))
+        );
+        // ids.remainder.high
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 15))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+    }
+
+    #[test]
+    fn run_uint256_mul_div_mod_missing_ids() {
+        let hint_code = "a = (ids.a.high << 128) + ids.a.low\nb = (ids.b.high << 128) + ids.b.low\ndiv = (ids.div.high << 128) + ids.div.low\nquotient, remainder = divmod(a * b, div)\n\nids.quotient_low.low = quotient & ((1 << 128) - 1)\nids.quotient_low.high = (quotient >> 128) & ((1 << 128) - 1)\nids.quotient_high.low = (quotient >> 256) & ((1 << 128) - 1)\nids.quotient_high.high = quotient >> 384\nids.remainder.low = remainder & ((1 << 128) - 1)\nids.remainder.high = remainder >> 128".as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((1, 10));
+
+        //Create ids without quotient_high
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("a"), bigint!(0));
+        ids.insert(String::from("b"), bigint!(1));
+        ids.insert(String::from("div"), bigint!(2));
+        ids.insert(String::from("quotient_low"), bigint!(3));
+        ids.insert(String::from("remainder"), bigint!(4));
+
+        //Create references
+        vm.references = HashMap::new();
+        for (i, offset) in [-6, -4, -2, 0, 2].iter().enumerate() {
+            vm.references.insert(
+                i,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: *offset,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            );
+        }
+
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Err(VirtualMachineError::FailedToGetIds)
+        );
+    }
+
+    #[test]
+    fn run_split_xx_even_root() {
+        let hint_code = "PRIME = 2**255 - 19\nII = pow(2, (PRIME - 1) // 4, PRIME)\n\nxx = ids.xx.low + (ids.xx.high<<128)\nx = pow(xx, (PRIME + 3) // 8, PRIME)\nif (x * x - xx) % PRIME != 0:\n    x = (x * II) % PRIME\nif x % 2 != 0:\n    x = PRIME - x\nids.x.low = x & ((1<<128)-1)\nids.x.high = x >> 128"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 4));
+        //Insert ids into memory
+        //ids.xx.low
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(4)),
+            )
+            .unwrap();
+        //ids.xx.high
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 1)),
+                &MaybeRelocatable::from(bigint!(0)),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("xx"), bigint!(0));
+        ids.insert(String::from("x"), bigint!(1));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -4,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //x = 2
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 2))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(2))))
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 3))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+    }
+
+    #[test]
+    fn run_split_xx_odd_root() {
+        let hint_code = "PRIME = 2**255 - 19\nII = pow(2, (PRIME - 1) // 4, PRIME)\n\nxx = ids.xx.low + (ids.xx.high<<128)\nx = pow(xx, (PRIME + 3) // 8, PRIME)\nif (x * x - xx) % PRIME != 0:\n    x = (x * II) % PRIME\nif x % 2 != 0:\n    x = PRIME - x\nids.x.low = x & ((1<<128)-1)\nids.x.high = x >> 128"
+            .as_bytes();
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..1 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        //Initialize fp
+        vm.run_context.fp = MaybeRelocatable::from((0, 4));
+        //Insert ids into memory
+        //ids.xx.low
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(9)),
+            )
+            .unwrap();
+        //ids.xx.high
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 1)),
+                &MaybeRelocatable::from(bigint!(0)),
+            )
+            .unwrap();
+        //Create ids
+        let mut ids = HashMap::<String, BigInt>::new();
+        ids.insert(String::from("xx"), bigint!(0));
+        ids.insert(String::from("x"), bigint!(1));
+        //Create references
+        vm.references = HashMap::from([
+            (
+                0,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -4,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+            (
+                1,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: -2,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            ),
+        ]);
+        //Execute the hint
+        assert_eq!(
+            execute_hint(&mut vm, hint_code, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //3 is odd, so x = PRIME - 3
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 2))),
+            Ok(Some(&MaybeRelocatable::from(bigint_str!(
+                b"340282366920938463463374607431768211434"
+            ))))
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((0, 3))),
+            Ok(Some(&MaybeRelocatable::from(bigint_str!(
+                b"170141183460469231731687303715884105727"
+            ))))
+        );
+    }
 }
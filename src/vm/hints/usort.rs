@@ -0,0 +1,483 @@
+use crate::serde::deserialize_program::ApTracking;
+use crate::types::{
+    exec_scope::PyValueType,
+    relocatable::{MaybeRelocatable, Relocatable},
+};
+use crate::vm::{
+    errors::vm_errors::VirtualMachineError,
+    hints::hint_utils::{get_address_from_var_name, get_int_from_scope, get_integer_from_var_name},
+    vm_core::VirtualMachine,
+    vm_memory::memory_segments::CairoArg,
+};
+use crate::{bigint, bigint_u64, bigintusize};
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, Signed, ToPrimitive};
+use std::collections::HashMap;
+
+//Returns the value in the current execution scope that matches the name and is of type ListU64
+fn get_list_u64_from_scope(vm: &mut VirtualMachine, name: &str) -> Option<Vec<u64>> {
+    let mut val: Option<Vec<u64>> = None;
+    if let Some(variables) = vm.exec_scopes.get_local_variables() {
+        if let Some(PyValueType::ListU64(py_val)) = variables.get(name) {
+            val = Some(py_val.clone());
+        }
+    }
+    val
+}
+
+//Returns the positions_dict stored in the current execution scope by usort_body
+fn get_positions_dict(vm: &mut VirtualMachine) -> Option<HashMap<BigInt, Vec<u64>>> {
+    let mut positions_dict: Option<HashMap<BigInt, Vec<u64>>> = None;
+    if let Some(variables) = vm.exec_scopes.get_local_variables() {
+        if let Some(PyValueType::KeyToListU64Map(py_positions_dict)) =
+            variables.get("positions_dict")
+        {
+            positions_dict = Some(py_positions_dict.clone());
+        }
+    }
+    positions_dict
+}
+
+//Implements hint: vm_enter_scope(dict(__usort_max_size = globals().get('__usort_max_size')))
+pub fn usort_enter_scope(vm: &mut VirtualMachine) -> Result<(), VirtualMachineError> {
+    let new_scope_locals = match get_int_from_scope(vm, "usort_max_size") {
+        Some(usort_max_size) => HashMap::from([(
+            String::from("usort_max_size"),
+            PyValueType::BigInt(usort_max_size),
+        )]),
+        None => HashMap::new(),
+    };
+    vm.exec_scopes.enter_scope(new_scope_locals);
+    Ok(())
+}
+
+/*
+Implements hint:
+%{
+    from collections import defaultdict
+
+    input_ptr = ids.input
+    input_len = int(ids.input_len)
+    if __usort_max_size is not None:
+        assert input_len <= __usort_max_size, (
+            f"usort() can only be used with input_len<={__usort_max_size}. "
+            f"Got: input_len={input_len}."
+        )
+
+    positions_dict = defaultdict(list)
+    for i in range(input_len):
+        val = memory[input_ptr + i]
+        positions_dict[val].append(i)
+
+    output = sorted(positions_dict.keys())
+    ids.output_len = len(output)
+    ids.output = segments.gen_arg(output)
+    ids.multiplicities = segments.gen_arg([len(positions_dict[k]) for k in output])
+%}
+*/
+pub fn usort_body(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let usort_max_size = get_int_from_scope(vm, "usort_max_size");
+    let input_addr = get_address_from_var_name("input", &ids, vm, hint_ap_tracking)?;
+    let input_len = get_integer_from_var_name("input_len", &ids, vm, hint_ap_tracking)?.clone();
+    let output_len_addr = get_address_from_var_name("output_len", &ids, vm, hint_ap_tracking)?;
+    let output_addr = get_address_from_var_name("output", &ids, vm, hint_ap_tracking)?;
+    let multiplicities_addr =
+        get_address_from_var_name("multiplicities", &ids, vm, hint_ap_tracking)?;
+
+    if let Some(usort_max_size) = usort_max_size {
+        if input_len > usort_max_size {
+            return Err(VirtualMachineError::UsortOutOfRange(
+                usort_max_size,
+                input_len,
+            ));
+        }
+    }
+
+    let input_ptr = match vm
+        .memory
+        .get(&input_addr)
+        .map_err(VirtualMachineError::MemoryError)?
+    {
+        Some(MaybeRelocatable::RelocatableValue(input_ptr)) => input_ptr.clone(),
+        Some(_) => return Err(VirtualMachineError::ExpectedRelocatable(input_addr)),
+        None => return Err(VirtualMachineError::MemoryGet(input_addr)),
+    };
+    if input_len.is_negative() {
+        return Err(VirtualMachineError::ValueOutOfRange(input_len));
+    }
+    let input_len_u64 = input_len
+        .to_u64()
+        .ok_or(VirtualMachineError::BigintToUsizeFail)?;
+
+    let mut positions_dict: HashMap<BigInt, Vec<u64>> = HashMap::new();
+    for i in 0..input_len_u64 {
+        let val = vm.memory.get_integer(&Relocatable::from((
+            input_ptr.segment_index,
+            input_ptr.offset + i as usize,
+        )))?;
+        positions_dict.entry(val.clone()).or_default().push(i);
+    }
+
+    let mut output: Vec<BigInt> = positions_dict.keys().cloned().collect();
+    output.sort();
+    let multiplicities: Vec<BigInt> = output
+        .iter()
+        .map(|k| bigintusize!(positions_dict[k].len()))
+        .collect();
+    let output_len = bigintusize!(output.len());
+
+    let output_base = vm
+        .segments
+        .gen_arg(&mut vm.memory, &CairoArg::from(output), None)
+        .map_err(VirtualMachineError::MemoryError)?;
+    let multiplicities_base = vm
+        .segments
+        .gen_arg(&mut vm.memory, &CairoArg::from(multiplicities), None)
+        .map_err(VirtualMachineError::MemoryError)?;
+
+    vm.exec_scopes.assign_or_update_variable(
+        "positions_dict",
+        PyValueType::KeyToListU64Map(positions_dict),
+    );
+
+    vm.memory
+        .insert(&output_len_addr, &MaybeRelocatable::from(output_len))
+        .map_err(VirtualMachineError::MemoryError)?;
+    vm.memory
+        .insert(&output_addr, &output_base)
+        .map_err(VirtualMachineError::MemoryError)?;
+    vm.memory
+        .insert(&multiplicities_addr, &multiplicities_base)
+        .map_err(VirtualMachineError::MemoryError)
+}
+
+/*
+Implements hint:
+%{
+    last_pos = 0
+    positions = positions_dict[ids.value][::-1]
+%}
+*/
+pub fn verify_usort(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let value = get_integer_from_var_name("value", &ids, vm, hint_ap_tracking)?.clone();
+    let positions_dict = get_positions_dict(vm)
+        .ok_or_else(|| VirtualMachineError::NoLocalVariable(String::from("positions_dict")))?;
+    // positions_dict is a defaultdict(list), so a missing key yields an empty list
+    let mut positions = positions_dict.get(&value).cloned().unwrap_or_default();
+    positions.reverse();
+    vm.exec_scopes
+        .assign_or_update_variable("positions", PyValueType::ListU64(positions));
+    vm.exec_scopes
+        .assign_or_update_variable("last_pos", PyValueType::BigInt(bigint!(0)));
+    Ok(())
+}
+
+//Implements hint: assert len(positions) == 0
+pub fn verify_multiplicity_assert(vm: &mut VirtualMachine) -> Result<(), VirtualMachineError> {
+    let positions = get_list_u64_from_scope(vm, "positions")
+        .ok_or_else(|| VirtualMachineError::NoLocalVariable(String::from("positions")))?;
+    if !positions.is_empty() {
+        return Err(VirtualMachineError::PositionsNotEmpty);
+    }
+    Ok(())
+}
+
+/*
+Implements hint:
+%{
+    current_pos = positions.pop()
+    ids.next_item_index = current_pos - last_pos
+    last_pos = current_pos + 1
+%}
+*/
+pub fn verify_multiplicity_body(
+    vm: &mut VirtualMachine,
+    ids: HashMap<String, BigInt>,
+    hint_ap_tracking: Option<&ApTracking>,
+) -> Result<(), VirtualMachineError> {
+    let mut positions = get_list_u64_from_scope(vm, "positions")
+        .ok_or_else(|| VirtualMachineError::NoLocalVariable(String::from("positions")))?;
+    let last_pos = get_int_from_scope(vm, "last_pos")
+        .ok_or_else(|| VirtualMachineError::NoLocalVariable(String::from("last_pos")))?;
+    let next_item_index_addr =
+        get_address_from_var_name("next_item_index", &ids, vm, hint_ap_tracking)?;
+
+    let current_pos = bigint_u64!(positions.pop().ok_or(VirtualMachineError::EmptyPositions)?);
+    let next_item_index = &current_pos - last_pos;
+
+    vm.memory
+        .insert(
+            &next_item_index_addr,
+            &MaybeRelocatable::from(next_item_index),
+        )
+        .map_err(VirtualMachineError::MemoryError)?;
+
+    vm.exec_scopes
+        .assign_or_update_variable("positions", PyValueType::ListU64(positions));
+    vm.exec_scopes
+        .assign_or_update_variable("last_pos", PyValueType::BigInt(current_pos + 1_i32));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::instruction::Register;
+    use crate::vm::hints::execute_hint::{execute_hint, HintReference};
+    use num_bigint::Sign;
+
+    const USORT_ENTER_SCOPE: &[u8] =
+        "vm_enter_scope(dict(__usort_max_size = globals().get('__usort_max_size')))".as_bytes();
+    const USORT_BODY: &[u8] = "from collections import defaultdict\n\ninput_ptr = ids.input\ninput_len = int(ids.input_len)\nif __usort_max_size is not None:\n    assert input_len <= __usort_max_size, (\n        f\"usort() can only be used with input_len<={__usort_max_size}. \"\n        f\"Got: input_len={input_len}.\"\n    )\n\npositions_dict = defaultdict(list)\nfor i in range(input_len):\n    val = memory[input_ptr + i]\n    positions_dict[val].append(i)\n\noutput = sorted(positions_dict.keys())\nids.output_len = len(output)\nids.output = segments.gen_arg(output)\nids.multiplicities = segments.gen_arg([len(positions_dict[k]) for k in output])".as_bytes();
+    const VERIFY_USORT: &[u8] =
+        "last_pos = 0\npositions = positions_dict[ids.value][::-1]".as_bytes();
+    const VERIFY_MULTIPLICITY_ASSERT: &[u8] = "assert len(positions) == 0".as_bytes();
+    const VERIFY_MULTIPLICITY_BODY: &[u8] = "current_pos = positions.pop()\nids.next_item_index = current_pos - last_pos\nlast_pos = current_pos + 1".as_bytes();
+
+    fn init_vm_ids(names: &[&str]) -> (VirtualMachine, HashMap<String, BigInt>) {
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        for _ in 0..3 {
+            vm.segments.add(&mut vm.memory, None);
+        }
+        vm.run_context.fp = MaybeRelocatable::from((1, 0));
+
+        let mut ids = HashMap::<String, BigInt>::new();
+        for (i, name) in names.iter().enumerate() {
+            ids.insert(name.to_string(), bigintusize!(i));
+            vm.references.insert(
+                i,
+                HintReference {
+                    register: Some(Register::FP),
+                    offset1: i as i32,
+                    offset2: 0,
+                    inner_dereference: false,
+                    dereference: true,
+                    cairo_type: None,
+                    ap_tracking_data: None,
+                    value: None,
+                },
+            );
+        }
+        (vm, ids)
+    }
+
+    fn init_usort_body() -> (VirtualMachine, HashMap<String, BigInt>) {
+        let (mut vm, ids) = init_vm_ids(&[
+            "input",
+            "input_len",
+            "output_len",
+            "output",
+            "multiplicities",
+        ]);
+        //input = (2, 0) -> [3, 1, 3, 2]
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 0)),
+                &MaybeRelocatable::from((2, 0)),
+            )
+            .unwrap();
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 1)),
+                &MaybeRelocatable::from(bigint!(4)),
+            )
+            .unwrap();
+        for (i, value) in [3, 1, 3, 2].iter().enumerate() {
+            vm.memory
+                .insert(
+                    &MaybeRelocatable::from((2, i)),
+                    &MaybeRelocatable::from(bigint!(*value)),
+                )
+                .unwrap();
+        }
+        (vm, ids)
+    }
+
+    #[test]
+    fn usort_enter_scope_keeps_max_size() {
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        vm.exec_scopes
+            .assign_or_update_variable("usort_max_size", PyValueType::BigInt(bigint!(10)));
+        assert_eq!(
+            execute_hint(
+                &mut vm,
+                USORT_ENTER_SCOPE,
+                HashMap::new(),
+                &ApTracking::new()
+            ),
+            Ok(())
+        );
+        assert_eq!(vm.exec_scopes.data.len(), 2);
+        assert_eq!(
+            get_int_from_scope(&mut vm, "usort_max_size"),
+            Some(bigint!(10))
+        );
+    }
+
+    #[test]
+    fn usort_enter_scope_without_max_size() {
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        assert_eq!(
+            execute_hint(
+                &mut vm,
+                USORT_ENTER_SCOPE,
+                HashMap::new(),
+                &ApTracking::new()
+            ),
+            Ok(())
+        );
+        assert_eq!(vm.exec_scopes.data.len(), 2);
+        assert_eq!(get_int_from_scope(&mut vm, "usort_max_size"), None);
+    }
+
+    #[test]
+    fn usort_body_ok() {
+        let (mut vm, ids) = init_usort_body();
+        assert_eq!(
+            execute_hint(&mut vm, USORT_BODY, ids, &ApTracking::new()),
+            Ok(())
+        );
+        //output_len
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 2))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(3))))
+        );
+        //output = (3, 0) -> [1, 2, 3]
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 3))),
+            Ok(Some(&MaybeRelocatable::from((3, 0))))
+        );
+        //multiplicities = (4, 0) -> [1, 1, 2]
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 4))),
+            Ok(Some(&MaybeRelocatable::from((4, 0))))
+        );
+        for (i, (value, multiplicity)) in [(1, 1), (2, 1), (3, 2)].iter().enumerate() {
+            assert_eq!(
+                vm.memory.get(&MaybeRelocatable::from((3, i))),
+                Ok(Some(&MaybeRelocatable::from(bigint!(*value))))
+            );
+            assert_eq!(
+                vm.memory.get(&MaybeRelocatable::from((4, i))),
+                Ok(Some(&MaybeRelocatable::from(bigint!(*multiplicity))))
+            );
+        }
+        assert_eq!(
+            get_positions_dict(&mut vm),
+            Some(HashMap::from([
+                (bigint!(1), vec![1]),
+                (bigint!(2), vec![3]),
+                (bigint!(3), vec![0, 2]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn usort_body_max_size_exceeded() {
+        let (mut vm, ids) = init_usort_body();
+        vm.exec_scopes
+            .assign_or_update_variable("usort_max_size", PyValueType::BigInt(bigint!(3)));
+        assert_eq!(
+            execute_hint(&mut vm, USORT_BODY, ids, &ApTracking::new()),
+            Err(VirtualMachineError::UsortOutOfRange(bigint!(3), bigint!(4)))
+        );
+    }
+
+    #[test]
+    fn verify_usort_and_multiplicity_ok() {
+        let (mut vm, ids) = init_vm_ids(&["value", "next_item_index"]);
+        vm.exec_scopes.assign_or_update_variable(
+            "positions_dict",
+            PyValueType::KeyToListU64Map(HashMap::from([(bigint!(3), vec![0, 2])])),
+        );
+        //ids.value = 3
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 0)),
+                &MaybeRelocatable::from(bigint!(3)),
+            )
+            .unwrap();
+        assert_eq!(
+            execute_hint(&mut vm, VERIFY_USORT, ids.clone(), &ApTracking::new()),
+            Ok(())
+        );
+        assert_eq!(
+            get_list_u64_from_scope(&mut vm, "positions"),
+            Some(vec![2, 0])
+        );
+        assert_eq!(get_int_from_scope(&mut vm, "last_pos"), Some(bigint!(0)));
+
+        assert_eq!(
+            execute_hint(
+                &mut vm,
+                VERIFY_MULTIPLICITY_BODY,
+                ids.clone(),
+                &ApTracking::new()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 1))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(0))))
+        );
+        assert_eq!(get_list_u64_from_scope(&mut vm, "positions"), Some(vec![2]));
+        assert_eq!(get_int_from_scope(&mut vm, "last_pos"), Some(bigint!(1)));
+        assert_eq!(
+            execute_hint(&mut vm, VERIFY_MULTIPLICITY_ASSERT, ids, &ApTracking::new()),
+            Err(VirtualMachineError::PositionsNotEmpty)
+        );
+    }
+
+    #[test]
+    fn verify_multiplicity_body_empty_positions() {
+        let (mut vm, ids) = init_vm_ids(&["next_item_index"]);
+        vm.exec_scopes
+            .assign_or_update_variable("positions", PyValueType::ListU64(Vec::new()));
+        vm.exec_scopes
+            .assign_or_update_variable("last_pos", PyValueType::BigInt(bigint!(0)));
+        assert_eq!(
+            execute_hint(&mut vm, VERIFY_MULTIPLICITY_BODY, ids, &ApTracking::new()),
+            Err(VirtualMachineError::EmptyPositions)
+        );
+    }
+
+    #[test]
+    fn verify_multiplicity_assert_ok() {
+        let mut vm = VirtualMachine::new(
+            BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            Vec::new(),
+            false,
+        );
+        vm.exec_scopes
+            .assign_or_update_variable("positions", PyValueType::ListU64(Vec::new()));
+        assert_eq!(
+            execute_hint(
+                &mut vm,
+                VERIFY_MULTIPLICITY_ASSERT,
+                HashMap::new(),
+                &ApTracking::new()
+            ),
+            Ok(())
+        );
+    }
+}
@@ -1,8 +1,11 @@
 pub mod context;
+pub mod coverage;
 pub mod decoding;
 pub mod errors;
 pub mod hints;
+pub mod profiler;
 pub mod runners;
+pub mod security;
 pub mod trace;
 pub mod vm_core;
 pub mod vm_memory;
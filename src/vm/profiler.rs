@@ -0,0 +1,367 @@
+use crate::types::relocatable::{MaybeRelocatable, Relocatable};
+use crate::vm::context::run_context::RunContext;
+use crate::vm::errors::runner_errors::RunnerError;
+use crate::vm::errors::trace_errors::TraceError;
+use crate::vm::runners::cairo_runner::CairoRunner;
+use crate::vm::trace::trace_entry::TraceEntry;
+use crate::vm::vm_core::VirtualMachine;
+use crate::vm::vm_memory::memory::Memory;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::str::FromStr;
+
+const UNKNOWN_FUNCTION: &str = "<unknown>";
+
+/// Output format of the profile file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileFormat {
+    Folded,
+    Summary,
+}
+
+impl FromStr for ProfileFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "folded" => Ok(ProfileFormat::Folded),
+            "summary" => Ok(ProfileFormat::Summary),
+            _ => Err(format!(
+                "Unknown profile format {format}, expected one of: folded, summary"
+            )),
+        }
+    }
+}
+
+/// Steps and builtin usage attributed to a single function.
+#[derive(Debug, Default, PartialEq)]
+pub struct FunctionProfile {
+    /// Steps executed by the function's own instructions.
+    pub self_steps: usize,
+    /// Steps executed while the function was on the call stack, including its callees.
+    pub inclusive_steps: usize,
+    /// Builtin memory cells accessed by the function's own instructions, by builtin name.
+    pub builtin_accesses: BTreeMap<String, usize>,
+}
+
+/// Per function profile of a run, built from its trace.
+#[derive(Debug, Default, PartialEq)]
+pub struct Profile {
+    pub functions: HashMap<String, FunctionProfile>,
+    /// Steps executed by each call stack, from the outermost function to the innermost one.
+    pub stacks: HashMap<Vec<String>, usize>,
+}
+
+impl Profile {
+    ///Builds the profile of a finished run. Each step is attributed to the function enclosing
+    /// its pc, and the call stack is recovered by following the fp chain through the memory,
+    /// as every frame stores the caller's fp at [fp - 2] and the return pc at [fp - 1].
+    pub fn new(runner: &CairoRunner) -> Result<Profile, RunnerError> {
+        let trace = runner
+            .vm
+            .trace
+            .as_ref()
+            .ok_or(RunnerError::Trace(TraceError::TraceNotEnabled))?;
+        let program_base = runner.get_program_base().ok_or(RunnerError::NoProgBase)?;
+        let functions = get_functions(runner);
+        let builtin_segments: HashMap<usize, &String> = runner
+            .vm
+            .builtin_runners
+            .iter()
+            .filter_map(|(name, builtin)| Some((builtin.base()?.segment_index, name)))
+            .collect();
+        let function_at = |pc: &Relocatable| -> String {
+            if pc.segment_index != program_base.segment_index {
+                return UNKNOWN_FUNCTION.to_string();
+            }
+            let index = functions.partition_point(|(start, _)| *start <= pc.offset);
+            match index.checked_sub(1) {
+                Some(index) => functions[index].1.clone(),
+                None => UNKNOWN_FUNCTION.to_string(),
+            }
+        };
+
+        let mut run_context = RunContext {
+            pc: MaybeRelocatable::from((0, 0)),
+            ap: MaybeRelocatable::from((0, 0)),
+            fp: MaybeRelocatable::from((0, 0)),
+            prime: runner.vm.prime.clone(),
+        };
+        let mut profile = Profile::default();
+        for entry in trace.iter() {
+            let mut stack = vec![function_at(&entry.pc)];
+            for return_pc in get_return_pcs(&runner.vm.memory, &entry.fp) {
+                if return_pc.segment_index != program_base.segment_index {
+                    break;
+                }
+                stack.push(function_at(&return_pc));
+            }
+            stack.reverse();
+
+            let mut counted: Vec<&String> = Vec::new();
+            for name in stack.iter() {
+                // Recursive functions count each step once
+                if !counted.contains(&name) {
+                    profile.function(name).inclusive_steps += 1;
+                    counted.push(name);
+                }
+            }
+            let current = profile.function(&stack[stack.len() - 1]);
+            current.self_steps += 1;
+            for address in get_operand_addresses(&runner.vm, &mut run_context, entry) {
+                if let Some(builtin_name) = builtin_segments.get(&address.segment_index) {
+                    *current
+                        .builtin_accesses
+                        .entry(builtin_name.to_string())
+                        .or_default() += 1;
+                }
+            }
+            *profile.stacks.entry(stack).or_default() += 1;
+        }
+        Ok(profile)
+    }
+
+    fn function(&mut self, name: &str) -> &mut FunctionProfile {
+        self.functions.entry(name.to_string()).or_default()
+    }
+
+    pub fn write(&self, out: &mut dyn io::Write, format: ProfileFormat) -> io::Result<()> {
+        match format {
+            ProfileFormat::Folded => self.write_folded_stacks(out),
+            ProfileFormat::Summary => self.write_summary(out),
+        }
+    }
+
+    ///Writes one `outer;inner count` line per call stack, as read by flamegraph tools.
+    pub fn write_folded_stacks(&self, out: &mut dyn io::Write) -> io::Result<()> {
+        let mut stacks: Vec<(String, usize)> = self
+            .stacks
+            .iter()
+            .map(|(stack, steps)| (stack.join(";"), *steps))
+            .collect();
+        stacks.sort();
+        for (stack, steps) in stacks {
+            writeln!(out, "{} {}", stack, steps)?;
+        }
+        Ok(())
+    }
+
+    ///Writes a table with the steps and builtin usage of each function, sorted by inclusive steps.
+    pub fn write_summary(&self, out: &mut dyn io::Write) -> io::Result<()> {
+        let mut functions: Vec<(&String, &FunctionProfile)> = self.functions.iter().collect();
+        functions.sort_by(|(a_name, a), (b_name, b)| {
+            b.inclusive_steps
+                .cmp(&a.inclusive_steps)
+                .then(a_name.cmp(b_name))
+        });
+        writeln!(
+            out,
+            "{:>10} {:>10}  {:<50} builtins",
+            "inclusive", "self", "function"
+        )?;
+        for (name, function) in functions {
+            let builtins: Vec<String> = function
+                .builtin_accesses
+                .iter()
+                .map(|(builtin, accesses)| format!("{}={}", builtin, accesses))
+                .collect();
+            let line = format!(
+                "{:>10} {:>10}  {:<50} {}",
+                function.inclusive_steps,
+                function.self_steps,
+                name,
+                builtins.join(" ")
+            );
+            writeln!(out, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+// Start pcs and names of the program's functions, sorted by pc.
+fn get_functions(runner: &CairoRunner) -> Vec<(usize, String)> {
+    let mut functions: Vec<(usize, String)> = runner
+        .get_program()
+        .identifiers
+        .iter()
+        .filter_map(|(name, identifier)| match identifier.type_.as_deref() {
+            Some("function") => Some((identifier.pc?, name.clone())),
+            _ => None,
+        })
+        .collect();
+    functions.sort();
+    functions
+}
+
+// Return pcs of the frames in the call chain of `fp`, from the innermost caller outwards.
+fn get_return_pcs(memory: &Memory, fp: &Relocatable) -> Vec<Relocatable> {
+    let mut return_pcs = Vec::new();
+    let mut fp = fp.clone();
+    while fp.offset >= 2 {
+        let return_pc = get_relocatable(memory, &fp, -1);
+        let caller_fp = get_relocatable(memory, &fp, -2);
+        match (return_pc, caller_fp) {
+            // Frames grow upwards, so a caller's fp that doesn't go down ends the chain
+            (Some(return_pc), Some(caller_fp))
+                if caller_fp.segment_index == fp.segment_index && caller_fp.offset < fp.offset =>
+            {
+                return_pcs.push(return_pc);
+                fp = caller_fp;
+            }
+            (Some(return_pc), _) => {
+                return_pcs.push(return_pc);
+                break;
+            }
+            _ => break,
+        }
+    }
+    return_pcs
+}
+
+fn get_relocatable(memory: &Memory, base: &Relocatable, offset: isize) -> Option<Relocatable> {
+    let address = add_offset(base, offset)?;
+    match memory.get(&MaybeRelocatable::RelocatableValue(address)) {
+        Ok(Some(MaybeRelocatable::RelocatableValue(value))) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn add_offset(base: &Relocatable, offset: isize) -> Option<Relocatable> {
+    Some(Relocatable {
+        segment_index: base.segment_index,
+        offset: usize::try_from(base.offset as isize + offset).ok()?,
+    })
+}
+
+// Memory addresses of the dst, op0 and op1 operands of the instruction executed at a step,
+// computed by the VM from the registers of the step.
+fn get_operand_addresses(
+    vm: &VirtualMachine,
+    run_context: &mut RunContext,
+    entry: &TraceEntry,
+) -> Vec<Relocatable> {
+    run_context.pc = MaybeRelocatable::RelocatableValue(entry.pc.clone());
+    run_context.ap = MaybeRelocatable::RelocatableValue(entry.ap.clone());
+    run_context.fp = MaybeRelocatable::RelocatableValue(entry.fp.clone());
+    let instruction = match vm.decode_instruction_at(&run_context.pc) {
+        Ok(instruction) => instruction,
+        Err(_) => return Vec::new(),
+    };
+    let dst_addr = run_context.compute_dst_addr(&instruction).ok();
+    let op0_addr = run_context.compute_op0_addr(&instruction).ok();
+    let op0 = op0_addr
+        .as_ref()
+        .and_then(|op0_addr| vm.memory.get(op0_addr).ok().flatten());
+    let op1_addr = run_context.compute_op1_addr(&instruction, op0).ok();
+    [dst_addr, op0_addr, op1_addr]
+        .into_iter()
+        .flatten()
+        .filter_map(|address| match address {
+            MaybeRelocatable::RelocatableValue(address) => Some(address),
+            MaybeRelocatable::Int(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde::deserialize_program::Identifier;
+    use crate::types::program::Program;
+    use crate::vm::decoding::assembler::assemble;
+    use num_bigint::{BigInt, Sign};
+
+    fn function(pc: usize) -> Identifier {
+        Identifier {
+            pc: Some(pc),
+            type_: Some(String::from("function")),
+            value: None,
+            full_name: None,
+            members: None,
+            cairo_type: None,
+        }
+    }
+
+    fn run_program(code: &str, builtins: Vec<String>, functions: &[(&str, usize)]) -> CairoRunner {
+        let prime = BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]);
+        let program = Program {
+            builtins,
+            data: assemble(code, &prime).unwrap(),
+            prime,
+            main: Some(0),
+            identifiers: functions
+                .iter()
+                .map(|(name, pc)| (name.to_string(), function(*pc)))
+                .collect(),
+            ..Default::default()
+        };
+        let mut cairo_runner = CairoRunner::new(&program, true).unwrap();
+        cairo_runner.initialize_segments(None);
+        let end = cairo_runner.initialize_main_entrypoint().unwrap();
+        cairo_runner.initialize_vm().unwrap();
+        cairo_runner.run_until_pc(end).unwrap();
+        cairo_runner
+    }
+
+    #[test]
+    fn profile_function_call() {
+        let runner = run_program(
+            "call rel 3\nret\n[ap + 0] = 1; ap++\nret\n",
+            vec![],
+            &[("__main__.main", 0), ("__main__.foo", 3)],
+        );
+        let profile = Profile::new(&runner).unwrap();
+
+        let main = &profile.functions["__main__.main"];
+        assert_eq!((main.self_steps, main.inclusive_steps), (2, 4));
+        let foo = &profile.functions["__main__.foo"];
+        assert_eq!((foo.self_steps, foo.inclusive_steps), (2, 2));
+
+        let mut out = Vec::new();
+        profile.write_folded_stacks(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "__main__.main 2\n__main__.main;__main__.foo 2\n"
+        );
+    }
+
+    #[test]
+    fn profile_builtin_accesses() {
+        // Writes 5 into the first range check cell, whose pointer main receives at [fp - 3]
+        let runner = run_program(
+            "[ap + 0] = 5; ap++\n[ap + -1] = [[fp + -3] + 0]\nret\n",
+            vec![String::from("range_check")],
+            &[("__main__.main", 0)],
+        );
+        let profile = Profile::new(&runner).unwrap();
+
+        let main = &profile.functions["__main__.main"];
+        assert_eq!(main.self_steps, 3);
+        assert_eq!(
+            main.builtin_accesses,
+            BTreeMap::from([(String::from("range_check"), 1)])
+        );
+
+        let mut out = Vec::new();
+        profile.write_summary(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out
+            .lines()
+            .nth(1)
+            .unwrap()
+            .ends_with("__main__.main                                      range_check=1"));
+    }
+
+    #[test]
+    fn profile_without_trace() {
+        let program = Program::new(std::path::Path::new(
+            "cairo_programs/manually_compiled/valid_program_a.json",
+        ))
+        .unwrap();
+        let runner = CairoRunner::new(&program, false).unwrap();
+        assert_eq!(
+            Profile::new(&runner),
+            Err(RunnerError::Trace(TraceError::TraceNotEnabled))
+        );
+    }
+}
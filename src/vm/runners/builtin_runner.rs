@@ -16,7 +16,7 @@ pub struct RangeCheckBuiltinRunner {
     included: bool,
     _ratio: BigInt,
     base: Option<Relocatable>,
-    _stop_ptr: Option<Relocatable>,
+    stop_ptr: Option<Relocatable>,
     _cells_per_instance: i32,
     _n_input_cells: i32,
     _inner_rc_bound: BigInt,
@@ -26,7 +26,7 @@ pub struct RangeCheckBuiltinRunner {
 pub struct OutputBuiltinRunner {
     included: bool,
     base: Option<Relocatable>,
-    _stop_ptr: Option<Relocatable>,
+    stop_ptr: Option<Relocatable>,
 }
 
 pub struct HashBuiltinRunner {
@@ -35,7 +35,7 @@ pub struct HashBuiltinRunner {
     _ratio: usize,
     cells_per_instance: usize,
     _n_input_cells: usize,
-    _stop_ptr: Option<Relocatable>,
+    stop_ptr: Option<Relocatable>,
     verified_addresses: Vec<MaybeRelocatable>,
 }
 
@@ -43,6 +43,7 @@ pub struct BitwiseBuiltinRunner {
     included: bool,
     _ratio: usize,
     pub base: Option<Relocatable>,
+    stop_ptr: Option<Relocatable>,
     cells_per_instance: usize,
     _n_input_cells: usize,
     total_n_bits: u32,
@@ -52,6 +53,7 @@ pub struct EcOpBuiltinRunner {
     included: bool,
     _ratio: usize,
     pub base: Option<Relocatable>,
+    stop_ptr: Option<Relocatable>,
     cells_per_instance: usize,
     n_input_cells: usize,
     scalar_height: usize,
@@ -65,15 +67,74 @@ pub trait BuiltinRunner {
     fn initial_stack(&self) -> Result<Vec<MaybeRelocatable>, RunnerError>;
     ///Returns the builtin's base
     fn base(&self) -> Option<Relocatable>;
-    fn add_validation_rule(&self, memory: &mut Memory);
+    fn add_validation_rule(&self, memory: &mut Memory) -> Result<(), RunnerError>;
     fn deduce_memory_cell(
         &mut self,
         address: &MaybeRelocatable,
         memory: &Memory,
     ) -> Result<Option<MaybeRelocatable>, RunnerError>;
+    ///Returns true if the builtin is included in the program's builtins
+    fn included(&self) -> bool;
+    ///Reads the builtin's stop pointer from the execution stack, right before pointer, and checks
+    ///that it points to the end of the used cells of the builtin's segment.
+    ///Builtins that aren't included don't have a stop pointer on the stack, and stop at their base.
+    ///Returns the pointer to the return values that precede it
+    fn final_stack(
+        &mut self,
+        segments: &MemorySegmentManager,
+        memory: &Memory,
+        pointer: Relocatable,
+    ) -> Result<Relocatable, RunnerError> {
+        if self.included() {
+            let stop_ptr = read_stop_ptr(&self.base(), segments, memory, &pointer)?;
+            self.set_stop_ptr(Some(stop_ptr));
+            Ok(Relocatable::from((
+                pointer.segment_index,
+                pointer.offset - 1,
+            )))
+        } else {
+            self.set_stop_ptr(self.base());
+            Ok(pointer)
+        }
+    }
+    ///Returns the builtin's stop pointer, which is None until final_stack is called
+    fn stop_ptr(&self) -> Option<Relocatable>;
+    ///Sets the builtin's stop pointer, used by final_stack
+    fn set_stop_ptr(&mut self, stop_ptr: Option<Relocatable>);
     fn as_any(&self) -> &dyn Any;
 }
 
+//Reads the stop pointer of the builtin with the given base, stored right before pointer
+fn read_stop_ptr(
+    base: &Option<Relocatable>,
+    segments: &MemorySegmentManager,
+    memory: &Memory,
+    pointer: &Relocatable,
+) -> Result<Relocatable, RunnerError> {
+    let base = base.as_ref().ok_or(RunnerError::UninitializedBase)?;
+    let stop_ptr_addr = pointer
+        .offset
+        .checked_sub(1)
+        .map(|offset| Relocatable::from((pointer.segment_index, offset)))
+        .ok_or_else(|| {
+            RunnerError::MemoryGet(MaybeRelocatable::RelocatableValue(pointer.clone()))
+        })?;
+    let stop_ptr = memory.get_relocatable(&stop_ptr_addr).map_err(|_| {
+        RunnerError::MemoryGet(MaybeRelocatable::RelocatableValue(stop_ptr_addr.clone()))
+    })?;
+    let used_size = segments
+        .get_segment_used_size(base.segment_index)
+        .unwrap_or(0);
+    let expected_stop_ptr = Relocatable::from((base.segment_index, base.offset + used_size));
+    if stop_ptr != &expected_stop_ptr {
+        return Err(RunnerError::InvalidStopPointer(
+            expected_stop_ptr,
+            stop_ptr.clone(),
+        ));
+    }
+    Ok(stop_ptr.clone())
+}
+
 impl RangeCheckBuiltinRunner {
     pub fn new(included: bool, ratio: BigInt, n_parts: u32) -> RangeCheckBuiltinRunner {
         let inner_rc_bound = bigint!(1i32 << 16);
@@ -81,7 +142,7 @@ impl RangeCheckBuiltinRunner {
             included,
             _ratio: ratio,
             base: None,
-            _stop_ptr: None,
+            stop_ptr: None,
             _cells_per_instance: 1,
             _n_input_cells: 1,
             _inner_rc_bound: inner_rc_bound.clone(),
@@ -112,14 +173,14 @@ impl BuiltinRunner for RangeCheckBuiltinRunner {
         self.base.clone()
     }
 
-    fn add_validation_rule(&self, memory: &mut Memory) {
+    fn add_validation_rule(&self, memory: &mut Memory) -> Result<(), RunnerError> {
         let rule: ValidationRule = ValidationRule(Box::new(
             |memory: &Memory,
              address: &MaybeRelocatable|
-             -> Result<MaybeRelocatable, MemoryError> {
+             -> Result<Vec<MaybeRelocatable>, MemoryError> {
                 if let Some(MaybeRelocatable::Int(ref num)) = memory.get(address)? {
                     if &BigInt::zero() <= num && num < &BigInt::one().shl(128u8) {
-                        Ok(address.to_owned())
+                        Ok(vec![address.to_owned()])
                     } else {
                         Err(MemoryError::NumOutOfBounds)
                     }
@@ -128,7 +189,9 @@ impl BuiltinRunner for RangeCheckBuiltinRunner {
                 }
             },
         ));
-        memory.add_validation_rule(self.base.as_ref().unwrap().segment_index, rule);
+        let base = self.base.as_ref().ok_or(RunnerError::UninitializedBase)?;
+        memory.add_validation_rule(base.segment_index, rule);
+        Ok(())
     }
 
     fn deduce_memory_cell(
@@ -139,6 +202,18 @@ impl BuiltinRunner for RangeCheckBuiltinRunner {
         Ok(None)
     }
 
+    fn included(&self) -> bool {
+        self.included
+    }
+
+    fn set_stop_ptr(&mut self, stop_ptr: Option<Relocatable>) {
+        self.stop_ptr = stop_ptr;
+    }
+
+    fn stop_ptr(&self) -> Option<Relocatable> {
+        self.stop_ptr.clone()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -149,7 +224,7 @@ impl OutputBuiltinRunner {
         OutputBuiltinRunner {
             included,
             base: None,
-            _stop_ptr: None,
+            stop_ptr: None,
         }
     }
 }
@@ -177,7 +252,9 @@ impl BuiltinRunner for OutputBuiltinRunner {
         self.base.clone()
     }
 
-    fn add_validation_rule(&self, _memory: &mut Memory) {}
+    fn add_validation_rule(&self, _memory: &mut Memory) -> Result<(), RunnerError> {
+        Ok(())
+    }
 
     fn deduce_memory_cell(
         &mut self,
@@ -187,6 +264,18 @@ impl BuiltinRunner for OutputBuiltinRunner {
         Ok(None)
     }
 
+    fn included(&self) -> bool {
+        self.included
+    }
+
+    fn set_stop_ptr(&mut self, stop_ptr: Option<Relocatable>) {
+        self.stop_ptr = stop_ptr;
+    }
+
+    fn stop_ptr(&self) -> Option<Relocatable> {
+        self.stop_ptr.clone()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -200,7 +289,7 @@ impl HashBuiltinRunner {
             _ratio: ratio,
             cells_per_instance: 3,
             _n_input_cells: 2,
-            _stop_ptr: None,
+            stop_ptr: None,
             verified_addresses: Vec::new(),
         }
     }
@@ -229,7 +318,9 @@ impl BuiltinRunner for HashBuiltinRunner {
         self.base.clone()
     }
 
-    fn add_validation_rule(&self, _memory: &mut Memory) {}
+    fn add_validation_rule(&self, _memory: &mut Memory) -> Result<(), RunnerError> {
+        Ok(())
+    }
 
     fn deduce_memory_cell(
         &mut self,
@@ -282,6 +373,18 @@ impl BuiltinRunner for HashBuiltinRunner {
         }
     }
 
+    fn included(&self) -> bool {
+        self.included
+    }
+
+    fn set_stop_ptr(&mut self, stop_ptr: Option<Relocatable>) {
+        self.stop_ptr = stop_ptr;
+    }
+
+    fn stop_ptr(&self) -> Option<Relocatable> {
+        self.stop_ptr.clone()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -291,6 +394,7 @@ impl BitwiseBuiltinRunner {
     pub fn new(included: bool, ratio: usize) -> Self {
         BitwiseBuiltinRunner {
             base: None,
+            stop_ptr: None,
             included,
             _ratio: ratio,
             cells_per_instance: 5,
@@ -323,7 +427,9 @@ impl BuiltinRunner for BitwiseBuiltinRunner {
         self.base.clone()
     }
 
-    fn add_validation_rule(&self, _memory: &mut Memory) {}
+    fn add_validation_rule(&self, _memory: &mut Memory) -> Result<(), RunnerError> {
+        Ok(())
+    }
 
     fn deduce_memory_cell(
         &mut self,
@@ -344,20 +450,20 @@ impl BuiltinRunner for BitwiseBuiltinRunner {
             ) = (memory.get(&x_addr), memory.get(&y_addr))
             {
                 let _2_pow_bits = bigint!(1).shl(self.total_n_bits);
-                assert!(
-                    num_x < &_2_pow_bits,
-                    "Expected integer at address {:?} to be smaller than 2^{}, Got {}",
-                    x_addr,
-                    self.total_n_bits,
-                    num_x
-                );
-                assert!(
-                    num_y < &_2_pow_bits,
-                    "Expected integer at address {:?} to be smaller than 2^{}, Got {}",
-                    y_addr,
-                    self.total_n_bits,
-                    num_y
-                );
+                if num_x >= &_2_pow_bits {
+                    return Err(RunnerError::IntegerBiggerThanPowerOfTwo(
+                        x_addr,
+                        self.total_n_bits,
+                        num_x.clone(),
+                    ));
+                }
+                if num_y >= &_2_pow_bits {
+                    return Err(RunnerError::IntegerBiggerThanPowerOfTwo(
+                        y_addr,
+                        self.total_n_bits,
+                        num_y.clone(),
+                    ));
+                }
                 let res = match index {
                     2 => Some(MaybeRelocatable::from(num_x & num_y)),
                     3 => Some(MaybeRelocatable::from(num_x ^ num_y)),
@@ -372,6 +478,18 @@ impl BuiltinRunner for BitwiseBuiltinRunner {
         }
     }
 
+    fn included(&self) -> bool {
+        self.included
+    }
+
+    fn set_stop_ptr(&mut self, stop_ptr: Option<Relocatable>) {
+        self.stop_ptr = stop_ptr;
+    }
+
+    fn stop_ptr(&self) -> Option<Relocatable> {
+        self.stop_ptr.clone()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -382,6 +500,7 @@ impl EcOpBuiltinRunner {
         EcOpBuiltinRunner {
             included,
             base: None,
+            stop_ptr: None,
             _ratio: ratio,
             n_input_cells: 5,
             cells_per_instance: 7,
@@ -418,21 +537,22 @@ impl EcOpBuiltinRunner {
         alpha: &BigInt,
         prime: &BigInt,
         height: usize,
-    ) -> (BigInt, BigInt) {
+    ) -> Result<(BigInt, BigInt), RunnerError> {
         let mut slope = m.clone();
         for _ in 0..height {
-            assert!((doubled_point.0.clone() - partial_sum.0.clone())% prime != bigint!(0), "Cannot apply EC operation: computation reched two points with the same x coordinate. \n 
-            Attempting to compute P + m * Q where:\n
-            P = {:?} \n
-            m = {}\n
-            Q = {:?}.", partial_sum,m, doubled_point);
+            if (doubled_point.0.clone() - partial_sum.0.clone()) % prime == bigint!(0) {
+                return Err(RunnerError::EcOpSameXCoordinate(format!(
+                    "partial_sum: {:?}, m: {}, doubled_point: {:?}",
+                    partial_sum, m, doubled_point
+                )));
+            }
             if slope.clone() & bigint!(1) != bigint!(0) {
                 partial_sum = ec_add(partial_sum, doubled_point.clone(), prime);
             }
             doubled_point = ec_double(doubled_point, alpha, prime);
             slope = slope.clone() >> 1_i32;
         }
-        partial_sum
+        Ok(partial_sum)
     }
 }
 
@@ -458,7 +578,9 @@ impl BuiltinRunner for EcOpBuiltinRunner {
     fn base(&self) -> Option<Relocatable> {
         self.base.clone()
     }
-    fn add_validation_rule(&self, _memory: &mut Memory) {}
+    fn add_validation_rule(&self, _memory: &mut Memory) -> Result<(), RunnerError> {
+        Ok(())
+    }
 
     fn deduce_memory_cell(
         &mut self,
@@ -514,17 +636,15 @@ impl BuiltinRunner for EcOpBuiltinRunner {
 
             // Assert that if the current address is part of a point, the point is on the curve
             for pair in &EC_POINT_INDICES[0..1] {
-                assert!(
-                    EcOpBuiltinRunner::point_on_curve(
-                        input_cells[pair.0],
-                        input_cells[pair.1],
-                        &alpha,
-                        &beta,
-                        &field_prime
-                    ),
-                    "EcOpBuiltin: point {:?} is not on the curve",
-                    pair
-                );
+                if !EcOpBuiltinRunner::point_on_curve(
+                    input_cells[pair.0],
+                    input_cells[pair.1],
+                    &alpha,
+                    &beta,
+                    &field_prime,
+                ) {
+                    return Err(RunnerError::PointNotOnCurve(*pair));
+                }
             }
             let result = EcOpBuiltinRunner::ec_op_impl(
                 (input_cells[0].clone(), input_cells[1].clone()),
@@ -533,7 +653,7 @@ impl BuiltinRunner for EcOpBuiltinRunner {
                 &alpha,
                 &field_prime,
                 self.scalar_height,
-            );
+            )?;
             match index - self.n_input_cells {
                 0 => Ok(Some(MaybeRelocatable::Int(result.0))),
                 _ => Ok(Some(MaybeRelocatable::Int(result.1))),
@@ -544,6 +664,18 @@ impl BuiltinRunner for EcOpBuiltinRunner {
         }
     }
 
+    fn included(&self) -> bool {
+        self.included
+    }
+
+    fn set_stop_ptr(&mut self, stop_ptr: Option<Relocatable>) {
+        self.stop_ptr = stop_ptr;
+    }
+
+    fn stop_ptr(&self) -> Option<Relocatable> {
+        self.stop_ptr.clone()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -555,6 +687,59 @@ mod tests {
     use crate::vm::vm_memory::memory::Memory;
     use crate::{bigint, bigint_str, relocatable};
 
+    #[test]
+    fn final_stack_reads_stop_ptr() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory, None);
+        builtin.initialize_segments(&mut segments, &mut memory);
+        for (address, value) in [
+            ((1, 0), MaybeRelocatable::from(bigint!(4))),
+            ((1, 1), MaybeRelocatable::from(bigint!(5))),
+            ((0, 2), MaybeRelocatable::from((1, 2))),
+        ] {
+            memory
+                .insert(&MaybeRelocatable::from(address), &value)
+                .unwrap();
+        }
+        segments.compute_effective_sizes(&memory);
+        assert_eq!(
+            builtin.final_stack(&segments, &memory, relocatable!(0, 3)),
+            Ok(relocatable!(0, 2))
+        );
+        assert_eq!(builtin.stop_ptr(), Some(relocatable!(1, 2)));
+    }
+
+    #[test]
+    fn final_stack_invalid_stop_ptr() {
+        let mut builtin = RangeCheckBuiltinRunner::new(true, bigint!(8), 8);
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory, None);
+        builtin.initialize_segments(&mut segments, &mut memory);
+        for (address, value) in [
+            ((1, 0), MaybeRelocatable::from(bigint!(4))),
+            ((0, 0), MaybeRelocatable::from((1, 0))),
+        ] {
+            memory
+                .insert(&MaybeRelocatable::from(address), &value)
+                .unwrap();
+        }
+        segments.compute_effective_sizes(&memory);
+        assert_eq!(
+            builtin.final_stack(&segments, &memory, relocatable!(0, 1)),
+            Err(RunnerError::InvalidStopPointer(
+                relocatable!(1, 1),
+                relocatable!(1, 0)
+            ))
+        );
+        assert_eq!(
+            builtin.final_stack(&segments, &memory, relocatable!(0, 0)),
+            Err(RunnerError::MemoryGet(MaybeRelocatable::from((0, 0))))
+        );
+    }
+
     #[test]
     fn initialize_segments_for_output() {
         let mut builtin = OutputBuiltinRunner::new(true);
@@ -1032,14 +1217,14 @@ mod tests {
             EcOpBuiltinRunner::ec_op_impl(partial_sum, doubled_point, &m, &alpha, &prime, height);
         assert_eq!(
             result,
-            (
+            Ok((
                 bigint_str!(
                     b"1977874238339000383330315148209250828062304908491266318460063803060754089297"
                 ),
                 bigint_str!(
                     b"2969386888251099938335087541720168257053975603483053253007176033556822156706"
                 )
-            )
+            ))
         );
     }
 
@@ -1071,14 +1256,35 @@ mod tests {
             EcOpBuiltinRunner::ec_op_impl(partial_sum, doubled_point, &m, &alpha, &prime, height);
         assert_eq!(
             result,
-            (
+            Ok((
                 bigint_str!(
                     b"2778063437308421278851140253538604815869848682781135193774472480292420096757"
                 ),
                 bigint_str!(
                     b"3598390311618116577316045819420613574162151407434885460365915347732568210029"
                 )
-            )
+            ))
+        );
+    }
+
+    #[test]
+    fn compute_ec_op_impl_same_x_coordinate() {
+        let point = (bigint!(3), bigint!(4));
+        let alpha = bigint!(1);
+        let prime = bigint!(17);
+        assert_eq!(
+            EcOpBuiltinRunner::ec_op_impl(
+                point.clone(),
+                point.clone(),
+                &bigint!(1),
+                &alpha,
+                &prime,
+                1
+            ),
+            Err(RunnerError::EcOpSameXCoordinate(format!(
+                "partial_sum: {:?}, m: 1, doubled_point: {:?}",
+                point, point
+            )))
         );
     }
 
@@ -14,6 +14,7 @@ use crate::vm::runners::builtin_runner::{
 };
 use crate::vm::trace::trace_entry::{relocate_trace_register, RelocatedTraceEntry};
 use crate::vm::vm_core::{HintData, VirtualMachine};
+use crate::vm::vm_memory::memory_segments::CairoArg;
 use num_bigint::BigInt;
 use num_traits::FromPrimitive;
 use std::collections::HashMap;
@@ -34,7 +35,7 @@ pub struct CairoRunner {
 }
 
 impl CairoRunner {
-    pub fn new(program: &Program, trace_enabled: bool) -> CairoRunner {
+    pub fn new(program: &Program, trace_enabled: bool) -> Result<CairoRunner, RunnerError> {
         let builtin_ordered_list = vec![
             String::from("output"),
             String::from("pedersen"),
@@ -43,10 +44,9 @@ impl CairoRunner {
             String::from("bitwise"),
             String::from("ec_op"),
         ];
-        assert!(
-            is_subsequence(&program.builtins, &builtin_ordered_list),
-            "Given builtins are not in appropiate order"
-        );
+        if !is_subsequence(&program.builtins, &builtin_ordered_list) {
+            return Err(RunnerError::DisorderedBuiltins);
+        }
         let mut builtin_runners = Vec::<(String, Box<dyn BuiltinRunner>)>::new();
         for builtin_name in program.builtins.iter() {
             if builtin_name == "output" {
@@ -84,7 +84,7 @@ impl CairoRunner {
             }
         }
         //Initialize a vm, with empty values, will later be filled with actual data in initialize_vm
-        CairoRunner {
+        Ok(CairoRunner {
             program: program.clone(),
             _layout: String::from("plain"),
             vm: VirtualMachine::new(program.prime.clone(), builtin_runners, trace_enabled),
@@ -96,7 +96,7 @@ impl CairoRunner {
             initial_pc: None,
             relocated_memory: Vec::new(),
             relocated_trace: None,
-        }
+        })
     }
     ///Creates the necessary segments for the program, execution, and each builtin on the MemorySegmentManager and stores the first adress of each of this new segments as each owner's base
     pub fn initialize_segments(&mut self, program_base: Option<Relocatable>) {
@@ -171,6 +171,27 @@ impl CairoRunner {
         self.final_pc = Some(end.clone());
         Ok(MaybeRelocatable::RelocatableValue(end))
     }
+    ///Initializes state for running the function at entrypoint with the given arguments.
+    ///Lists are written into new segments (as segments.gen_arg does) and passed as pointers.
+    ///Returns the value of the program counter after returning from the function.
+    pub fn initialize_function_entrypoint_with_args(
+        &mut self,
+        entrypoint: usize,
+        args: &[CairoArg],
+    ) -> Result<MaybeRelocatable, RunnerError> {
+        let mut stack = Vec::new();
+        for arg in args {
+            stack.push(
+                self.vm
+                    .segments
+                    .gen_arg(&mut self.vm.memory, arg, Some(&self.vm.prime))
+                    .map_err(RunnerError::MemoryInitializationError)?,
+            );
+        }
+        let return_fp = MaybeRelocatable::from(bigint!(0));
+        self.initialize_function_entrypoint(entrypoint, stack, return_fp)
+    }
+
     ///Initializes state for running a program from the main() entrypoint.
     ///If self.proof_mode == True, the execution starts from the start label rather then the main() function.
     ///Returns the value of the program counter after returning from main.
@@ -215,10 +236,12 @@ impl CairoRunner {
             None => return Err(RunnerError::NoProgBase),
         }
         for (_, builtin) in self.vm.builtin_runners.iter() {
-            builtin.add_validation_rule(&mut self.vm.memory);
+            builtin.add_validation_rule(&mut self.vm.memory)?;
         }
         self.vm.hints = self.get_hint_dictionary()?;
         self.vm.references = self.get_reference_list();
+        self.vm.constants = self.program.constants.clone();
+        self.vm.struct_types = self.program.struct_types.clone();
         match self.vm.memory.validate_existing_memory() {
             Err(error) => Err(RunnerError::MemoryValidationError(error)),
             Ok(_) => Ok(()),
@@ -228,23 +251,35 @@ impl CairoRunner {
         let mut references = HashMap::<usize, HintReference>::new();
 
         for (i, reference) in self.program.reference_manager.references.iter().enumerate() {
-            if let Some(register) = &reference.value_address.register {
-                references.insert(
-                    i,
-                    HintReference {
-                        register: register.clone(),
-                        offset1: reference.value_address.offset1,
-                        offset2: reference.value_address.offset2,
-                        inner_dereference: reference.value_address.inner_dereference,
-                        // only store `ap` tracking data if the reference is referred to it
-                        ap_tracking_data: if register == &Register::FP {
-                            None
-                        } else {
-                            Some(reference.ap_tracking_data.clone())
-                        },
+            let hint_reference = match reference.value.value_address() {
+                Ok(value_address) => HintReference {
+                    // only store `ap` tracking data if the reference is referred to it
+                    ap_tracking_data: match value_address.register {
+                        Some(Register::AP) => Some(reference.ap_tracking_data.clone()),
+                        _ => None,
                     },
-                );
-            }
+                    register: value_address.register,
+                    offset1: value_address.offset1,
+                    offset2: value_address.offset2,
+                    inner_dereference: value_address.inner_dereference,
+                    dereference: value_address.dereference,
+                    cairo_type: value_address.value_type,
+                    value: Some(reference.value.clone()),
+                },
+                //References that can't be reduced to a register and offsets are kept without a
+                //register, so that hints fail when they resolve them
+                Err(_) => HintReference {
+                    register: None,
+                    offset1: 0,
+                    offset2: 0,
+                    inner_dereference: false,
+                    ap_tracking_data: None,
+                    dereference: false,
+                    cairo_type: None,
+                    value: Some(reference.value.clone()),
+                },
+            };
+            references.insert(i, hint_reference);
         }
         references
     }
@@ -262,6 +297,7 @@ impl CairoRunner {
                         hint_data.code.clone(),
                         hint_data.flow_tracking_data.reference_ids.clone(),
                         hint_data.flow_tracking_data.ap_tracking.clone(),
+                        hint_data.accessible_scopes.clone(),
                     ));
                 } else {
                     //Insert the first hint at a given pc
@@ -273,6 +309,7 @@ impl CairoRunner {
                                 &hint_data.flow_tracking_data.reference_ids,
                             )?,
                             hint_data.flow_tracking_data.ap_tracking.clone(),
+                            hint_data.accessible_scopes.clone(),
                         )],
                     );
                 }
@@ -295,6 +332,27 @@ impl CairoRunner {
         Ok(reference_ids_new)
     }
 
+    pub fn get_program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn get_program_base(&self) -> Option<Relocatable> {
+        self.program_base.clone()
+    }
+
+    ///Reads the stop pointers of the builtins, returned by main at the end of the execution stack
+    pub fn read_return_values(&mut self) -> Result<(), RunnerError> {
+        let mut pointer = match &self.vm.run_context.ap {
+            MaybeRelocatable::RelocatableValue(ap) => ap.clone(),
+            MaybeRelocatable::Int(_) => return Err(RunnerError::NonRelocatableAddress),
+        };
+        self.vm.segments.compute_effective_sizes(&self.vm.memory);
+        for (_name, builtin) in self.vm.builtin_runners.iter_mut().rev() {
+            pointer = builtin.final_stack(&self.vm.segments, &self.vm.memory, pointer)?;
+        }
+        Ok(())
+    }
+
     pub fn run_until_pc(&mut self, address: MaybeRelocatable) -> Result<(), VirtualMachineError> {
         while self.vm.run_context.pc != address {
             self.vm.step()?;
@@ -302,6 +360,18 @@ impl CairoRunner {
         Ok(())
     }
 
+    ///Finishes the run. If the memory uses lazy_validation, the cells written during the run are
+    /// validated here, before the auto deductions of the builtins are verified
+    pub fn end_run(&mut self) -> Result<(), VirtualMachineError> {
+        if self.vm.memory.lazy_validation {
+            self.vm
+                .memory
+                .validate_existing_memory()
+                .map_err(VirtualMachineError::MemoryError)?;
+        }
+        self.vm.verify_auto_deductions()
+    }
+
     ///Relocates the VM's memory, turning bidimensional indexes into contiguous numbers, and values into BigInts
     /// Uses the relocation_table to asign each index a number according to the value on its segment number
     fn relocate_memory(&mut self, relocation_table: &Vec<usize>) -> Result<(), MemoryError> {
@@ -351,15 +421,28 @@ impl CairoRunner {
         Ok(())
     }
 
+    ///Returns the number of memory cells that were not accessed during the run.
+    ///Builtin segments are not taken into account, as all of their cells are considered accessed.
+    pub fn get_memory_holes(&mut self) -> Result<usize, MemoryError> {
+        let builtin_segments: Vec<usize> = self
+            .vm
+            .builtin_runners
+            .iter()
+            .filter_map(|(_name, builtin)| builtin.base())
+            .map(|base| base.segment_index)
+            .collect();
+        self.vm
+            .segments
+            .get_memory_holes(&self.vm.memory, &builtin_segments)
+    }
+
     pub fn relocate(&mut self) -> Result<(), TraceError> {
         self.vm.segments.compute_effective_sizes(&self.vm.memory);
-        // relocate_segments can fail if compute_effective_sizes is not called before.
-        // The expect should be unreachable.
         let relocation_table = self
             .vm
             .segments
             .relocate_segments()
-            .expect("compute_effective_sizes called but relocate_memory still returned error");
+            .map_err(TraceError::MemoryError)?;
         if let Err(memory_error) = self.relocate_memory(&relocation_table) {
             return Err(TraceError::MemoryError(memory_error));
         }
@@ -369,6 +452,65 @@ impl CairoRunner {
         Ok(())
     }
 
+    ///Returns the role of each memory segment: program, execution, the name of its builtin,
+    ///dict, or user-added for the remaining segments
+    pub fn get_segment_names(&self) -> HashMap<usize, String> {
+        let mut segment_names = HashMap::new();
+        for segment_index in 0..self.vm.segments.num_segments {
+            segment_names.insert(segment_index, String::from("user-added"));
+        }
+        for segment_index in self.vm.dict_manager.trackers.keys() {
+            segment_names.insert(*segment_index, String::from("dict"));
+        }
+        for (name, builtin) in self.vm.builtin_runners.iter() {
+            if let Some(base) = builtin.base() {
+                segment_names.insert(base.segment_index, name.clone());
+            }
+        }
+        if let Some(base) = &self.execution_base {
+            segment_names.insert(base.segment_index, String::from("execution"));
+        }
+        if let Some(base) = &self.program_base {
+            segment_names.insert(base.segment_index, String::from("program"));
+        }
+        segment_names
+    }
+
+    ///Writes the contents of the memory, segment by segment
+    pub fn write_memory(&self, out: &mut dyn io::Write) -> Result<(), RunnerError> {
+        let mut dump = String::new();
+        self.vm
+            .memory
+            .dump(&mut dump, &self.get_segment_names(), Some(&self.vm.prime))
+            .map_err(|_| RunnerError::WriteFail)?;
+        out.write_all(dump.as_bytes())
+            .map_err(|_| RunnerError::WriteFail)
+    }
+
+    ///Writes the role, used size and number of accessed cells of each memory segment
+    pub fn write_segments(&mut self, out: &mut dyn io::Write) -> Result<(), RunnerError> {
+        let segment_names = self.get_segment_names();
+        self.vm.segments.compute_effective_sizes(&self.vm.memory);
+        for segment_index in 0..self.vm.segments.num_segments {
+            writeln!(
+                out,
+                "segment {} ({}): size {}, accessed {}",
+                segment_index,
+                segment_names[&segment_index],
+                self.vm
+                    .segments
+                    .get_segment_size(segment_index)
+                    .unwrap_or(0),
+                self.vm
+                    .memory
+                    .get_amount_of_accessed_addresses_for_segment(segment_index)
+                    .unwrap_or(0)
+            )
+            .map_err(|_| RunnerError::WriteFail)?;
+        }
+        Ok(())
+    }
+
     pub fn get_output(&mut self) -> Result<Option<String>, RunnerError> {
         let mut output = Vec::<u8>::new();
         self.write_output(&mut output)?;
@@ -382,21 +524,19 @@ impl CairoRunner {
         //If the output builtin is present it will always be the first one
         if !self.vm.builtin_runners.is_empty() && self.vm.builtin_runners[0].0 == *"output" {
             let builtin = &self.vm.builtin_runners[0].1;
-            self.vm.segments.compute_effective_sizes(&self.vm.memory);
-
             let base = match builtin.base() {
                 Some(base) => base,
                 None => return Err(RunnerError::UninitializedBase),
             };
 
-            // After this if block,
-            // segment_used_sizes is always Some(_)
-            if self.vm.segments.segment_used_sizes == None {
-                self.vm.segments.compute_effective_sizes(&self.vm.memory);
-            }
+            self.vm.segments.compute_effective_sizes(&self.vm.memory);
+            let segment_used_size = self
+                .vm
+                .segments
+                .get_segment_used_size(base.segment_index)
+                .ok_or(RunnerError::UninitializedBase)?;
 
-            // See previous comment, the unwrap below is safe.
-            for i in 0..self.vm.segments.segment_used_sizes.as_ref().unwrap()[base.segment_index] {
+            for i in 0..segment_used_size {
                 let value =
                     match self.vm.memory.get(
                         &MaybeRelocatable::RelocatableValue(base.clone()).add_usize_mod(i, None),
@@ -426,26 +566,23 @@ mod tests {
     use num_bigint::Sign;
 
     use super::*;
-    use crate::serde::deserialize_program::ReferenceManager;
     use crate::vm::trace::trace_entry::TraceEntry;
     use crate::{bigint64, bigint_str, relocatable};
     use std::collections::HashMap;
+    use std::path::Path;
 
     #[test]
-    #[should_panic]
     fn create_cairo_runner_with_disordered_builtins() {
         //This test works with basic Program definition, will later be updated to use Program::new() when fully defined
         let program = Program {
             builtins: vec![String::from("range_check"), String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let _cairo_runner = CairoRunner::new(&program, false);
+        assert_eq!(
+            CairoRunner::new(&program, false).err(),
+            Some(RunnerError::DisorderedBuiltins)
+        );
     }
 
     #[test]
@@ -454,15 +591,10 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output"), String::from("ecdsa")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
         //We only check that the creation doesnt panic
-        let _cairo_runner = CairoRunner::new(&program, false);
+        let _cairo_runner = CairoRunner::new(&program, false).unwrap();
     }
 
     #[test]
@@ -471,14 +603,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         let program_base = Some(Relocatable {
             segment_index: 5,
             offset: 9,
@@ -514,14 +641,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initialize_segments(None);
         assert_eq!(
             cairo_runner.program_base,
@@ -552,14 +674,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.program_base = Some(relocatable!(1, 0));
         cairo_runner.execution_base = Some(relocatable!(2, 0));
         let stack = Vec::new();
@@ -583,13 +700,9 @@ mod tests {
                 MaybeRelocatable::from(bigint!(4)),
                 MaybeRelocatable::from(bigint!(6)),
             ],
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         for _ in 0..2 {
             cairo_runner
                 .vm
@@ -629,14 +742,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         for _ in 0..3 {
             cairo_runner
                 .vm
@@ -677,14 +785,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         for _ in 0..2 {
             cairo_runner
                 .vm
@@ -709,14 +812,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         for _ in 0..2 {
             cairo_runner
                 .vm
@@ -737,14 +835,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         for _ in 0..2 {
             cairo_runner
                 .vm
@@ -784,14 +877,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         for _ in 0..2 {
             cairo_runner
                 .vm
@@ -833,6 +921,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn initialize_function_entrypoint_with_args() {
+        let program = Program {
+            prime: bigint!(17),
+            ..Default::default()
+        };
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
+        cairo_runner.initialize_segments(None);
+        //f(-1, [3, 4])
+        let args = vec![
+            CairoArg::from(bigint!(-1)),
+            CairoArg::from(vec![bigint!(3), bigint!(4)]),
+        ];
+        assert_eq!(
+            cairo_runner.initialize_function_entrypoint_with_args(0, &args),
+            Ok(MaybeRelocatable::from((3, 0)))
+        );
+        //The list is written into its own segment
+        assert_eq!(
+            cairo_runner.vm.memory.data[2],
+            vec![
+                Some(MaybeRelocatable::from(bigint!(3))),
+                Some(MaybeRelocatable::from(bigint!(4)))
+            ]
+        );
+        //stack: args (reduced modulo prime), return_fp and the end pc
+        assert_eq!(
+            cairo_runner.vm.memory.data[1],
+            vec![
+                Some(MaybeRelocatable::from(bigint!(16))),
+                Some(MaybeRelocatable::from((2, 0))),
+                Some(MaybeRelocatable::from(bigint!(0))),
+                Some(MaybeRelocatable::from((3, 0))),
+            ]
+        );
+        assert_eq!(cairo_runner.initial_fp, Some(relocatable!(1, 4)));
+    }
+
     #[test]
     #[should_panic]
     fn initialize_function_entrypoint_no_execution_base() {
@@ -840,14 +966,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         let stack = vec![MaybeRelocatable::from(bigint!(7))];
         let return_fp = MaybeRelocatable::from(bigint!(9));
         cairo_runner
@@ -862,14 +983,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initialize_main_entrypoint().unwrap();
     }
 
@@ -877,16 +993,11 @@ mod tests {
     fn initialize_main_entrypoint() {
         //This test works with basic Program definition, will later be updated to use Program::new() when fully defined
         let program = Program {
-            builtins: Vec::new(),
             prime: bigint!(17),
-            data: Vec::new(),
             main: Some(1),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.program_base = Some(relocatable!(0, 0));
         cairo_runner.execution_base = Some(relocatable!(0, 0));
         let return_pc = cairo_runner.initialize_main_entrypoint().unwrap();
@@ -897,16 +1008,11 @@ mod tests {
     fn initialize_vm_no_builtins() {
         //This test works with basic Program definition, will later be updated to use Program::new() when fully defined
         let program = Program {
-            builtins: Vec::new(),
             prime: bigint!(17),
-            data: Vec::new(),
             main: Some(1),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.program_base = Some(relocatable!(0, 0));
         cairo_runner.initial_pc = Some(relocatable!(0, 1));
         cairo_runner.initial_ap = Some(relocatable!(1, 2));
@@ -936,14 +1042,10 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("range_check")],
             prime: bigint!(17),
-            data: Vec::new(),
             main: Some(1),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initial_pc = Some(relocatable!(0, 1));
         cairo_runner.initial_ap = Some(relocatable!(1, 2));
         cairo_runner.initial_fp = Some(relocatable!(1, 2));
@@ -993,14 +1095,10 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("range_check")],
             prime: bigint!(17),
-            data: Vec::new(),
             main: Some(1),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initial_pc = Some(relocatable!(0, 1));
         cairo_runner.initial_ap = Some(relocatable!(1, 2));
         cairo_runner.initial_fp = Some(relocatable!(1, 2));
@@ -1044,7 +1142,6 @@ mod tests {
     */
     fn initialization_phase_no_builtins() {
         let program = Program {
-            builtins: vec![],
             prime: bigint!(17),
             data: vec![
                 MaybeRelocatable::from(BigInt::from_i64(5207990763031199744).unwrap()),
@@ -1063,12 +1160,9 @@ mod tests {
                 MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
             ],
             main: Some(3),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initialize_segments(None);
         cairo_runner.initialize_main_entrypoint().unwrap();
         cairo_runner.initialize_vm().unwrap();
@@ -1228,12 +1322,9 @@ mod tests {
                 MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
             ],
             main: Some(4),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initialize_segments(None);
         cairo_runner.initialize_main_entrypoint().unwrap();
         cairo_runner.initialize_vm().unwrap();
@@ -1431,12 +1522,9 @@ mod tests {
                 MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
             ],
             main: Some(8),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initialize_segments(None);
         cairo_runner.initialize_main_entrypoint().unwrap();
         cairo_runner.initialize_vm().unwrap();
@@ -1647,7 +1735,6 @@ mod tests {
     fn initialize_and_run_function_call() {
         //Initialization Phase
         let program = Program {
-            builtins: vec![],
             prime: BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
             data: vec![
                 MaybeRelocatable::from(BigInt::from_i64(5207990763031199744).unwrap()),
@@ -1666,12 +1753,9 @@ mod tests {
                 MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
             ],
             main: Some(3),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, true);
+        let mut cairo_runner = CairoRunner::new(&program, true).unwrap();
         cairo_runner.initialize_segments(None);
         let end = cairo_runner.initialize_main_entrypoint().unwrap();
         assert_eq!(end, MaybeRelocatable::from((3, 0)));
@@ -1834,12 +1918,9 @@ mod tests {
                 MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
             ],
             main: Some(8),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, true);
+        let mut cairo_runner = CairoRunner::new(&program, true).unwrap();
         cairo_runner.initialize_segments(None);
         let end = cairo_runner.initialize_main_entrypoint().unwrap();
         cairo_runner.initialize_vm().unwrap();
@@ -2128,12 +2209,9 @@ mod tests {
                 MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
             ],
             main: Some(4),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, true);
+        let mut cairo_runner = CairoRunner::new(&program, true).unwrap();
         cairo_runner.initialize_segments(None);
         let end = cairo_runner.initialize_main_entrypoint().unwrap();
         cairo_runner.initialize_vm().unwrap();
@@ -2395,6 +2473,105 @@ mod tests {
         );
     }
 
+    #[test]
+    fn write_memory_and_segments_with_roles() {
+        let program = Program {
+            builtins: vec![String::from("output")],
+            prime: bigint!(17),
+            data: vec![MaybeRelocatable::from(bigint!(16))],
+            main: Some(0),
+            ..Default::default()
+        };
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
+        cairo_runner.initialize_segments(None);
+        cairo_runner.initialize_main_entrypoint().unwrap();
+        cairo_runner
+            .vm
+            .dict_manager
+            .new_dict(
+                &mut cairo_runner.vm.segments,
+                &mut cairo_runner.vm.memory,
+                HashMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            cairo_runner.get_segment_names(),
+            HashMap::from([
+                (0, String::from("program")),
+                (1, String::from("execution")),
+                (2, String::from("output")),
+                (3, String::from("user-added")),
+                (4, String::from("user-added")),
+                (5, String::from("dict")),
+            ])
+        );
+
+        let mut memory_dump = Vec::<u8>::new();
+        cairo_runner.write_memory(&mut memory_dump).unwrap();
+        assert_eq!(
+            String::from_utf8(memory_dump),
+            Ok(String::from(
+                "segment 0 (program):\n  0:0 = 16 (-1)\nsegment 1 (execution):\n  1:0 = 2:0\n  1:1 = 3:0\n  1:2 = 4:0\nsegment 2 (output):\nsegment 3 (user-added):\nsegment 4 (user-added):\nsegment 5 (dict):\n"
+            ))
+        );
+
+        let mut segments = Vec::<u8>::new();
+        cairo_runner.write_segments(&mut segments).unwrap();
+        assert_eq!(
+            String::from_utf8(segments),
+            Ok(String::from(
+                "segment 0 (program): size 1, accessed 0\nsegment 1 (execution): size 3, accessed 0\nsegment 2 (output): size 0, accessed 0\nsegment 3 (user-added): size 0, accessed 0\nsegment 4 (user-added): size 0, accessed 0\nsegment 5 (dict): size 0, accessed 0\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn get_memory_holes_output_builtin() {
+        let program = Program {
+            builtins: vec![String::from("output")],
+            prime: BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            data: vec![
+                MaybeRelocatable::from(BigInt::from_i64(4612671182993129469).unwrap()),
+                MaybeRelocatable::from(BigInt::from_i64(5198983563776393216).unwrap()),
+                MaybeRelocatable::from(bigint!(1)),
+                MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
+                MaybeRelocatable::from(BigInt::from_i64(5191102247248822272).unwrap()),
+                MaybeRelocatable::from(BigInt::from_i64(5189976364521848832).unwrap()),
+                MaybeRelocatable::from(bigint!(1)),
+                MaybeRelocatable::from(BigInt::from_i64(1226245742482522112).unwrap()),
+                MaybeRelocatable::from(bigint_str!(
+                    b"3618502788666131213697322783095070105623107215331596699973092056135872020474"
+                )),
+                MaybeRelocatable::from(BigInt::from_i64(5189976364521848832).unwrap()),
+                MaybeRelocatable::from(bigint!(17)),
+                MaybeRelocatable::from(BigInt::from_i64(1226245742482522112).unwrap()),
+                MaybeRelocatable::from(bigint_str!(
+                    b"3618502788666131213697322783095070105623107215331596699973092056135872020470"
+                )),
+                MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
+            ],
+            main: Some(4),
+            ..Default::default()
+        };
+        let mut cairo_runner = CairoRunner::new(&program, true).unwrap();
+        cairo_runner.initialize_segments(None);
+        let end = cairo_runner.initialize_main_entrypoint().unwrap();
+        cairo_runner.initialize_vm().unwrap();
+        assert_eq!(cairo_runner.run_until_pc(end), Ok(()));
+        //Every cell used by the program was accessed
+        assert_eq!(cairo_runner.get_memory_holes(), Ok(0));
+        //A cell written past ap leaves a gap, and neither of them was accessed
+        cairo_runner
+            .vm
+            .memory
+            .insert(
+                &MaybeRelocatable::from((1, 13)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+        assert_eq!(cairo_runner.get_memory_holes(), Ok(2));
+    }
+
     #[test]
     /*Program used:
     %builtins output range_check
@@ -2479,12 +2656,9 @@ mod tests {
                 MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
             ],
             main: Some(13),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, true);
+        let mut cairo_runner = CairoRunner::new(&program, true).unwrap();
         cairo_runner.initialize_segments(None);
         let end = cairo_runner.initialize_main_entrypoint().unwrap();
         cairo_runner.initialize_vm().unwrap();
@@ -2902,16 +3076,10 @@ mod tests {
     */
     fn relocate_memory_with_gap() {
         let program = Program {
-            builtins: Vec::new(),
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, true);
+        let mut cairo_runner = CairoRunner::new(&program, true).unwrap();
         for _ in 0..4 {
             cairo_runner
                 .vm
@@ -3062,12 +3230,9 @@ mod tests {
                 MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
             ],
             main: Some(4),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initialize_segments(None);
         let end = cairo_runner.initialize_main_entrypoint().unwrap();
         cairo_runner.initialize_vm().unwrap();
@@ -3198,12 +3363,9 @@ mod tests {
                 MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
             ],
             main: Some(4),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, true);
+        let mut cairo_runner = CairoRunner::new(&program, true).unwrap();
         cairo_runner.initialize_segments(None);
         let end = cairo_runner.initialize_main_entrypoint().unwrap();
         cairo_runner.initialize_vm().unwrap();
@@ -3323,14 +3485,9 @@ mod tests {
         let program = Program {
             builtins: vec![String::from("output")],
             prime: bigint!(17),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initialize_segments(None);
         assert_eq!(cairo_runner.vm.builtin_runners[0].0, String::from("output"));
         assert_eq!(
@@ -3396,12 +3553,9 @@ mod tests {
                 MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
             ],
             main: Some(4),
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initialize_segments(None);
         let end = cairo_runner.initialize_main_entrypoint().unwrap();
         cairo_runner.initialize_vm().unwrap();
@@ -3419,14 +3573,9 @@ mod tests {
             prime: bigint_str!(
                 b"3618502788666131213697322783095070105623107215331596699973092056135872020481"
             ),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let mut cairo_runner = CairoRunner::new(&program, false);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
         cairo_runner.initialize_segments(None);
         assert_eq!(cairo_runner.vm.builtin_runners[0].0, String::from("output"));
         assert_eq!(
@@ -3467,14 +3616,9 @@ mod tests {
             prime: bigint_str!(
                 b"3618502788666131213697322783095070105623107215331596699973092056135872020481"
             ),
-            data: Vec::new(),
-            main: None,
-            hints: HashMap::new(),
-            reference_manager: ReferenceManager {
-                references: Vec::new(),
-            },
+            ..Default::default()
         };
-        let cairo_runner = CairoRunner::new(&program, false);
+        let cairo_runner = CairoRunner::new(&program, false).unwrap();
         assert_eq!(cairo_runner.vm.builtin_runners[0].0, String::from("output"));
         assert_eq!(
             cairo_runner.vm.builtin_runners[1].0,
@@ -3490,4 +3634,28 @@ mod tests {
         );
         assert_eq!(cairo_runner.vm.builtin_runners[4].0, String::from("ec_op"));
     }
+
+    #[test]
+    fn end_run_validates_memory_with_lazy_validation() {
+        let mut program = Program::new(Path::new(
+            "cairo_programs/manually_compiled/valid_program_b.json",
+        ))
+        .unwrap();
+        //main calls check_range(7), which writes its argument to the range_check segment.
+        //Replace it with 2**128, which is out of the range_check bounds
+        program.data[15] = MaybeRelocatable::from(bigint!(1) << 128_usize);
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
+        cairo_runner.vm.memory.lazy_validation = true;
+        cairo_runner.initialize_segments(None);
+        let end = cairo_runner.initialize_main_entrypoint().unwrap();
+        cairo_runner.initialize_vm().unwrap();
+
+        assert_eq!(cairo_runner.run_until_pc(end), Ok(()));
+        assert_eq!(
+            cairo_runner.end_run(),
+            Err(VirtualMachineError::MemoryError(
+                MemoryError::NumOutOfBounds
+            ))
+        );
+    }
 }
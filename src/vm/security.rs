@@ -0,0 +1,151 @@
+use crate::vm::errors::runner_errors::RunnerError;
+use crate::vm::runners::cairo_runner::CairoRunner;
+
+///Verifies that a finished run is safe to trust:
+/// * The program segment wasn't written beyond the program data.
+/// * The builtin segments weren't written beyond their stop pointers.
+/// * The builtin cells are consistent with their auto deduction rules.
+///
+///The builtin stop pointers must have been read beforehand (see CairoRunner::read_return_values)
+pub fn verify_secure_runner(runner: &mut CairoRunner) -> Result<(), RunnerError> {
+    let program_base = runner.get_program_base().ok_or(RunnerError::NoProgBase)?;
+    let program_length = program_base.offset + runner.get_program().data.len();
+    let program_segment_size = segment_size(runner, program_base.segment_index);
+    if program_segment_size > program_length {
+        return Err(RunnerError::OutOfBoundsProgramSegmentAccess(
+            program_segment_size,
+            program_length,
+        ));
+    }
+
+    for (name, builtin) in runner.vm.builtin_runners.iter() {
+        let stop_ptr = builtin
+            .stop_ptr()
+            .ok_or_else(|| RunnerError::NoStopPointer(name.clone()))?;
+        let builtin_segment_size = segment_size(runner, stop_ptr.segment_index);
+        if builtin_segment_size > stop_ptr.offset {
+            return Err(RunnerError::OutOfBoundsBuiltinSegmentAccess(
+                name.clone(),
+                builtin_segment_size,
+                stop_ptr.offset,
+            ));
+        }
+    }
+
+    runner
+        .vm
+        .verify_auto_deductions()
+        .map_err(|error| RunnerError::FailedAutoDeductions(Box::new(error)))
+}
+
+//Returns the highest written offset + 1 of the given segment
+fn segment_size(runner: &CairoRunner, segment_index: usize) -> usize {
+    runner
+        .vm
+        .memory
+        .data
+        .get(segment_index)
+        .map_or(0, |segment| segment.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::program::Program;
+    use crate::types::relocatable::MaybeRelocatable;
+    use crate::{bigint, bigint_str};
+    use num_bigint::{BigInt, Sign};
+    use num_traits::FromPrimitive;
+
+    //Runs a program that writes 1 and 17 into the output builtin
+    fn run_output_program() -> CairoRunner {
+        let program = Program {
+            builtins: vec![String::from("output")],
+            prime: BigInt::new(Sign::Plus, vec![1, 0, 0, 0, 0, 0, 17, 134217728]),
+            data: vec![
+                MaybeRelocatable::from(BigInt::from_i64(4612671182993129469).unwrap()),
+                MaybeRelocatable::from(BigInt::from_i64(5198983563776393216).unwrap()),
+                MaybeRelocatable::from(bigint!(1)),
+                MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
+                MaybeRelocatable::from(BigInt::from_i64(5191102247248822272).unwrap()),
+                MaybeRelocatable::from(BigInt::from_i64(5189976364521848832).unwrap()),
+                MaybeRelocatable::from(bigint!(1)),
+                MaybeRelocatable::from(BigInt::from_i64(1226245742482522112).unwrap()),
+                MaybeRelocatable::from(bigint_str!(
+                    b"3618502788666131213697322783095070105623107215331596699973092056135872020474"
+                )),
+                MaybeRelocatable::from(BigInt::from_i64(5189976364521848832).unwrap()),
+                MaybeRelocatable::from(bigint!(17)),
+                MaybeRelocatable::from(BigInt::from_i64(1226245742482522112).unwrap()),
+                MaybeRelocatable::from(bigint_str!(
+                    b"3618502788666131213697322783095070105623107215331596699973092056135872020470"
+                )),
+                MaybeRelocatable::from(BigInt::from_i64(2345108766317314046).unwrap()),
+            ],
+            main: Some(4),
+            ..Default::default()
+        };
+        let mut cairo_runner = CairoRunner::new(&program, false).unwrap();
+        cairo_runner.initialize_segments(None);
+        let end = cairo_runner.initialize_main_entrypoint().unwrap();
+        cairo_runner.initialize_vm().unwrap();
+        cairo_runner.run_until_pc(end).unwrap();
+        cairo_runner
+    }
+
+    #[test]
+    fn verify_secure_runner_valid_run() {
+        let mut cairo_runner = run_output_program();
+        assert_eq!(cairo_runner.read_return_values(), Ok(()));
+        assert_eq!(verify_secure_runner(&mut cairo_runner), Ok(()));
+    }
+
+    #[test]
+    fn verify_secure_runner_write_to_program_segment() {
+        let mut cairo_runner = run_output_program();
+        cairo_runner.read_return_values().unwrap();
+        cairo_runner
+            .vm
+            .memory
+            .insert(
+                &MaybeRelocatable::from((0, 15)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+        assert_eq!(
+            verify_secure_runner(&mut cairo_runner),
+            Err(RunnerError::OutOfBoundsProgramSegmentAccess(16, 14))
+        );
+    }
+
+    #[test]
+    fn verify_secure_runner_write_beyond_builtin_stop_ptr() {
+        let mut cairo_runner = run_output_program();
+        cairo_runner.read_return_values().unwrap();
+        cairo_runner
+            .vm
+            .memory
+            .insert(
+                &MaybeRelocatable::from((2, 2)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+        assert_eq!(
+            verify_secure_runner(&mut cairo_runner),
+            Err(RunnerError::OutOfBoundsBuiltinSegmentAccess(
+                String::from("output"),
+                3,
+                2
+            ))
+        );
+    }
+
+    #[test]
+    fn verify_secure_runner_without_stop_ptrs() {
+        let mut cairo_runner = run_output_program();
+        assert_eq!(
+            verify_secure_runner(&mut cairo_runner),
+            Err(RunnerError::NoStopPointer(String::from("output")))
+        );
+    }
+}
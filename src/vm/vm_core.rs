@@ -1,5 +1,5 @@
 use crate::bigint;
-use crate::serde::deserialize_program::ApTracking;
+use crate::serde::deserialize_program::{ApTracking, Member};
 use crate::types::exec_scope::ExecutionScopes;
 use crate::types::instruction::{ApUpdate, FpUpdate, Instruction, Opcode, PcUpdate, Res};
 use crate::types::relocatable::MaybeRelocatable;
@@ -37,6 +37,8 @@ pub struct HintData {
     //Maps the name of the variable to its reference id
     pub ids: HashMap<String, BigInt>,
     pub ap_tracking_data: ApTracking,
+    //Scopes from which the hint's ids are resolved, from the outermost to the innermost one
+    pub accessible_scopes: Vec<String>,
 }
 
 pub struct VirtualMachine {
@@ -50,6 +52,12 @@ pub struct VirtualMachine {
     //enter_scope:
     pub hints: HashMap<MaybeRelocatable, Vec<HintData>>,
     pub references: HashMap<usize, HintReference>,
+    //Constants defined in the program (const identifiers), by full name
+    pub constants: HashMap<String, BigInt>,
+    //Accessible scopes of the hint being executed, used to resolve the constants it references
+    pub accessible_scopes: Vec<String>,
+    //Members of the structs defined in the program, by struct full name
+    pub struct_types: HashMap<String, HashMap<String, Member>>,
     //hint_locals: HashMap<..., ...>,
     //hint_pc_and_index: HashMap<i64, (MaybeRelocatable, i64)>,
     //static_locals: Option<HashMap<..., ...>>,
@@ -58,10 +66,10 @@ pub struct VirtualMachine {
     //error_message_attributes: Vec<VmAttributeScope>,
     //program: ProgramBase,
     //auto_deduction: HashMap<BigInt, Vec<(Rule, ())>>,
-    //Some(accessed_addresses) == proof mode enabled
-    accessed_addresses: Option<Vec<MaybeRelocatable>>,
     //None if trace is not enabled, Some otherwise
     pub trace: Option<Vec<TraceEntry>>,
+    //Decoded instructions of the program segment, by pc offset
+    instruction_cache: Vec<Option<Instruction>>,
     current_step: usize,
     skip_instruction_execution: bool,
     pub dict_manager: DictManager,
@@ -72,11 +80,13 @@ impl HintData {
         hint_code: Vec<u8>,
         ids: HashMap<String, BigInt>,
         ap_tracking_data: ApTracking,
+        accessible_scopes: Vec<String>,
     ) -> HintData {
         HintData {
             hint_code,
             ids,
             ap_tracking_data,
+            accessible_scopes,
         }
     }
 }
@@ -106,10 +116,13 @@ impl VirtualMachine {
             builtin_runners,
             hints: HashMap::<MaybeRelocatable, Vec<HintData>>::new(),
             references: HashMap::<usize, HintReference>::new(),
+            constants: HashMap::<String, BigInt>::new(),
+            accessible_scopes: Vec::new(),
+            struct_types: HashMap::new(),
             _program_base: None,
             memory: Memory::new(),
-            accessed_addresses: None,
             trace,
+            instruction_cache: Vec::new(),
             current_step: 0,
             skip_instruction_execution: false,
             segments: MemorySegmentManager::new(),
@@ -120,13 +133,14 @@ impl VirtualMachine {
     ///Returns the encoded instruction (the value at pc) and the immediate value (the value at pc + 1, if it exists in the memory).
     fn get_instruction_encoding(
         &self,
+        pc: &MaybeRelocatable,
     ) -> Result<(&BigInt, Option<&MaybeRelocatable>), VirtualMachineError> {
-        let encoding_ref: &BigInt = match self.memory.get(&self.run_context.pc) {
+        let encoding_ref: &BigInt = match self.memory.get(pc) {
             Ok(Some(MaybeRelocatable::Int(ref encoding))) => encoding,
             _ => return Err(VirtualMachineError::InvalidInstructionEncoding),
         };
 
-        let imm_addr = self.run_context.pc.add_usize_mod(1, None);
+        let imm_addr = pc.add_usize_mod(1, None);
 
         if let Ok(optional_imm) = self.memory.get(&imm_addr) {
             Ok((encoding_ref, optional_imm))
@@ -200,12 +214,12 @@ impl VirtualMachine {
 
     fn update_registers(
         &mut self,
-        instruction: Instruction,
+        instruction: &Instruction,
         operands: Operands,
     ) -> Result<(), VirtualMachineError> {
-        self.update_fp(&instruction, &operands);
-        self.update_ap(&instruction, &operands)?;
-        self.update_pc(&instruction, &operands)?;
+        self.update_fp(instruction, &operands);
+        self.update_ap(instruction, &operands)?;
+        self.update_pc(instruction, &operands)?;
         Ok(())
     }
 
@@ -437,9 +451,9 @@ impl VirtualMachine {
         }
     }
 
-    fn run_instruction(&mut self, instruction: Instruction) -> Result<(), VirtualMachineError> {
-        let (operands, operands_mem_addresses) = self.compute_operands(&instruction)?;
-        self.opcode_assertions(&instruction, &operands)?;
+    fn run_instruction(&mut self, instruction: &Instruction) -> Result<(), VirtualMachineError> {
+        let (operands, operands_mem_addresses) = self.compute_operands(instruction)?;
+        self.opcode_assertions(instruction, &operands)?;
 
         if let Some(ref mut trace) = &mut self.trace {
             if let (RelocatableValue(ref pc), RelocatableValue(ref ap), RelocatableValue(ref fp)) = (
@@ -455,16 +469,15 @@ impl VirtualMachine {
             }
         }
 
-        if let Some(ref mut accessed_addresses) = self.accessed_addresses {
-            let op_addrs =
-                operands_mem_addresses.ok_or(VirtualMachineError::InvalidInstructionEncoding)?;
-            let addresses = &[
-                op_addrs.0,
-                op_addrs.1,
-                op_addrs.2,
-                self.run_context.pc.clone(),
-            ];
-            accessed_addresses.extend_from_slice(addresses);
+        let OperandsAddresses(dst_addr, op0_addr, op1_addr) = operands_mem_addresses;
+        for address in [dst_addr, op0_addr, op1_addr, self.run_context.pc.clone()] {
+            match address {
+                MaybeRelocatable::RelocatableValue(ref address) => self
+                    .memory
+                    .mark_as_accessed(address)
+                    .map_err(VirtualMachineError::MemoryError)?,
+                _ => return Err(VirtualMachineError::InvalidInstructionEncoding),
+            }
         }
 
         self.update_registers(instruction, operands)?;
@@ -473,7 +486,15 @@ impl VirtualMachine {
     }
 
     fn decode_current_instruction(&self) -> Result<Instruction, VirtualMachineError> {
-        let (instruction_ref, imm) = self.get_instruction_encoding()?;
+        self.decode_instruction_at(&self.run_context.pc)
+    }
+
+    ///Decodes the instruction stored in memory at pc, along with its immediate
+    pub fn decode_instruction_at(
+        &self,
+        pc: &MaybeRelocatable,
+    ) -> Result<Instruction, VirtualMachineError> {
+        let (instruction_ref, imm) = self.get_instruction_encoding(pc)?;
         match instruction_ref.to_i64() {
             Some(instruction) => {
                 if let Some(MaybeRelocatable::Int(imm_ref)) = imm {
@@ -491,6 +512,7 @@ impl VirtualMachine {
     pub fn step(&mut self) -> Result<(), VirtualMachineError> {
         if let Some(hint_list) = self.hints.get(&self.run_context.pc) {
             for hint_data in hint_list.clone().iter() {
+                self.accessible_scopes = hint_data.accessible_scopes.clone();
                 execute_hint(
                     self,
                     &hint_data.hint_code,
@@ -500,9 +522,36 @@ impl VirtualMachine {
             }
         }
         self.skip_instruction_execution = false;
-        let instruction = self.decode_current_instruction()?;
-        self.run_instruction(instruction)?;
-        Ok(())
+
+        // The program segment is write-once, so its instructions are only decoded once
+        let cache_index = self.get_instruction_cache_index();
+        let instruction = match cache_index
+            .and_then(|index| self.instruction_cache.get_mut(index))
+            .and_then(Option::take)
+        {
+            Some(instruction) => instruction,
+            None => self.decode_current_instruction()?,
+        };
+        let result = self.run_instruction(&instruction);
+        if let Some(index) = cache_index {
+            if self.instruction_cache.len() <= index {
+                self.instruction_cache.resize_with(index + 1, || None);
+            }
+            self.instruction_cache[index] = Some(instruction);
+        }
+        result
+    }
+
+    ///Returns the offset of pc if it points to the program segment, None otherwise
+    fn get_instruction_cache_index(&self) -> Option<usize> {
+        match (&self._program_base, &self.run_context.pc) {
+            (Some(RelocatableValue(program_base)), RelocatableValue(pc))
+                if pc.segment_index == program_base.segment_index =>
+            {
+                Some(pc.offset)
+            }
+            _ => None,
+        }
     }
 
     /// Compute operands and result, trying to deduce them if normal memory access returns a None
@@ -510,7 +559,7 @@ impl VirtualMachine {
     fn compute_operands(
         &mut self,
         instruction: &Instruction,
-    ) -> Result<(Operands, Option<OperandsAddresses>), VirtualMachineError> {
+    ) -> Result<(Operands, OperandsAddresses), VirtualMachineError> {
         let dst_addr: MaybeRelocatable = self.run_context.compute_dst_addr(instruction)?;
 
         let mut dst: Option<MaybeRelocatable> = match self.memory.get(&dst_addr) {
@@ -621,22 +670,15 @@ impl VirtualMachine {
         }
 
         match (dst, op0, op1) {
-            (Some(unwrapped_dst), Some(unwrapped_op0), Some(unwrapped_op1)) => {
-                let accessed_addresses = if self.accessed_addresses.is_some() {
-                    Some(OperandsAddresses(dst_addr, op0_addr, op1_addr))
-                } else {
-                    None
-                };
-                Ok((
-                    Operands {
-                        dst: unwrapped_dst,
-                        op0: unwrapped_op0,
-                        op1: unwrapped_op1,
-                        res,
-                    },
-                    accessed_addresses,
-                ))
-            }
+            (Some(unwrapped_dst), Some(unwrapped_op0), Some(unwrapped_op1)) => Ok((
+                Operands {
+                    dst: unwrapped_dst,
+                    op0: unwrapped_op0,
+                    op1: unwrapped_op1,
+                    res,
+                },
+                OperandsAddresses(dst_addr, op0_addr, op1_addr),
+            )),
             _ => Err(VirtualMachineError::InvalidInstructionEncoding),
         }
     }
@@ -695,7 +737,6 @@ mod tests {
     use crate::{bigint64, bigint_str};
     use crate::{relocatable, types::relocatable::Relocatable};
     use num_bigint::Sign;
-    use std::collections::HashSet;
 
     pub fn memory_from(
         key_val_list: Vec<(MaybeRelocatable, MaybeRelocatable)>,
@@ -722,7 +763,10 @@ mod tests {
                 &MaybeRelocatable::Int(bigint!(5)),
             )
             .unwrap();
-        assert_eq!(Ok((&bigint!(5), None)), vm.get_instruction_encoding());
+        assert_eq!(
+            Ok((&bigint!(5), None)),
+            vm.get_instruction_encoding(&vm.run_context.pc)
+        );
     }
 
     #[test]
@@ -743,7 +787,9 @@ mod tests {
                 &MaybeRelocatable::from(bigint!(6)),
             )
             .unwrap();
-        if let Ok((num_ref, Some(MaybeRelocatable::Int(imm_ref)))) = vm.get_instruction_encoding() {
+        if let Ok((num_ref, Some(MaybeRelocatable::Int(imm_ref)))) =
+            vm.get_instruction_encoding(&vm.run_context.pc)
+        {
             assert_eq!(num_ref.clone(), bigint!(5));
             assert_eq!(imm_ref.clone(), bigint!(6));
         } else {
@@ -755,7 +801,7 @@ mod tests {
     fn get_instruction_encoding_unsuccesful() {
         let mut vm = VirtualMachine::new(bigint!(39), Vec::new(), false);
         vm.run_context.pc = MaybeRelocatable::from((0, 0));
-        let error = vm.get_instruction_encoding();
+        let error = vm.get_instruction_encoding(&vm.run_context.pc);
         assert_eq!(error, Err(VirtualMachineError::InvalidInstructionEncoding));
         assert_eq!(
             error.unwrap_err().to_string(),
@@ -1367,7 +1413,7 @@ mod tests {
         vm.run_context.ap = MaybeRelocatable::Int(bigint!(5));
         vm.run_context.fp = MaybeRelocatable::Int(bigint!(6));
 
-        assert_eq!(Ok(()), vm.update_registers(instruction, operands));
+        assert_eq!(Ok(()), vm.update_registers(&instruction, operands));
         assert_eq!(vm.run_context.pc, MaybeRelocatable::Int(bigint!(5)));
         assert_eq!(vm.run_context.ap, MaybeRelocatable::Int(bigint!(5)));
         assert_eq!(vm.run_context.fp, MaybeRelocatable::Int(bigint!(6)));
@@ -1402,7 +1448,7 @@ mod tests {
         vm.run_context.ap = MaybeRelocatable::Int(bigint!(5));
         vm.run_context.fp = MaybeRelocatable::Int(bigint!(6));
 
-        assert_eq!(Ok(()), vm.update_registers(instruction, operands));
+        assert_eq!(Ok(()), vm.update_registers(&instruction, operands));
         assert_eq!(vm.run_context.pc, MaybeRelocatable::Int(bigint!(12)));
         assert_eq!(vm.run_context.ap, MaybeRelocatable::Int(bigint!(7)));
         assert_eq!(vm.run_context.fp, MaybeRelocatable::Int(bigint!(11)));
@@ -2120,7 +2166,6 @@ mod tests {
         };
 
         let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
-        vm.accessed_addresses = Some(Vec::new());
         vm.memory.data.push(Vec::new());
         let dst_addr = MaybeRelocatable::from((0, 0));
         let dst_addr_value = MaybeRelocatable::Int(bigint!(5));
@@ -2139,11 +2184,8 @@ mod tests {
             op1: op1_addr_value.clone(),
         };
 
-        let expected_addresses = Some(OperandsAddresses(
-            dst_addr.clone(),
-            op0_addr.clone(),
-            op1_addr.clone(),
-        ));
+        let expected_addresses =
+            OperandsAddresses(dst_addr.clone(), op0_addr.clone(), op1_addr.clone());
         let (operands, addresses) = vm.compute_operands(&inst).unwrap();
         assert!(operands == expected_operands);
         assert!(addresses == expected_addresses);
@@ -2166,7 +2208,6 @@ mod tests {
             opcode: Opcode::NOp,
         };
         let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
-        vm.accessed_addresses = Some(Vec::new());
         vm.memory.data.push(Vec::new());
         let dst_addr = MaybeRelocatable::from((0, 0));
         let dst_addr_value = MaybeRelocatable::from(bigint!(6));
@@ -2185,11 +2226,8 @@ mod tests {
             op1: op1_addr_value.clone(),
         };
 
-        let expected_addresses = Some(OperandsAddresses(
-            dst_addr.clone(),
-            op0_addr.clone(),
-            op1_addr.clone(),
-        ));
+        let expected_addresses =
+            OperandsAddresses(dst_addr.clone(), op0_addr.clone(), op1_addr.clone());
         let (operands, addresses) = vm.compute_operands(&inst).unwrap();
         assert!(operands == expected_operands);
         assert!(addresses == expected_addresses);
@@ -2224,7 +2262,6 @@ mod tests {
         ];
 
         let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
-        vm.accessed_addresses = Some(Vec::new());
         vm.memory = memory_from(mem_arr.clone(), 2).unwrap();
 
         let expected_operands = Operands {
@@ -2234,11 +2271,11 @@ mod tests {
             op1: MaybeRelocatable::Int(bigint64!(0x4)),
         };
 
-        let expected_addresses = Some(OperandsAddresses(
+        let expected_addresses = OperandsAddresses(
             MaybeRelocatable::from((0, 1)),
             MaybeRelocatable::from((0, 1)),
             MaybeRelocatable::from((0, 1)),
-        ));
+        );
 
         let (operands, addresses) = vm.compute_operands(&instruction).unwrap();
 
@@ -2441,9 +2478,12 @@ mod tests {
             builtin_runners: Vec::new(),
             hints: HashMap::<MaybeRelocatable, Vec<HintData>>::new(),
             references: HashMap::<usize, HintReference>::new(),
+            constants: HashMap::<String, BigInt>::new(),
+            accessible_scopes: Vec::new(),
+            struct_types: HashMap::new(),
             memory: Memory::new(),
-            accessed_addresses: Some(Vec::<MaybeRelocatable>::new()),
             trace: Some(Vec::<TraceEntry>::new()),
+            instruction_cache: Vec::new(),
             current_step: 1,
             skip_instruction_execution: false,
             segments: MemorySegmentManager::new(),
@@ -2485,7 +2525,6 @@ mod tests {
             Vec::new(),
             true,
         );
-        vm.accessed_addresses = Some(Vec::new());
         for _ in 0..4 {
             vm.memory.data.push(Vec::new());
         }
@@ -2532,10 +2571,9 @@ mod tests {
         assert_eq!(vm.run_context.pc, MaybeRelocatable::from((3, 0)));
         assert_eq!(vm.run_context.ap, MaybeRelocatable::from((1, 2)));
         assert_eq!(vm.run_context.fp, MaybeRelocatable::from((2, 0)));
-        let accessed_addresses = vm.accessed_addresses.as_ref().unwrap();
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((1, 0))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((1, 1))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((0, 0))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((1, 0))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((1, 1))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((0, 0))));
     }
 
     #[test]
@@ -2575,7 +2613,6 @@ mod tests {
             Vec::new(),
             true,
         );
-        vm.accessed_addresses = Some(Vec::new());
         for _ in 0..4 {
             vm.memory.data.push(Vec::new());
         }
@@ -2750,29 +2787,29 @@ mod tests {
             }
         );
         //Check accessed_addresses
-        //Order will differ from python vm execution, (due to python version using set's update() method)
-        //We will instead check that all elements are contained and not duplicated
-        let accessed_addresses = vm
-            .accessed_addresses
-            .unwrap()
-            .into_iter()
-            .collect::<HashSet<MaybeRelocatable>>();
-        assert_eq!(accessed_addresses.len(), 14);
-        //Check each element individually
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((0, 1))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((0, 7))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((1, 2))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((0, 4))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((0, 0))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((1, 5))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((1, 1))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((0, 3))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((1, 4))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((0, 6))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((0, 2))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((0, 5))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((1, 0))));
-        assert!(accessed_addresses.contains(&MaybeRelocatable::from((1, 3))));
+        //Each accessed cell is counted once, even if it was accessed by more than one instruction
+        assert_eq!(
+            vm.memory.get_amount_of_accessed_addresses_for_segment(0),
+            Some(8)
+        );
+        assert_eq!(
+            vm.memory.get_amount_of_accessed_addresses_for_segment(1),
+            Some(6)
+        );
+        assert!(vm.memory.is_accessed(&Relocatable::from((0, 1))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((0, 7))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((1, 2))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((0, 4))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((0, 0))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((1, 5))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((1, 1))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((0, 3))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((1, 4))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((0, 6))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((0, 2))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((0, 5))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((1, 0))));
+        assert!(vm.memory.is_accessed(&Relocatable::from((1, 3))));
     }
 
     #[test]
@@ -2891,6 +2928,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn step_caches_program_segment_instructions() {
+        //[ap + 0] = 5; ap++
+        let mem_arr = vec![
+            (
+                MaybeRelocatable::from((0, 0)),
+                MaybeRelocatable::Int(bigint64!(0x480680017fff8000)),
+            ),
+            (
+                MaybeRelocatable::from((0, 1)),
+                MaybeRelocatable::Int(bigint!(5)),
+            ),
+            (
+                MaybeRelocatable::from((1, 4)),
+                MaybeRelocatable::Int(bigint!(0)),
+            ),
+        ];
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        vm.memory = memory_from(mem_arr, 2).unwrap();
+        vm._program_base = Some(MaybeRelocatable::from((0, 0)));
+        vm.run_context.pc = MaybeRelocatable::from((0, 0));
+        vm.run_context.ap = MaybeRelocatable::from((1, 0));
+        vm.run_context.fp = MaybeRelocatable::from((1, 5));
+
+        assert_eq!(vm.step(), Ok(()));
+        assert_eq!(
+            vm.instruction_cache,
+            vec![Some(
+                decode_instruction(0x480680017fff8000, Some(bigint!(5))).unwrap()
+            )]
+        );
+
+        //The second run of the instruction uses the cached one
+        vm.run_context.pc = MaybeRelocatable::from((0, 0));
+        assert_eq!(vm.step(), Ok(()));
+        assert_eq!(vm.run_context.ap, MaybeRelocatable::from((1, 2)));
+        assert_eq!(
+            vm.memory.get(&MaybeRelocatable::from((1, 1))),
+            Ok(Some(&MaybeRelocatable::Int(bigint!(5))))
+        );
+        assert!(vm.instruction_cache[0].is_some());
+    }
+
+    #[test]
+    fn step_doesnt_cache_instructions_outside_program_segment() {
+        //[ap + 0] = 5; ap++
+        let mem_arr = vec![
+            (
+                MaybeRelocatable::from((0, 0)),
+                MaybeRelocatable::Int(bigint64!(0x480680017fff8000)),
+            ),
+            (
+                MaybeRelocatable::from((0, 1)),
+                MaybeRelocatable::Int(bigint!(5)),
+            ),
+            (
+                MaybeRelocatable::from((1, 4)),
+                MaybeRelocatable::Int(bigint!(0)),
+            ),
+        ];
+        let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
+        vm.memory = memory_from(mem_arr, 3).unwrap();
+        vm._program_base = Some(MaybeRelocatable::from((2, 0)));
+        vm.run_context.pc = MaybeRelocatable::from((0, 0));
+        vm.run_context.ap = MaybeRelocatable::from((1, 0));
+        vm.run_context.fp = MaybeRelocatable::from((1, 5));
+
+        assert_eq!(vm.step(), Ok(()));
+        assert!(vm.instruction_cache.is_empty());
+    }
+
     #[test]
     fn deduce_memory_cell_no_pedersen_builtin() {
         let mut vm = VirtualMachine::new(bigint!(17), Vec::new(), false);
@@ -2976,7 +3084,6 @@ mod tests {
         let mut builtin = HashBuiltinRunner::new(true, 8);
         builtin.base = Some(relocatable!(3, 0));
         let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
-        vm.accessed_addresses = Some(Vec::new());
         vm.builtin_runners
             .push((String::from("pedersen"), Box::new(builtin)));
         vm.run_context.ap = MaybeRelocatable::from((1, 13));
@@ -3099,11 +3206,11 @@ mod tests {
                 b"3270867057177188607814717243084834301278723532952411121381966378910183338911"
             )),
         };
-        let expected_operands_mem_addresses = Some(OperandsAddresses(
+        let expected_operands_mem_addresses = OperandsAddresses(
             MaybeRelocatable::from((1, 13)),
             MaybeRelocatable::from((1, 7)),
             MaybeRelocatable::from((3, 2)),
-        ));
+        );
         assert_eq!(
             Ok((expected_operands, expected_operands_mem_addresses)),
             vm.compute_operands(&instruction)
@@ -3173,7 +3280,6 @@ mod tests {
         let mut builtin = BitwiseBuiltinRunner::new(true, 256);
         builtin.base = Some(relocatable!(2, 0));
         let mut vm = VirtualMachine::new(bigint!(127), Vec::new(), false);
-        vm.accessed_addresses = Some(Vec::new());
         vm.builtin_runners
             .push((String::from("bitwise"), Box::new(builtin)));
         vm.run_context.ap = MaybeRelocatable::from((1, 9));
@@ -3250,11 +3356,11 @@ mod tests {
             op0: MaybeRelocatable::from((2, 0)),
             op1: MaybeRelocatable::from(bigint!(8)),
         };
-        let expected_operands_mem_addresses = Some(OperandsAddresses(
+        let expected_operands_mem_addresses = OperandsAddresses(
             MaybeRelocatable::from((1, 9)),
             MaybeRelocatable::from((1, 3)),
             MaybeRelocatable::from((2, 2)),
-        ));
+        );
         assert_eq!(
             Ok((expected_operands, expected_operands_mem_addresses)),
             vm.compute_operands(&instruction)
@@ -3593,6 +3699,7 @@ mod tests {
                 "memory[ap] = segments.add()".as_bytes().to_vec(),
                 HashMap::new(),
                 ApTracking::new(),
+                Vec::new(),
             )],
         );
 
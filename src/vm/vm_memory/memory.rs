@@ -1,18 +1,27 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
+use crate::math_utils::as_int;
 use crate::types::relocatable::Relocatable;
 use crate::vm::errors::memory_errors::MemoryError;
 use crate::vm::errors::vm_errors::VirtualMachineError;
 use crate::{types::relocatable::MaybeRelocatable, utils::from_relocatable_to_indexes};
 use num_bigint::BigInt;
 
+///Rule used to validate the memory cells of a segment.
+///Returns the addresses validated by the rule, which may include cells other than the given one
 pub struct ValidationRule(
-    pub Box<dyn Fn(&Memory, &MaybeRelocatable) -> Result<MaybeRelocatable, MemoryError>>,
+    pub Box<dyn Fn(&Memory, &MaybeRelocatable) -> Result<Vec<MaybeRelocatable>, MemoryError>>,
 );
 pub struct Memory {
     pub data: Vec<Vec<Option<MaybeRelocatable>>>,
     pub validated_addresses: HashSet<MaybeRelocatable>,
-    pub validation_rules: HashMap<usize, ValidationRule>,
+    pub validation_rules: HashMap<usize, Vec<ValidationRule>>,
+    //If true, cells are not validated on insertion, but when validate_existing_memory is called,
+    //which CairoRunner::end_run does at the end of the run
+    pub lazy_validation: bool,
+    //Bitset of the accessed cells of each segment, one bit per offset
+    accessed_addresses: Vec<Vec<u64>>,
 }
 
 impl Memory {
@@ -21,6 +30,8 @@ impl Memory {
             data: Vec::<Vec<Option<MaybeRelocatable>>>::new(),
             validated_addresses: HashSet::<MaybeRelocatable>::new(),
             validation_rules: HashMap::new(),
+            lazy_validation: false,
+            accessed_addresses: Vec::new(),
         }
     }
     ///Inserts an MaybeRelocatable value into an address given by a MaybeRelocatable::Relocatable
@@ -70,6 +81,9 @@ impl Memory {
         } else {
             return Err(MemoryError::AddressNotRelocatable);
         }
+        if self.lazy_validation {
+            return Ok(());
+        }
         self.validate_memory_cell(key)
     }
 
@@ -100,18 +114,93 @@ impl Memory {
         }
     }
 
+    //Gets the value from memory address.
+    //If the value is an MaybeRelocatable::RelocatableValue(Relocatable) return &Relocatable
+    //else raises Err
+    pub fn get_relocatable(&self, key: &Relocatable) -> Result<&Relocatable, VirtualMachineError> {
+        match self.get(&MaybeRelocatable::from((key.segment_index, key.offset))) {
+            Ok(Some(MaybeRelocatable::RelocatableValue(rel))) => Ok(rel),
+            Ok(_) => Err(VirtualMachineError::ExpectedRelocatable(
+                MaybeRelocatable::from((key.segment_index, key.offset)),
+            )),
+            Err(memory_error) => Err(VirtualMachineError::MemoryError(memory_error)),
+        }
+    }
+
+    ///Marks the memory cell at address as accessed by the vm
+    pub fn mark_as_accessed(&mut self, address: &Relocatable) -> Result<(), MemoryError> {
+        let (segment_index, offset) = from_relocatable_to_indexes(address.clone());
+        if segment_index >= self.data.len() {
+            return Err(MemoryError::UnallocatedSegment(
+                segment_index,
+                self.data.len(),
+            ));
+        }
+        if self.accessed_addresses.len() <= segment_index {
+            self.accessed_addresses
+                .resize(segment_index + 1, Vec::new());
+        }
+        let segment = &mut self.accessed_addresses[segment_index];
+        let (word, bit) = (offset / 64, offset % 64);
+        if segment.len() <= word {
+            segment.resize(word + 1, 0);
+        }
+        segment[word] |= 1 << bit;
+        Ok(())
+    }
+
+    ///Returns true if the memory cell at address was accessed by the vm
+    pub fn is_accessed(&self, address: &Relocatable) -> bool {
+        let (segment_index, offset) = from_relocatable_to_indexes(address.clone());
+        self.accessed_addresses
+            .get(segment_index)
+            .and_then(|segment| segment.get(offset / 64))
+            .map_or(false, |word| word & (1 << (offset % 64)) != 0)
+    }
+
+    ///Returns the number of accessed cells of the given segment, or None if the segment doesn't exist
+    pub fn get_amount_of_accessed_addresses_for_segment(
+        &self,
+        segment_index: usize,
+    ) -> Option<usize> {
+        if segment_index >= self.data.len() {
+            return None;
+        }
+        Some(
+            self.accessed_addresses
+                .get(segment_index)
+                .map_or(0, |segment| {
+                    segment.iter().map(|word| word.count_ones() as usize).sum()
+                }),
+        )
+    }
+
+    ///Adds a validation rule for the given segment. Segments can have more than one rule,
+    ///and every rule of the segment is applied to each of its cells
     pub fn add_validation_rule(&mut self, segment_index: usize, rule: ValidationRule) {
-        self.validation_rules.insert(segment_index, rule);
+        self.validation_rules
+            .entry(segment_index)
+            .or_default()
+            .push(rule);
     }
 
     fn validate_memory_cell(&mut self, address: &MaybeRelocatable) -> Result<(), MemoryError> {
         if let &MaybeRelocatable::RelocatableValue(ref rel_addr) = address {
-            if !self.validated_addresses.contains(address) {
-                for (index, validation_rule) in self.validation_rules.iter() {
-                    if &rel_addr.segment_index == index {
-                        self.validated_addresses
-                            .insert(validation_rule.0(self, address)?);
+            if self.validated_addresses.contains(address) {
+                return Ok(());
+            }
+            let mut validated_addresses = Vec::new();
+            if let Some(rules) = self.validation_rules.get(&rel_addr.segment_index) {
+                for rule in rules {
+                    validated_addresses.extend(rule.0(self, address)?);
+                }
+            }
+            for validated_address in validated_addresses {
+                match validated_address {
+                    MaybeRelocatable::RelocatableValue(_) => {
+                        self.validated_addresses.insert(validated_address);
                     }
+                    _ => return Err(MemoryError::AddressNotRelocatable),
                 }
             }
             Ok(())
@@ -119,12 +208,14 @@ impl Memory {
             Err(MemoryError::AddressNotRelocatable)
         }
     }
-    ///Applies validation_rules to the current memory
-    //Should be called during initialization, as None values will raise a FoundNonInt error
+    ///Applies validation_rules to the current memory, skipping the cells that were already validated
+    //Memory gaps (None values) are not validated
     pub fn validate_existing_memory(&mut self) -> Result<(), MemoryError> {
         for i in 0..self.data.len() {
             for j in 0..self.data[i].len() {
-                self.validate_memory_cell(&MaybeRelocatable::from((i, j)))?;
+                if self.data[i][j].is_some() {
+                    self.validate_memory_cell(&MaybeRelocatable::from((i, j)))?;
+                }
             }
         }
         Ok(())
@@ -145,6 +236,70 @@ impl Memory {
     }
 }
 
+impl Memory {
+    ///Writes the contents of each memory segment, naming the segments found in segment_names.
+    ///Consecutive memory gaps are compressed into a single line.
+    ///If prime is not None, integers are also shown as signed values when they differ.
+    pub fn dump(
+        &self,
+        out: &mut dyn fmt::Write,
+        segment_names: &HashMap<usize, String>,
+        prime: Option<&BigInt>,
+    ) -> fmt::Result {
+        for (segment_index, segment) in self.data.iter().enumerate() {
+            match segment_names.get(&segment_index) {
+                Some(name) => writeln!(out, "segment {} ({}):", segment_index, name)?,
+                None => writeln!(out, "segment {}:", segment_index)?,
+            }
+            let mut offset = 0;
+            while offset < segment.len() {
+                match &segment[offset] {
+                    Some(MaybeRelocatable::Int(num)) => {
+                        write!(out, "  {}:{} = {}", segment_index, offset, num)?;
+                        match prime.map(|prime| as_int(num, prime)) {
+                            Some(signed_num) if &signed_num != num => {
+                                writeln!(out, " ({})", signed_num)?
+                            }
+                            _ => writeln!(out)?,
+                        }
+                        offset += 1;
+                    }
+                    Some(MaybeRelocatable::RelocatableValue(rel)) => {
+                        writeln!(
+                            out,
+                            "  {}:{} = {}:{}",
+                            segment_index, offset, rel.segment_index, rel.offset
+                        )?;
+                        offset += 1;
+                    }
+                    None => {
+                        let gap_start = offset;
+                        while offset < segment.len() && segment[offset].is_none() {
+                            offset += 1;
+                        }
+                        writeln!(
+                            out,
+                            "  {}:{}..{}:{} = <{} empty>",
+                            segment_index,
+                            gap_start,
+                            segment_index,
+                            offset - 1,
+                            offset - gap_start
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.dump(f, &HashMap::new(), None)
+    }
+}
+
 impl Default for Memory {
     fn default() -> Self {
         Self::new()
@@ -312,7 +467,7 @@ mod memory_tests {
         let mut segments = MemorySegmentManager::new();
         let mut memory = Memory::new();
         builtin.initialize_segments(&mut segments, &mut memory);
-        builtin.add_validation_rule(&mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
         for _ in 0..3 {
             segments.add(&mut memory, None);
         }
@@ -342,7 +497,7 @@ mod memory_tests {
                 &MaybeRelocatable::from(bigint!(-10)),
             )
             .unwrap();
-        builtin.add_validation_rule(&mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
         let error = memory.validate_existing_memory();
         assert_eq!(error, Err(MemoryError::NumOutOfBounds));
         assert_eq!(
@@ -365,7 +520,7 @@ mod memory_tests {
                 &MaybeRelocatable::from((1, 4)),
             )
             .unwrap();
-        builtin.add_validation_rule(&mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
         let error = memory.validate_existing_memory();
         assert_eq!(error, Err(MemoryError::FoundNonInt));
         assert_eq!(
@@ -387,10 +542,162 @@ mod memory_tests {
                 &MaybeRelocatable::from(bigint!(-45)),
             )
             .unwrap();
-        builtin.add_validation_rule(&mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
         assert_eq!(memory.validate_existing_memory(), Ok(()));
     }
 
+    #[test]
+    fn mark_as_accessed_and_count() {
+        let mut memory = Memory::new();
+        memory.data.push(Vec::new());
+        memory.data.push(Vec::new());
+        for offset in [0, 3, 63, 64, 130, 3] {
+            assert_eq!(
+                memory.mark_as_accessed(&Relocatable::from((1, offset))),
+                Ok(())
+            );
+        }
+        assert!(memory.is_accessed(&Relocatable::from((1, 64))));
+        assert!(!memory.is_accessed(&Relocatable::from((1, 65))));
+        assert!(!memory.is_accessed(&Relocatable::from((0, 0))));
+        assert_eq!(
+            memory.get_amount_of_accessed_addresses_for_segment(0),
+            Some(0)
+        );
+        assert_eq!(
+            memory.get_amount_of_accessed_addresses_for_segment(1),
+            Some(5)
+        );
+        assert_eq!(memory.get_amount_of_accessed_addresses_for_segment(2), None);
+    }
+
+    #[test]
+    fn mark_as_accessed_non_allocated_segment() {
+        let mut memory = Memory::new();
+        assert_eq!(
+            memory.mark_as_accessed(&Relocatable::from((0, 0))),
+            Err(MemoryError::UnallocatedSegment(0, 0))
+        );
+    }
+
+    //Validates the cells holding even numbers, together with the cell that follows them
+    fn even_pair_rule() -> ValidationRule {
+        ValidationRule(Box::new(|memory, address| match memory.get(address)? {
+            Some(MaybeRelocatable::Int(num)) if num % 2 == BigInt::from(0) => {
+                Ok(vec![address.clone(), address.add_usize_mod(1, None)])
+            }
+            _ => Ok(Vec::new()),
+        }))
+    }
+
+    #[test]
+    fn validation_rule_validates_several_addresses() {
+        let mut memory = Memory::new();
+        memory.data.push(Vec::new());
+        memory.add_validation_rule(0, even_pair_rule());
+        memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(4)),
+            )
+            .unwrap();
+        assert!(memory
+            .validated_addresses
+            .contains(&MaybeRelocatable::from((0, 0))));
+        assert!(memory
+            .validated_addresses
+            .contains(&MaybeRelocatable::from((0, 1))));
+        assert_eq!(memory.validated_addresses.len(), 2);
+    }
+
+    #[test]
+    fn multiple_validation_rules_for_one_segment() {
+        let mut builtin = RangeCheckBuiltinRunner::new(true, bigint!(8), 8);
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        builtin.initialize_segments(&mut segments, &mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
+        memory.add_validation_rule(0, even_pair_rule());
+        assert_eq!(memory.validation_rules[&0].len(), 2);
+        //Both rules are applied, the range check one rejects the value
+        assert_eq!(
+            memory.insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(-2)),
+            ),
+            Err(MemoryError::NumOutOfBounds)
+        );
+        memory
+            .insert(
+                &MaybeRelocatable::from((0, 2)),
+                &MaybeRelocatable::from(bigint!(6)),
+            )
+            .unwrap();
+        assert!(memory
+            .validated_addresses
+            .contains(&MaybeRelocatable::from((0, 2))));
+        assert!(memory
+            .validated_addresses
+            .contains(&MaybeRelocatable::from((0, 3))));
+    }
+
+    #[test]
+    fn lazy_validation_on_validate_existing_memory() {
+        let mut builtin = RangeCheckBuiltinRunner::new(true, bigint!(8), 8);
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        memory.lazy_validation = true;
+        builtin.initialize_segments(&mut segments, &mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
+        memory
+            .insert(
+                &MaybeRelocatable::from((0, 1)),
+                &MaybeRelocatable::from(bigint!(-1)),
+            )
+            .unwrap();
+        assert!(memory.validated_addresses.is_empty());
+        assert_eq!(
+            memory.validate_existing_memory(),
+            Err(MemoryError::NumOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn dump_named_segments_with_gaps() {
+        let mut memory = memory_from(
+            vec![
+                (
+                    MaybeRelocatable::from((0, 0)),
+                    MaybeRelocatable::from(bigint!(5)),
+                ),
+                (
+                    MaybeRelocatable::from((0, 1)),
+                    MaybeRelocatable::from(bigint!(15)),
+                ),
+                (
+                    MaybeRelocatable::from((1, 3)),
+                    MaybeRelocatable::from((0, 1)),
+                ),
+            ],
+            2,
+        )
+        .unwrap();
+        memory.data.push(Vec::new());
+        let segment_names = HashMap::from([(0, String::from("program"))]);
+        let mut dump = String::new();
+        memory
+            .dump(&mut dump, &segment_names, Some(&bigint!(17)))
+            .unwrap();
+        assert_eq!(
+            dump,
+            "segment 0 (program):\n  0:0 = 5\n  0:1 = 15 (-2)\nsegment 1:\n  1:0..1:2 = <3 empty>\n  1:3 = 0:1\nsegment 2:\n"
+        );
+        assert_eq!(
+            memory.to_string(),
+            "segment 0:\n  0:0 = 5\n  0:1 = 15\nsegment 1:\n  1:0..1:2 = <3 empty>\n  1:3 = 0:1\nsegment 2:\n"
+        );
+    }
+
     #[test]
     fn get_integer_valid() {
         let mut segments = MemorySegmentManager::new();
@@ -426,4 +733,40 @@ mod memory_tests {
             ))
         );
     }
+
+    #[test]
+    fn get_relocatable_valid() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory, None);
+        memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from((0, 10)),
+            )
+            .unwrap();
+        assert_eq!(
+            memory.get_relocatable(&Relocatable::from((0, 0))),
+            Ok(&Relocatable::from((0, 10)))
+        );
+    }
+
+    #[test]
+    fn get_relocatable_invalid_expected_relocatable() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory, None);
+        memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(bigint!(10)),
+            )
+            .unwrap();
+        assert_eq!(
+            memory.get_relocatable(&Relocatable::from((0, 0))),
+            Err(VirtualMachineError::ExpectedRelocatable(
+                MaybeRelocatable::from((0, 0))
+            ))
+        );
+    }
 }
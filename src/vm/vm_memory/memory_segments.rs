@@ -1,24 +1,82 @@
 use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 use crate::vm::errors::memory_errors::MemoryError;
 use crate::vm::vm_memory::memory::Memory;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use std::collections::HashMap;
+
+///Argument for segments.gen_arg and segments.write_arg: either a single value, or a (possibly
+///nested) list of values which is written into its own segment
+#[derive(Debug, PartialEq, Clone)]
+pub enum CairoArg {
+    Single(MaybeRelocatable),
+    Array(Vec<CairoArg>),
+}
+
+impl From<MaybeRelocatable> for CairoArg {
+    fn from(value: MaybeRelocatable) -> Self {
+        CairoArg::Single(value)
+    }
+}
+
+impl From<BigInt> for CairoArg {
+    fn from(value: BigInt) -> Self {
+        CairoArg::Single(MaybeRelocatable::from(value))
+    }
+}
+
+impl From<Relocatable> for CairoArg {
+    fn from(value: Relocatable) -> Self {
+        CairoArg::Single(MaybeRelocatable::RelocatableValue(value))
+    }
+}
+
+impl<T: Into<CairoArg>> From<Vec<T>> for CairoArg {
+    fn from(values: Vec<T>) -> Self {
+        CairoArg::Array(values.into_iter().map(Into::into).collect())
+    }
+}
 
 pub struct MemorySegmentManager {
     pub num_segments: usize,
     pub segment_used_sizes: Option<Vec<usize>>,
+    //Sizes of the finalized segments, which may be bigger than their used sizes
+    pub segment_sizes: HashMap<usize, usize>,
+    //Offsets (and page ids) of the public memory cells of each finalized segment
+    pub public_memory_offsets: HashMap<usize, Vec<(usize, usize)>>,
 }
 
 impl MemorySegmentManager {
     ///Adds a new segment and returns its starting location as a RelocatableValue.
     ///If size is not None the segment is finalized with the given size. (size will be always none for initialization)
-    pub fn add(&mut self, memory: &mut Memory, _size: Option<usize>) -> Relocatable {
+    pub fn add(&mut self, memory: &mut Memory, size: Option<usize>) -> Relocatable {
         let segment_index = self.num_segments;
         self.num_segments += 1;
         memory.data.push(Vec::new());
+        if size.is_some() {
+            self.finalize(segment_index, size, &[]);
+        }
         Relocatable {
             segment_index,
             offset: 0,
         }
     }
+
+    ///Writes the following information for the given segment:
+    /// * size - The size of the segment (to be used in relocate_segments).
+    /// * public_memory - A list of offsets and page ids for memory cells that will be considered as public memory.
+    pub fn finalize(
+        &mut self,
+        segment_index: usize,
+        size: Option<usize>,
+        public_memory: &[(usize, usize)],
+    ) {
+        if let Some(size) = size {
+            self.segment_sizes.insert(segment_index, size);
+        }
+        self.public_memory_offsets
+            .insert(segment_index, public_memory.to_vec());
+    }
     ///Writes data into the memory at address ptr and returns the first address after the data.
     pub fn load_data(
         &mut self,
@@ -32,23 +90,123 @@ impl MemorySegmentManager {
         Ok(ptr.add_usize_mod(data.len(), None))
     }
 
+    ///Converts arg into a value that can be stored in a memory cell.
+    ///Lists are written into a new segment (recursively) and replaced by a pointer to it.
+    ///If prime is not None, integers are reduced modulo prime.
+    pub fn gen_arg(
+        &mut self,
+        memory: &mut Memory,
+        arg: &CairoArg,
+        prime: Option<&BigInt>,
+    ) -> Result<MaybeRelocatable, MemoryError> {
+        match arg {
+            CairoArg::Single(MaybeRelocatable::Int(value)) => match prime {
+                Some(prime) => Ok(MaybeRelocatable::Int(value.mod_floor(prime))),
+                None => Ok(MaybeRelocatable::Int(value.clone())),
+            },
+            CairoArg::Single(value) => Ok(value.clone()),
+            CairoArg::Array(_) => {
+                let base = MaybeRelocatable::RelocatableValue(self.add(memory, None));
+                self.write_arg(memory, &base, arg, prime)?;
+                Ok(base)
+            }
+        }
+    }
+
+    ///Writes arg into the memory at address ptr, generating segments for nested lists, and
+    ///returns the first address after the written data.
+    pub fn write_arg(
+        &mut self,
+        memory: &mut Memory,
+        ptr: &MaybeRelocatable,
+        arg: &CairoArg,
+        prime: Option<&BigInt>,
+    ) -> Result<MaybeRelocatable, MemoryError> {
+        let data = match arg {
+            CairoArg::Single(_) => vec![self.gen_arg(memory, arg, prime)?],
+            CairoArg::Array(args) => args
+                .iter()
+                .map(|arg| self.gen_arg(memory, arg, prime))
+                .collect::<Result<Vec<MaybeRelocatable>, MemoryError>>()?,
+        };
+        self.load_data(memory, ptr, data)
+    }
+
     pub fn new() -> MemorySegmentManager {
         MemorySegmentManager {
             num_segments: 0,
             segment_used_sizes: None,
+            segment_sizes: HashMap::new(),
+            public_memory_offsets: HashMap::new(),
         }
     }
 
-    ///Calculates the size (number of non-none elements) of each memory segment
-    pub fn compute_effective_sizes(&mut self, memory: &Memory) {
-        if self.segment_used_sizes != None {
-            return;
+    ///Calculates the used size (highest accessed offset + 1) of each memory segment.
+    ///The sizes are recomputed on every call, so they are never stale if memory grew since the last call
+    pub fn compute_effective_sizes(&mut self, memory: &Memory) -> &Vec<usize> {
+        self.segment_used_sizes
+            .insert(memory.data.iter().map(|segment| segment.len()).collect())
+    }
+
+    ///Returns the used size of the given segment, as computed by the last call to compute_effective_sizes
+    pub fn get_segment_used_size(&self, segment_index: usize) -> Option<usize> {
+        self.segment_used_sizes
+            .as_ref()?
+            .get(segment_index)
+            .copied()
+    }
+
+    ///Returns the size of the given segment: the size it was finalized with, or its used size otherwise
+    pub fn get_segment_size(&self, segment_index: usize) -> Option<usize> {
+        self.segment_sizes
+            .get(&segment_index)
+            .copied()
+            .or_else(|| self.get_segment_used_size(segment_index))
+    }
+
+    ///Returns the number of memory cells that were not accessed by the vm, across all segments
+    ///but the ones in skip_segments (ie: builtin segments, whose cells are all considered accessed)
+    pub fn get_memory_holes(
+        &mut self,
+        memory: &Memory,
+        skip_segments: &[usize],
+    ) -> Result<usize, MemoryError> {
+        self.compute_effective_sizes(memory);
+        let mut memory_holes = 0;
+        for segment_index in 0..self.num_segments {
+            if skip_segments.contains(&segment_index) {
+                continue;
+            }
+            let accessed_amount = memory
+                .get_amount_of_accessed_addresses_for_segment(segment_index)
+                .ok_or(MemoryError::UnallocatedSegment(
+                    segment_index,
+                    memory.data.len(),
+                ))?;
+            //Accessed cells outside the used size of the segment are not holes
+            let segment_size = self.get_segment_size(segment_index).unwrap_or(0);
+            memory_holes += segment_size.saturating_sub(accessed_amount);
         }
-        let mut segment_used_sizes = Vec::new();
-        for segment in memory.data.iter() {
-            segment_used_sizes.push(segment.len());
+        Ok(memory_holes)
+    }
+
+    ///Returns the relocated addresses of the public memory cells, together with their page ids
+    pub fn get_public_memory_addresses(
+        &self,
+        segment_offsets: &[usize],
+    ) -> Result<Vec<(usize, usize)>, MemoryError> {
+        let mut addresses = Vec::new();
+        for segment_index in 0..self.num_segments {
+            let segment_start = segment_offsets
+                .get(segment_index)
+                .ok_or(MemoryError::MalformedPublicMemory)?;
+            if let Some(public_memory) = self.public_memory_offsets.get(&segment_index) {
+                for (offset, page_id) in public_memory {
+                    addresses.push((segment_start + offset, *page_id));
+                }
+            }
         }
-        self.segment_used_sizes = Some(segment_used_sizes);
+        Ok(addresses)
     }
 
     ///Returns a vector that contains the first relocated address of each memory segment
@@ -57,7 +215,8 @@ impl MemorySegmentManager {
         let mut relocation_table = vec![first_addr];
         match &self.segment_used_sizes {
             Some(segment_used_sizes) => {
-                for (i, size) in segment_used_sizes.iter().enumerate() {
+                for (i, used_size) in segment_used_sizes.iter().enumerate() {
+                    let size = self.segment_sizes.get(&i).unwrap_or(used_size);
                     relocation_table.push(relocation_table[i] + size);
                 }
             }
@@ -78,7 +237,6 @@ impl Default for MemorySegmentManager {
 #[cfg(test)]
 mod tests {
     use crate::{bigint, relocatable};
-    use num_bigint::BigInt;
     use num_traits::FromPrimitive;
 
     use super::*;
@@ -377,4 +535,210 @@ mod tests {
             vec![1, 4, 7, 63, 141]
         )
     }
+
+    #[test]
+    fn compute_effective_sizes_after_memory_grows() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory, None);
+        memory
+            .insert(
+                &MaybeRelocatable::from((0, 1)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+        assert_eq!(segments.compute_effective_sizes(&memory), &vec![2]);
+        memory
+            .insert(
+                &MaybeRelocatable::from((0, 4)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+        assert_eq!(segments.compute_effective_sizes(&memory), &vec![5]);
+        assert_eq!(segments.get_segment_used_size(0), Some(5));
+    }
+
+    #[test]
+    fn get_segment_size_of_finalized_segment() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory, None);
+        segments.add(&mut memory, Some(10));
+        memory
+            .insert(
+                &MaybeRelocatable::from((0, 2)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+        memory
+            .insert(
+                &MaybeRelocatable::from((1, 2)),
+                &MaybeRelocatable::from(bigint!(1)),
+            )
+            .unwrap();
+        assert_eq!(segments.get_segment_used_size(1), None);
+        segments.compute_effective_sizes(&memory);
+        assert_eq!(segments.get_segment_used_size(1), Some(3));
+        assert_eq!(segments.get_segment_size(0), Some(3));
+        assert_eq!(segments.get_segment_size(1), Some(10));
+        assert_eq!(segments.get_segment_size(2), None);
+    }
+
+    #[test]
+    fn relocate_segments_with_finalized_size() {
+        let mut segments = MemorySegmentManager::new();
+        segments.segment_used_sizes = Some(vec![3, 3, 5]);
+        segments.finalize(1, Some(8), &[]);
+        assert_eq!(segments.relocate_segments(), Ok(vec![1, 4, 12]));
+    }
+
+    #[test]
+    fn get_public_memory_addresses_of_finalized_segments() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        for _ in 0..3 {
+            segments.add(&mut memory, None);
+        }
+        segments.finalize(0, None, &[(0, 0), (2, 0)]);
+        segments.finalize(2, Some(4), &[(1, 1)]);
+        assert_eq!(
+            segments.get_public_memory_addresses(&[1, 4, 7]),
+            Ok(vec![(1, 0), (3, 0), (8, 1)])
+        );
+        assert_eq!(
+            segments.get_public_memory_addresses(&[1, 4]),
+            Err(MemoryError::MalformedPublicMemory)
+        );
+    }
+
+    #[test]
+    fn get_memory_holes_skipping_segments() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        for _ in 0..3 {
+            segments.add(&mut memory, None);
+        }
+        for (segment_index, offset) in [(0, 0), (0, 4), (1, 0), (2, 3)] {
+            memory
+                .insert(
+                    &MaybeRelocatable::from((segment_index, offset)),
+                    &MaybeRelocatable::from(bigint!(1)),
+                )
+                .unwrap();
+        }
+        memory.mark_as_accessed(&relocatable!(0, 0)).unwrap();
+        memory.mark_as_accessed(&relocatable!(0, 4)).unwrap();
+        memory.mark_as_accessed(&relocatable!(1, 0)).unwrap();
+        assert_eq!(segments.get_memory_holes(&memory, &[]), Ok(7));
+        assert_eq!(segments.get_memory_holes(&memory, &[2]), Ok(3));
+    }
+
+    #[test]
+    fn gen_arg_single_values() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+
+        assert_eq!(
+            segments.gen_arg(&mut memory, &CairoArg::from(bigint!(-1)), Some(&bigint!(7))),
+            Ok(MaybeRelocatable::from(bigint!(6)))
+        );
+        assert_eq!(
+            segments.gen_arg(&mut memory, &CairoArg::from(bigint!(-1)), None),
+            Ok(MaybeRelocatable::from(bigint!(-1)))
+        );
+        assert_eq!(
+            segments.gen_arg(&mut memory, &CairoArg::from(relocatable!(3, 4)), None),
+            Ok(MaybeRelocatable::from((3, 4)))
+        );
+        //No segments are created for single values
+        assert_eq!(segments.num_segments, 0);
+    }
+
+    #[test]
+    fn gen_arg_list() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        let arg = CairoArg::from(vec![bigint!(1), bigint!(2)]);
+
+        assert_eq!(
+            segments.gen_arg(&mut memory, &arg, None),
+            Ok(MaybeRelocatable::from((0, 0)))
+        );
+        assert_eq!(
+            memory.data[0],
+            vec![
+                Some(MaybeRelocatable::from(bigint!(1))),
+                Some(MaybeRelocatable::from(bigint!(2)))
+            ]
+        );
+    }
+
+    #[test]
+    fn gen_arg_nested_lists() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        //[5, [1, 2], [], (0, 0)]
+        let arg = CairoArg::Array(vec![
+            CairoArg::from(bigint!(5)),
+            CairoArg::from(vec![bigint!(1), bigint!(2)]),
+            CairoArg::Array(Vec::new()),
+            CairoArg::from(relocatable!(0, 0)),
+        ]);
+
+        assert_eq!(
+            segments.gen_arg(&mut memory, &arg, None),
+            Ok(MaybeRelocatable::from((0, 0)))
+        );
+        assert_eq!(segments.num_segments, 3);
+        assert_eq!(
+            memory.data[0],
+            vec![
+                Some(MaybeRelocatable::from(bigint!(5))),
+                Some(MaybeRelocatable::from((1, 0))),
+                Some(MaybeRelocatable::from((2, 0))),
+                Some(MaybeRelocatable::from((0, 0))),
+            ]
+        );
+        assert_eq!(
+            memory.data[1],
+            vec![
+                Some(MaybeRelocatable::from(bigint!(1))),
+                Some(MaybeRelocatable::from(bigint!(2)))
+            ]
+        );
+        assert!(memory.data[2].is_empty());
+    }
+
+    #[test]
+    fn write_arg_at_pointer() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory, None);
+        let arg = CairoArg::Array(vec![
+            CairoArg::from(bigint!(9)),
+            CairoArg::from(vec![bigint!(3)]),
+        ]);
+
+        assert_eq!(
+            segments.write_arg(
+                &mut memory,
+                &MaybeRelocatable::from((0, 2)),
+                &arg,
+                Some(&bigint!(7))
+            ),
+            Ok(MaybeRelocatable::from((0, 4)))
+        );
+        assert_eq!(
+            memory.get(&MaybeRelocatable::from((0, 2))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(2))))
+        );
+        assert_eq!(
+            memory.get(&MaybeRelocatable::from((0, 3))),
+            Ok(Some(&MaybeRelocatable::from((1, 0))))
+        );
+        assert_eq!(
+            memory.get(&MaybeRelocatable::from((1, 0))),
+            Ok(Some(&MaybeRelocatable::from(bigint!(3))))
+        );
+    }
 }
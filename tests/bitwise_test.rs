@@ -9,7 +9,7 @@ use cleopatra_cairo::{
 fn bitwise_integration_test() {
     let program = Program::new(Path::new("cairo_programs/bitwise_builtin_test.json"))
         .expect("Failed to deserialize program");
-    let mut cairo_runner = CairoRunner::new(&program, true);
+    let mut cairo_runner = CairoRunner::new(&program, true).unwrap();
     cairo_runner.initialize_segments(None);
     let end = cairo_runner.initialize_main_entrypoint().unwrap();
 
@@ -1,203 +1,329 @@
 use std::path::Path;
 
-use cleopatra_cairo::cairo_run;
+use cleopatra_cairo::cairo_run::{self, CairoRunConfig};
 
 #[test]
 fn cairo_run_test() {
-    cairo_run::cairo_run(Path::new("cairo_programs/fibonacci.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/fibonacci.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_bitwise_output() {
-    cairo_run::cairo_run(Path::new("cairo_programs/bitwise_output.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/bitwise_output.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
+}
+
+#[test]
+fn cairo_run_bitwise_output_secure_run() {
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/bitwise_output.json"),
+        &CairoRunConfig {
+            secure_run: true,
+            ..CairoRunConfig::default()
+        },
+    )
+    .expect("Couldn't run program");
+}
+
+#[test]
+fn cairo_run_output_and_range_check_secure_run() {
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/manually_compiled/valid_program_b.json"),
+        &CairoRunConfig {
+            secure_run: true,
+            ..CairoRunConfig::default()
+        },
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_bitwise_recursion() {
-    cairo_run::cairo_run(Path::new("cairo_programs/bitwise_recursion.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/bitwise_recursion.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_integration() {
-    cairo_run::cairo_run(Path::new("cairo_programs/integration.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/integration.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_integration_with_alloc_locals() {
     cairo_run::cairo_run(
         Path::new("cairo_programs/integration_with_alloc_locals.json"),
-        false,
+        &CairoRunConfig::default(),
     )
     .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_compare_arrays() {
-    cairo_run::cairo_run(Path::new("cairo_programs/compare_arrays.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/compare_arrays.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_compare_greater_array() {
     cairo_run::cairo_run(
         Path::new("cairo_programs/compare_greater_array.json"),
-        false,
+        &CairoRunConfig::default(),
     )
     .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_compare_lesser_array() {
-    cairo_run::cairo_run(Path::new("cairo_programs/compare_lesser_array.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/compare_lesser_array.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_assert_le_felt_hint() {
-    cairo_run::cairo_run(Path::new("cairo_programs/assert_le_felt_hint.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/assert_le_felt_hint.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_assert_250_bit_element_array() {
     cairo_run::cairo_run(
         Path::new("cairo_programs/assert_250_bit_element_array.json"),
-        false,
+        &CairoRunConfig::default(),
     )
     .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_abs_value() {
-    cairo_run::cairo_run(Path::new("cairo_programs/abs_value_array.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/abs_value_array.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_compare_different_arrays() {
     cairo_run::cairo_run(
         Path::new("cairo_programs/compare_different_arrays.json"),
-        false,
+        &CairoRunConfig::default(),
     )
     .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_assert_nn() {
-    cairo_run::cairo_run(Path::new("cairo_programs/assert_nn.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/assert_nn.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_sqrt() {
-    cairo_run::cairo_run(Path::new("cairo_programs/sqrt.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/sqrt.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_assert_not_zero() {
-    cairo_run::cairo_run(Path::new("cairo_programs/assert_not_zero.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/assert_not_zero.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_split_int() {
-    cairo_run::cairo_run(Path::new("cairo_programs/split_int.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/split_int.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_split_int_big() {
-    cairo_run::cairo_run(Path::new("cairo_programs/split_int_big.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/split_int_big.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_split_felt() {
-    cairo_run::cairo_run(Path::new("cairo_programs/split_felt.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/split_felt.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_is_le_felt() {
-    cairo_run::cairo_run(Path::new("cairo_programs/math_cmp_is_le_felt.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/math_cmp_is_le_felt.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_unsigned_div_rem() {
-    cairo_run::cairo_run(Path::new("cairo_programs/unsigned_div_rem.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/unsigned_div_rem.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_signed_div_rem() {
-    cairo_run::cairo_run(Path::new("cairo_programs/signed_div_rem.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/signed_div_rem.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_assert_lt_felt() {
-    cairo_run::cairo_run(Path::new("cairo_programs/assert_lt_felt.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/assert_lt_felt.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_memcpy() {
-    cairo_run::cairo_run(Path::new("cairo_programs/memcpy_test.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/memcpy_test.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_memset() {
-    cairo_run::cairo_run(Path::new("cairo_programs/memset.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/memset.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_pow() {
-    cairo_run::cairo_run(Path::new("cairo_programs/pow.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/pow.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_dict() {
-    cairo_run::cairo_run(Path::new("cairo_programs/dict.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/dict.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_dict_update() {
-    cairo_run::cairo_run(Path::new("cairo_programs/dict_update.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/dict_update.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_uint256() {
-    cairo_run::cairo_run(Path::new("cairo_programs/uint256.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/uint256.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
+}
+
+#[test]
+fn cairo_run_uint256_integration() {
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/uint256_integration_test.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_find_element() {
-    cairo_run::cairo_run(Path::new("cairo_programs/find_element.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/find_element.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
+}
+
+#[test]
+fn cairo_run_search_sorted_lower() {
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/search_sorted_lower.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
+}
+
+#[test]
+fn cairo_run_usort() {
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/usort.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_dict_write_bad() {
     assert!(cairo_run::cairo_run(
         Path::new("cairo_programs/bad_programs/bad_dict_new.json"),
-        false
+        &CairoRunConfig::default()
     )
     .is_err());
     let err = cairo_run::cairo_run(
         Path::new("cairo_programs/bad_programs/bad_dict_new.json"),
-        false,
+        &CairoRunConfig::default(),
     )
     .err();
     assert_eq!(
@@ -210,12 +336,12 @@ fn cairo_run_dict_write_bad() {
 fn cairo_run_dict_update_bad() {
     assert!(cairo_run::cairo_run(
         Path::new("cairo_programs/bad_programs/bad_dict_update.json"),
-        false
+        &CairoRunConfig::default()
     )
     .is_err());
     let err = cairo_run::cairo_run(
         Path::new("cairo_programs/bad_programs/bad_dict_update.json"),
-        false,
+        &CairoRunConfig::default(),
     )
     .err();
     assert_eq!(
@@ -226,12 +352,27 @@ fn cairo_run_dict_update_bad() {
 
 #[test]
 fn cairo_run_squash_dict() {
-    cairo_run::cairo_run(Path::new("cairo_programs/squash_dict.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/squash_dict.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
+}
+
+#[test]
+fn cairo_run_dict_squash() {
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/dict_squash.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
 
 #[test]
 fn cairo_run_set_add() {
-    cairo_run::cairo_run(Path::new("cairo_programs/set_add.json"), false)
-        .expect("Couldn't run program");
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/set_add.json"),
+        &CairoRunConfig::default(),
+    )
+    .expect("Couldn't run program");
 }
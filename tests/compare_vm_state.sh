@@ -1,6 +1,7 @@
 #!/usr/bin/env sh
 
 tests_path="../cairo_programs"
+compare="../target/release/cleopatra-compare"
 exit_code=0
 trace=false
 memory=false
@@ -22,7 +23,7 @@ for file in $(ls $tests_path | grep .cairo | sed -E 's/\.cairo//'); do
     path_file="$tests_path/$file"
 
     if $trace; then
-        if ! diff -q $path_file.trace $path_file.cleopatra.trace; then
+        if ! $compare trace $path_file.trace $path_file.cleopatra.trace; then
             echo "Traces for $file differ"
             exit_code=1
             failed_tests=$((failed_tests + 1))
@@ -32,7 +33,7 @@ for file in $(ls $tests_path | grep .cairo | sed -E 's/\.cairo//'); do
     fi
 
     if $memory; then
-        if ! ./memory_comparator.py $path_file.memory $path_file.cleopatra.memory; then
+        if ! $compare memory $path_file.memory $path_file.cleopatra.memory; then
             echo "Memory differs for $file"
             exit_code=1
             failed_tests=$((failed_tests + 1))